@@ -2,9 +2,13 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufWriter, Write};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use serde::{Deserialize, Serialize};
 use serde_json::{self, Value};
 
+use crate::governance::abort_flush::{AbortAndFlush, FlushOutcome, FlushSigner, Flushable};
+use crate::spectral_rights_engine::GuardEngine;
+
 // Define SpectralObject struct aligned with excavated schema.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct SpectralObject {
@@ -85,12 +89,44 @@ struct GovernanceFlags {
     soul_modeling_forbidden: bool,
 }
 
+// Collect every object key in the payload, dotted by nesting path.
+fn metadata_keys(value: &Value, prefix: &str, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                out.push(path.clone());
+                metadata_keys(child, &path, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                metadata_keys(item, prefix, out);
+            }
+        }
+        _ => {}
+    }
+}
+
 // Function to validate and excavate spectral-object from JSON input.
-fn excavate_spectral_object(json_data: &str, flags: &GovernanceFlags) -> Result<SpectralObject, io::Error> {
+// The data_ingest guards run over the payload keys before it is accepted.
+// Soul-modeling is non-waivable, so both they and the governance flag check
+// block in every enforcement mode.
+fn excavate_spectral_object(json_data: &str, flags: &GovernanceFlags, guards: &GuardEngine) -> Result<SpectralObject, io::Error> {
     if !flags.soul_modeling_forbidden {
         return Err(io::Error::new(io::ErrorKind::PermissionDenied, "Governance-Abort-Flush: Soul-modeling forbidden violated."));
     }
-    let obj: SpectralObject = serde_json::from_str(json_data)?;
+    let raw: Value = serde_json::from_str(json_data)?;
+    let mut keys = Vec::new();
+    metadata_keys(&raw, "", &mut keys);
+    let mut violations = Vec::new();
+    if guards.enforce_ingest(&keys, &mut violations).is_err() {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("Governance-Abort-Flush: {}.", violations[0].detail),
+        ));
+    }
+    let obj: SpectralObject = serde_json::from_value(raw)?;
     // Validate scores with mathematical-rigor.
     if obj.stability_score < 0.0 || obj.stability_score > 1.0 {
         return Err(io::Error::new(io::ErrorKind::InvalidData, "Stability-score out of [0,1] bounds."));
@@ -99,6 +135,35 @@ fn excavate_spectral_object(json_data: &str, flags: &GovernanceFlags) -> Result<
     Ok(obj)
 }
 
+// Excavated objects held in memory until written; registered with
+// AbortAndFlush so pending output is dropped on an emergency flush.
+#[derive(Debug, Default)]
+pub struct ExcavationBuffer {
+    pending: Vec<SpectralObject>,
+}
+
+impl ExcavationBuffer {
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl Flushable for ExcavationBuffer {
+    fn flush_name(&self) -> String {
+        "spectral_schema_excavator".to_string()
+    }
+
+    fn abort_and_flush(&mut self, _reason: &str) -> Result<FlushOutcome, String> {
+        let cleared = self.pending.len();
+        self.pending.clear();
+        Ok(FlushOutcome {
+            cleared,
+            quarantined: 0,
+            detail: "pending excavation output dropped".to_string(),
+        })
+    }
+}
+
 // Function to output NDJSON for sniffing-view.
 fn output_ndjson(objects: Vec<SpectralObject>, path: &Path) -> Result<(), io::Error> {
     let file = File::create(path)?;
@@ -131,7 +196,24 @@ fn main() -> Result<(), io::Error> {
         "tags": ["performance"],
         "provenance": {"har_files": [], "trace_dumps": [], "memory_images": [], "tools": []}
     }"#;
-    let obj = excavate_spectral_object(input_json, &flags)?;
-    output_ndjson(vec![obj], Path::new("spectral_sniffing.ndjson"))?;
+    let guards = GuardEngine::bundled()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    let buffer = Arc::new(Mutex::new(ExcavationBuffer::default()));
+    let mut flush = FlushSigner::from_env("GHOSTNET_FLUSH_KEY")
+        .map(AbortAndFlush::new)
+        .unwrap_or_else(AbortAndFlush::unsigned);
+    flush.register(Arc::clone(&buffer));
+    match excavate_spectral_object(input_json, &flags, &guards) {
+        Ok(obj) => buffer.lock().unwrap_or_else(|e| e.into_inner()).pending.push(obj),
+        // A governance refusal aborts the run: drop anything already pending.
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            let report = flush.trigger(&e.to_string());
+            eprintln!("{}", serde_json::to_string_pretty(&report)?);
+            return Err(e);
+        }
+        Err(e) => return Err(e),
+    }
+    let pending = std::mem::take(&mut buffer.lock().unwrap_or_else(|e| e.into_inner()).pending);
+    output_ndjson(pending, Path::new("spectral_sniffing.ndjson"))?;
     Ok(())
 }
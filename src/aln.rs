@@ -0,0 +1,217 @@
+//! ALN policy files (`@rights_spectrum`, `@guard`, `@policy`, `@config`,
+//! `@audit_log`) parsed into typed structs so policy loads at runtime.
+
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub mod lexer;
+pub mod model;
+pub mod parser;
+
+pub use model::{
+    AlnAction, AlnDocument, AuditLogDefinition, EntityFilter, GuardDefinition, GuardEnforcement,
+    GuardEvent, GuardHandler, GuardResponse, GuardRule, PolicyDefinition, PolicyThresholds,
+    RegionFilter, RightDefinition, RuntimeSettings, ViolationResponse,
+};
+pub use parser::{parse_directives, AlnDirective, AlnItem, AlnItemKind, AlnValue, Condition};
+
+/// 1-based line/column in ALN source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum AlnError {
+    #[error("{line}:{column}: syntax error: {message}")]
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("{line}:{column}: {message}")]
+    Semantic {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("cannot read {path}: {message}")]
+    Io { path: String, message: String },
+}
+
+impl AlnError {
+    pub fn syntax(pos: Position, message: impl Into<String>) -> Self {
+        AlnError::Syntax {
+            line: pos.line,
+            column: pos.column,
+            message: message.into(),
+        }
+    }
+
+    pub fn semantic(pos: Position, message: impl Into<String>) -> Self {
+        AlnError::Semantic {
+            line: pos.line,
+            column: pos.column,
+            message: message.into(),
+        }
+    }
+
+    /// Source position, if the error has one.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            AlnError::Syntax { line, column, .. } | AlnError::Semantic { line, column, .. } => {
+                Some(Position {
+                    line: *line,
+                    column: *column,
+                })
+            }
+            AlnError::Io { .. } => None,
+        }
+    }
+}
+
+/// Parse ALN source into the typed model.
+pub fn parse_aln(src: &str) -> Result<AlnDocument, AlnError> {
+    AlnDocument::from_directives(parse_directives(src)?)
+}
+
+/// Read and parse an ALN file.
+pub fn load_aln_file(path: impl AsRef<Path>) -> Result<AlnDocument, AlnError> {
+    let path = path.as_ref();
+    let src = std::fs::read_to_string(path).map_err(|e| AlnError::Io {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    })?;
+    parse_aln(&src)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::EnforcementMode;
+
+    const RIGHTS_ENGINE: &str = include_str!("core/spectral_rights_engine.aln");
+
+    #[test]
+    fn test_parses_rights_engine_policy() {
+        let doc = parse_aln(RIGHTS_ENGINE).expect("shipped policy must parse");
+
+        assert_eq!(doc.module.as_deref(), Some("spectral_rights_engine"));
+        assert_eq!(doc.version.as_deref(), Some("1.0.0"));
+        assert_eq!(doc.rights.len(), 4);
+        let privacy = doc.right("SpectralPrivacy").unwrap();
+        assert_eq!(privacy.violation_action, Some(AlnAction::AbortAndFlush));
+        assert_eq!(privacy.scope.as_deref(), Some("telemetry_channel"));
+
+        let ingest = &doc.guard("soul_modeling_forbidden").unwrap().handlers[0];
+        assert_eq!(ingest.event, GuardEvent::DataIngest);
+        assert_eq!(ingest.inspect.as_deref(), Some("payload.metadata_keys"));
+        assert_eq!(
+            ingest.forbid,
+            [
+                "soul_id",
+                "essence",
+                "moral_rank",
+                "afterlife_status",
+                "consciousness_model",
+                "person_data",
+                "karma_score"
+            ]
+        );
+        assert_eq!(ingest.rules.len(), 1);
+        let rule = &ingest.rules[0];
+        assert_eq!(rule.condition, Condition::Flag("violation_detected".into()));
+        assert_eq!(rule.response.actions(), vec![AlnAction::AbortAndFlush]);
+        assert_eq!(rule.response.log_level.as_deref(), Some("CRITICAL"));
+        assert_eq!(
+            rule.response.notify,
+            ["governance_engine", "companion_space_manager"]
+        );
+
+        let query = &doc.guard("non_resurrection_right").unwrap().handlers[0];
+        assert_eq!(query.event, GuardEvent::DataQuery);
+        let intent = |v: &str| Condition::Eq("intent".into(), AlnValue::Str(v.into()));
+        assert_eq!(
+            query.rules[0].condition,
+            Condition::Or(Box::new(intent("reconstruction")), Box::new(intent("simulation")))
+        );
+        assert!(query.rules[0].response.deny);
+        assert_eq!(
+            query.rules[0].response.actions(),
+            vec![AlnAction::PurgeQueryCache]
+        );
+
+        let policy = doc.policy("example_companion_space_policy").unwrap();
+        let region = &policy.region_filters[0];
+        assert_eq!(region.region, "sacred_grounds");
+        assert_eq!(region.enforce, ["SpectralPrivacy", "SpectralIntegrity"]);
+        assert_eq!(
+            region.thresholds,
+            PolicyThresholds {
+                allow_haunt_density_max: Some(0.10),
+                allow_risk_index_max: Some(0.05),
+            }
+        );
+        assert_eq!(
+            policy.entity_filters[0].entity,
+            "registered_spectral_shadow"
+        );
+
+        assert_eq!(doc.runtime, RuntimeSettings::default());
+        assert_eq!(doc.runtime.enforcement_mode, EnforcementMode::Strict);
+        assert_eq!(doc.audit_logs[0].name, "spectral_rights_ledger");
+        assert!(doc.other.iter().any(|d| d.name == "state_machine"));
+    }
+
+    #[test]
+    fn test_syntax_error_reports_line_and_column() {
+        let err = parse_aln("@module demo\n@guard g {\n    on_event \"data_ingest\" {}\n}\n")
+            .unwrap_err();
+        assert!(matches!(err, AlnError::Syntax { .. }));
+        assert_eq!(
+            err.position(),
+            Some(Position {
+                line: 3,
+                column: 14
+            })
+        );
+        assert!(err.to_string().starts_with("3:14:"));
+    }
+
+    #[test]
+    fn test_semantic_errors_carry_position() {
+        let src = "@config runtime_settings {\n  enforcement_mode: \"lenient\"\n}\n";
+        let err = parse_aln(src).unwrap_err();
+        assert_eq!(err.position(), Some(Position { line: 2, column: 3 }));
+
+        let src = "@policy p {\n  region_filter: \"r\" {\n    allow_ghosts: true\n  }\n}\n";
+        let err = parse_aln(src).unwrap_err();
+        assert!(matches!(
+            err,
+            AlnError::Semantic {
+                line: 3,
+                column: 5,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_runtime_settings_override_defaults() {
+        let src = "@config runtime_settings {\n  enforcement_mode: \"advisory\"\n  flush_timeout_ms: 20\n}\n";
+        let doc = parse_aln(src).unwrap();
+        assert_eq!(doc.runtime.enforcement_mode, EnforcementMode::Advisory);
+        assert_eq!(doc.runtime.flush_timeout_ms, 20);
+        assert_eq!(doc.runtime.max_violation_buffer, 1000);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::aln::{AlnError, Position};
+
+/// ALN token.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    At,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    Colon,
+    Comma,
+    Dot,
+    FatArrow,
+    EqEq,
+    NotEq,
+    Ident(String),
+    Str(String),
+    Num(f64),
+    /// `//!` doc comment text, without the marker.
+    Doc(String),
+    Eof,
+}
+
+impl Token {
+    /// Short description for error messages.
+    pub fn describe(&self) -> String {
+        match self {
+            Token::At => "'@'".to_string(),
+            Token::LBrace => "'{'".to_string(),
+            Token::RBrace => "'}'".to_string(),
+            Token::LBracket => "'['".to_string(),
+            Token::RBracket => "']'".to_string(),
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+            Token::Colon => "':'".to_string(),
+            Token::Comma => "','".to_string(),
+            Token::Dot => "'.'".to_string(),
+            Token::FatArrow => "'=>'".to_string(),
+            Token::EqEq => "'=='".to_string(),
+            Token::NotEq => "'!='".to_string(),
+            Token::Ident(s) => format!("identifier '{}'", s),
+            Token::Str(s) => format!("string \"{}\"", s),
+            Token::Num(n) => format!("number {}", n),
+            Token::Doc(_) => "doc comment".to_string(),
+            Token::Eof => "end of input".to_string(),
+        }
+    }
+}
+
+/// Token with its source position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned {
+    pub token: Token,
+    pub pos: Position,
+}
+
+struct Cursor<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn pos(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+/// Split ALN source into tokens. Plain `//` comments are dropped; `//!`
+/// doc comments are kept so the parser can attach them to items.
+pub fn tokenize(src: &str) -> Result<Vec<Spanned>, AlnError> {
+    let mut cur = Cursor {
+        chars: src.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+    let mut out = Vec::new();
+
+    while let Some(c) = cur.peek() {
+        let pos = cur.pos();
+        let single = |t: Token| Spanned { token: t, pos };
+
+        match c {
+            c if c.is_whitespace() => {
+                cur.bump();
+            }
+            '/' => {
+                cur.bump();
+                if cur.peek() != Some('/') {
+                    return Err(AlnError::syntax(pos, "expected '//' comment"));
+                }
+                cur.bump();
+                let doc = cur.peek() == Some('!');
+                if doc {
+                    cur.bump();
+                }
+                let mut text = String::new();
+                while let Some(c) = cur.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    text.push(c);
+                    cur.bump();
+                }
+                if doc {
+                    out.push(single(Token::Doc(text.trim().to_string())));
+                }
+            }
+            '@' | '{' | '}' | '[' | ']' | '(' | ')' | ':' | ',' | '.' => {
+                cur.bump();
+                out.push(single(match c {
+                    '@' => Token::At,
+                    '{' => Token::LBrace,
+                    '}' => Token::RBrace,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    ':' => Token::Colon,
+                    ',' => Token::Comma,
+                    _ => Token::Dot,
+                }));
+            }
+            '=' => {
+                cur.bump();
+                match cur.bump() {
+                    Some('>') => out.push(single(Token::FatArrow)),
+                    Some('=') => out.push(single(Token::EqEq)),
+                    _ => return Err(AlnError::syntax(pos, "expected '=>' or '=='")),
+                }
+            }
+            '!' => {
+                cur.bump();
+                if cur.bump() != Some('=') {
+                    return Err(AlnError::syntax(pos, "expected '!='"));
+                }
+                out.push(single(Token::NotEq));
+            }
+            '"' => {
+                cur.bump();
+                let mut s = String::new();
+                loop {
+                    match cur.bump() {
+                        None | Some('\n') => {
+                            return Err(AlnError::syntax(pos, "unterminated string"));
+                        }
+                        Some('"') => break,
+                        Some('\\') => match cur.bump() {
+                            Some('n') => s.push('\n'),
+                            Some('t') => s.push('\t'),
+                            Some('"') => s.push('"'),
+                            Some('\\') => s.push('\\'),
+                            _ => return Err(AlnError::syntax(cur.pos(), "invalid escape")),
+                        },
+                        Some(c) => s.push(c),
+                    }
+                }
+                out.push(single(Token::Str(s)));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut text = String::new();
+                text.push(c);
+                cur.bump();
+                while let Some(c) = cur.peek() {
+                    if c.is_ascii_digit() || c == '.' {
+                        text.push(c);
+                        cur.bump();
+                    } else {
+                        break;
+                    }
+                }
+                let n: f64 = text
+                    .parse()
+                    .map_err(|_| AlnError::syntax(pos, format!("invalid number '{}'", text)))?;
+                out.push(single(Token::Num(n)));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut text = String::new();
+                while let Some(c) = cur.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        text.push(c);
+                        cur.bump();
+                    } else {
+                        break;
+                    }
+                }
+                out.push(single(Token::Ident(text)));
+            }
+            other => {
+                return Err(AlnError::syntax(
+                    pos,
+                    format!("unexpected character '{}'", other),
+                ));
+            }
+        }
+    }
+
+    out.push(Spanned {
+        token: Token::Eof,
+        pos: cur.pos(),
+    });
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex(src: &str) -> Vec<(Token, (usize, usize))> {
+        tokenize(src)
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.token, (t.pos.line, t.pos.column)))
+            .collect()
+    }
+
+    fn lex_err(src: &str) -> AlnError {
+        tokenize(src).unwrap_err()
+    }
+
+    #[test]
+    fn test_tokens_and_positions() {
+        let src = "@state_machine m {\n  //! Doc line\n  x: [1, -2.5] => \"s\\n\" // gone\n}";
+        assert_eq!(
+            lex(src),
+            vec![
+                (Token::At, (1, 1)),
+                (Token::Ident("state_machine".into()), (1, 2)),
+                (Token::Ident("m".into()), (1, 16)),
+                (Token::LBrace, (1, 18)),
+                (Token::Doc("Doc line".into()), (2, 3)),
+                (Token::Ident("x".into()), (3, 3)),
+                (Token::Colon, (3, 4)),
+                (Token::LBracket, (3, 6)),
+                (Token::Num(1.0), (3, 7)),
+                (Token::Comma, (3, 8)),
+                (Token::Num(-2.5), (3, 10)),
+                (Token::RBracket, (3, 14)),
+                (Token::FatArrow, (3, 16)),
+                (Token::Str("s\n".into()), (3, 19)),
+                (Token::RBrace, (4, 1)),
+                (Token::Eof, (4, 2)),
+            ]
+        );
+        assert_eq!(
+            lex("a == b != c.d"),
+            vec![
+                (Token::Ident("a".into()), (1, 1)),
+                (Token::EqEq, (1, 3)),
+                (Token::Ident("b".into()), (1, 6)),
+                (Token::NotEq, (1, 8)),
+                (Token::Ident("c".into()), (1, 11)),
+                (Token::Dot, (1, 12)),
+                (Token::Ident("d".into()), (1, 13)),
+                (Token::Eof, (1, 14)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_input_reports_position() {
+        let at = |line, column, message: &str| AlnError::syntax(Position { line, column }, message);
+        assert_eq!(lex_err("a: \"open\nb"), at(1, 4, "unterminated string"));
+        assert_eq!(lex_err("x: \"bad \\q\""), at(1, 11, "invalid escape"));
+        assert_eq!(lex_err("a / b"), at(1, 3, "expected '//' comment"));
+        assert_eq!(lex_err("a = b"), at(1, 3, "expected '=>' or '=='"));
+        assert_eq!(lex_err("a ! b"), at(1, 3, "expected '!='"));
+        assert_eq!(lex_err("\n  #"), at(2, 3, "unexpected character '#'"));
+        assert_eq!(lex_err("x: 1.2.3"), at(1, 4, "invalid number '1.2.3'"));
+    }
+}
@@ -0,0 +1,685 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::aln::parser::{AlnDirective, AlnItem, AlnItemKind, AlnValue, Condition};
+use crate::aln::{AlnError, Position};
+use crate::governance::EnforcementMode;
+
+/// Actions an ALN rule or right can trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AlnAction {
+    AbortAndFlush,
+    LogAndNotify,
+    PurgeHistory,
+    PurgeQueryCache,
+    TerminateSafe,
+}
+
+impl AlnAction {
+    /// Parse the ALN spelling, e.g. "AbortAndFlush".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "AbortAndFlush" => Some(Self::AbortAndFlush),
+            "LogAndNotify" => Some(Self::LogAndNotify),
+            "PurgeHistory" => Some(Self::PurgeHistory),
+            "PurgeQueryCache" => Some(Self::PurgeQueryCache),
+            "TerminateSafe" => Some(Self::TerminateSafe),
+            _ => None,
+        }
+    }
+}
+
+/// `on_event` kinds a guard can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardEvent {
+    DataIngest,
+    ZoneEntry,
+    DataQuery,
+}
+
+impl GuardEvent {
+    /// Parse the ALN spelling, e.g. "data_ingest".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "data_ingest" => Some(Self::DataIngest),
+            "zone_entry" => Some(Self::ZoneEntry),
+            "data_query" => Some(Self::DataQuery),
+            _ => None,
+        }
+    }
+}
+
+/// `enforce:` directive inside a guard response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardEnforcement {
+    ObserveOnly,
+}
+
+/// `define Name => { ... }` inside `@rights_spectrum`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RightDefinition {
+    pub name: String,
+    pub doc: Vec<String>,
+    pub scope: Option<String>,
+    pub constraint: Option<String>,
+    pub violation_action: Option<AlnAction>,
+    /// Remaining right-specific properties (proxy_only, data_retention, ...).
+    pub properties: BTreeMap<String, AlnValue>,
+    pub pos: Position,
+}
+
+/// Body of an `if <condition> => { ... }` rule.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GuardResponse {
+    pub trigger: Option<AlnAction>,
+    pub action: Option<AlnAction>,
+    pub deny: bool,
+    pub reason: Option<String>,
+    pub enforce: Option<GuardEnforcement>,
+    pub disable: Vec<String>,
+    pub enable: Vec<String>,
+    pub log_level: Option<String>,
+    pub audit_path: Option<String>,
+    pub notify: Vec<String>,
+}
+
+impl GuardResponse {
+    /// Triggered actions, `trigger` first.
+    pub fn actions(&self) -> Vec<AlnAction> {
+        self.trigger
+            .iter()
+            .chain(self.action.iter())
+            .copied()
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuardRule {
+    pub condition: Condition,
+    pub response: GuardResponse,
+    pub pos: Position,
+}
+
+/// `on_event: "<event>" { ... }` block of a guard.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuardHandler {
+    pub event: GuardEvent,
+    /// `inspect:` / `check:` path, e.g. `payload.metadata_keys`.
+    pub inspect: Option<String>,
+    pub forbid: Vec<String>,
+    pub rules: Vec<GuardRule>,
+    pub pos: Position,
+}
+
+/// `@guard name { ... }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuardDefinition {
+    pub name: String,
+    pub doc: Vec<String>,
+    pub handlers: Vec<GuardHandler>,
+    pub pos: Position,
+}
+
+/// Numeric limits of a policy region filter.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PolicyThresholds {
+    pub allow_haunt_density_max: Option<f64>,
+    pub allow_risk_index_max: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionFilter {
+    pub region: String,
+    /// Rights enforced in this region.
+    pub enforce: Vec<String>,
+    pub override_zone_classification: Option<String>,
+    pub thresholds: PolicyThresholds,
+    pub pos: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityFilter {
+    pub entity: String,
+    /// Rights granted to matching entities.
+    pub grant: Vec<String>,
+    pub lease_duration_hours: Option<f64>,
+    pub auto_renew: Option<bool>,
+    pub pos: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViolationResponse {
+    pub threshold: u32,
+    pub action: AlnAction,
+    pub notify: Vec<String>,
+}
+
+/// `@policy name { ... }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyDefinition {
+    pub name: String,
+    pub doc: Vec<String>,
+    pub region_filters: Vec<RegionFilter>,
+    pub entity_filters: Vec<EntityFilter>,
+    pub violation_response: Option<ViolationResponse>,
+    pub pos: Position,
+}
+
+/// `@config runtime_settings { ... }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeSettings {
+    pub enforcement_mode: EnforcementMode,
+    pub audit_logging: bool,
+    pub crypto_signing: bool,
+    pub max_violation_buffer: u32,
+    pub flush_timeout_ms: u64,
+    pub heartbeat_interval_s: u64,
+}
+
+impl Default for RuntimeSettings {
+    fn default() -> Self {
+        Self {
+            enforcement_mode: EnforcementMode::Strict,
+            audit_logging: true,
+            crypto_signing: true,
+            max_violation_buffer: 1000,
+            flush_timeout_ms: 50,
+            heartbeat_interval_s: 5,
+        }
+    }
+}
+
+/// `@audit_log name { ... }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditLogDefinition {
+    pub name: String,
+    pub format: Option<String>,
+    pub encryption: Option<String>,
+    pub retention_policy: Option<String>,
+    pub fields: Vec<String>,
+    /// Remaining settings (on_write, ...).
+    pub extra: BTreeMap<String, AlnValue>,
+    pub pos: Position,
+}
+
+/// Typed view of an ALN policy file.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AlnDocument {
+    pub module: Option<String>,
+    pub version: Option<String>,
+    pub license: Option<String>,
+    pub identity: BTreeMap<String, String>,
+    pub rights: Vec<RightDefinition>,
+    pub guards: Vec<GuardDefinition>,
+    pub policies: Vec<PolicyDefinition>,
+    /// Defaults apply when the file has no `@config runtime_settings`.
+    pub runtime: RuntimeSettings,
+    pub audit_logs: Vec<AuditLogDefinition>,
+    /// Directives without a typed model (state_machine, export, checksum).
+    pub other: Vec<AlnDirective>,
+}
+
+impl AlnDocument {
+    /// Right by name.
+    pub fn right(&self, name: &str) -> Option<&RightDefinition> {
+        self.rights.iter().find(|r| r.name == name)
+    }
+
+    /// Guard by name.
+    pub fn guard(&self, name: &str) -> Option<&GuardDefinition> {
+        self.guards.iter().find(|g| g.name == name)
+    }
+
+    /// Policy by name.
+    pub fn policy(&self, name: &str) -> Option<&PolicyDefinition> {
+        self.policies.iter().find(|p| p.name == name)
+    }
+
+    /// Build the typed model from parsed directives.
+    pub fn from_directives(directives: Vec<AlnDirective>) -> Result<Self, AlnError> {
+        let mut doc = AlnDocument::default();
+        let mut saw_runtime = false;
+
+        for d in directives {
+            match d.name.as_str() {
+                "module" => doc.module = d.label.clone(),
+                "version" => {
+                    doc.version = Some(expect_str(directive_value(&d)?, "version", d.pos)?)
+                }
+                "license" => {
+                    doc.license = Some(expect_str(directive_value(&d)?, "license", d.pos)?)
+                }
+                "identity" => {
+                    for (key, value, item) in fields(directive_body(&d)?)? {
+                        doc.identity
+                            .insert(key.to_string(), expect_str(value, key, item.pos)?);
+                    }
+                }
+                "rights_spectrum" => {
+                    for item in directive_body(&d)? {
+                        doc.rights.push(right(item)?);
+                    }
+                }
+                "guard" => doc.guards.push(guard(&d)?),
+                "policy" => doc.policies.push(policy(&d)?),
+                "config" if d.label.as_deref() == Some("runtime_settings") => {
+                    if saw_runtime {
+                        return Err(AlnError::semantic(
+                            d.pos,
+                            "duplicate @config runtime_settings",
+                        ));
+                    }
+                    saw_runtime = true;
+                    doc.runtime = runtime_settings(&d)?;
+                }
+                "audit_log" => doc.audit_logs.push(audit_log(&d)?),
+                _ => doc.other.push(d),
+            }
+        }
+        Ok(doc)
+    }
+}
+
+fn label(d: &AlnDirective) -> Result<String, AlnError> {
+    d.label
+        .clone()
+        .ok_or_else(|| AlnError::semantic(d.pos, format!("@{} requires a name", d.name)))
+}
+
+fn directive_value(d: &AlnDirective) -> Result<&AlnValue, AlnError> {
+    d.value
+        .as_ref()
+        .ok_or_else(|| AlnError::semantic(d.pos, format!("@{} requires a value", d.name)))
+}
+
+fn directive_body(d: &AlnDirective) -> Result<&[AlnItem], AlnError> {
+    d.body
+        .as_deref()
+        .ok_or_else(|| AlnError::semantic(d.pos, format!("@{} requires a body", d.name)))
+}
+
+/// All items must be plain `key: value` fields without trailing blocks.
+fn fields(items: &[AlnItem]) -> Result<Vec<(&str, &AlnValue, &AlnItem)>, AlnError> {
+    items
+        .iter()
+        .map(|item| match &item.kind {
+            AlnItemKind::Field {
+                key,
+                value,
+                block: None,
+                target: None,
+            } => Ok((key.as_str(), value, item)),
+            _ => Err(AlnError::semantic(item.pos, "expected 'key: value'")),
+        })
+        .collect()
+}
+
+fn type_error(key: &str, expected: &str, value: &AlnValue, pos: Position) -> AlnError {
+    AlnError::semantic(
+        pos,
+        format!(
+            "'{}' expects {}, found {}",
+            key,
+            expected,
+            value.kind_name()
+        ),
+    )
+}
+
+fn unknown_key(key: &str, context: &str, pos: Position) -> AlnError {
+    AlnError::semantic(pos, format!("unknown key '{}' in {}", key, context))
+}
+
+fn expect_str(value: &AlnValue, key: &str, pos: Position) -> Result<String, AlnError> {
+    match value {
+        AlnValue::Str(s) => Ok(s.clone()),
+        other => Err(type_error(key, "a string", other, pos)),
+    }
+}
+
+fn expect_path(value: &AlnValue, key: &str, pos: Position) -> Result<String, AlnError> {
+    match value {
+        AlnValue::Path(s) | AlnValue::Str(s) => Ok(s.clone()),
+        other => Err(type_error(key, "an identifier", other, pos)),
+    }
+}
+
+fn expect_num(value: &AlnValue, key: &str, pos: Position) -> Result<f64, AlnError> {
+    match value {
+        AlnValue::Num(n) => Ok(*n),
+        other => Err(type_error(key, "a number", other, pos)),
+    }
+}
+
+fn expect_uint(value: &AlnValue, key: &str, pos: Position) -> Result<u64, AlnError> {
+    let n = expect_num(value, key, pos)?;
+    if n < 0.0 || n.fract() != 0.0 || n > u64::MAX as f64 {
+        return Err(AlnError::semantic(
+            pos,
+            format!("'{}' expects a non-negative integer, found {}", key, n),
+        ));
+    }
+    Ok(n as u64)
+}
+
+fn expect_bool(value: &AlnValue, key: &str, pos: Position) -> Result<bool, AlnError> {
+    match value {
+        AlnValue::Bool(b) => Ok(*b),
+        other => Err(type_error(key, "a bool", other, pos)),
+    }
+}
+
+/// A string list; a single string is accepted as a one-element list.
+fn expect_str_list(value: &AlnValue, key: &str, pos: Position) -> Result<Vec<String>, AlnError> {
+    match value {
+        AlnValue::Str(s) => Ok(vec![s.clone()]),
+        AlnValue::List(items) => items.iter().map(|v| expect_str(v, key, pos)).collect(),
+        other => Err(type_error(key, "a list of strings", other, pos)),
+    }
+}
+
+fn expect_action(value: &AlnValue, key: &str, pos: Position) -> Result<AlnAction, AlnError> {
+    let name = expect_str(value, key, pos)?;
+    AlnAction::from_name(&name)
+        .ok_or_else(|| AlnError::semantic(pos, format!("unknown action '{}'", name)))
+}
+
+fn right(item: &AlnItem) -> Result<RightDefinition, AlnError> {
+    let (name, body) = match &item.kind {
+        AlnItemKind::Define { name, body } => (name, body),
+        _ => {
+            return Err(AlnError::semantic(
+                item.pos,
+                "expected 'define Name => { ... }'",
+            ))
+        }
+    };
+
+    let mut right = RightDefinition {
+        name: name.clone(),
+        doc: item.doc.clone(),
+        scope: None,
+        constraint: None,
+        violation_action: None,
+        properties: BTreeMap::new(),
+        pos: item.pos,
+    };
+    for (key, value, field) in fields(body)? {
+        match key {
+            "scope" => right.scope = Some(expect_str(value, key, field.pos)?),
+            "constraint" => right.constraint = Some(expect_str(value, key, field.pos)?),
+            "violation_action" => {
+                right.violation_action = Some(expect_action(value, key, field.pos)?)
+            }
+            _ => {
+                right.properties.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+    Ok(right)
+}
+
+fn guard(d: &AlnDirective) -> Result<GuardDefinition, AlnError> {
+    let mut handlers = Vec::new();
+    for item in directive_body(d)? {
+        match &item.kind {
+            AlnItemKind::Field {
+                key,
+                value,
+                block: Some(block),
+                target: None,
+            } if key == "on_event" => {
+                let name = expect_str(value, key, item.pos)?;
+                let event = GuardEvent::from_name(&name).ok_or_else(|| {
+                    AlnError::semantic(item.pos, format!("unknown event '{}'", name))
+                })?;
+                handlers.push(guard_handler(event, block, item.pos)?);
+            }
+            _ => {
+                return Err(AlnError::semantic(
+                    item.pos,
+                    "expected 'on_event: \"<event>\" { ... }'",
+                ))
+            }
+        }
+    }
+    Ok(GuardDefinition {
+        name: label(d)?,
+        doc: d.doc.clone(),
+        handlers,
+        pos: d.pos,
+    })
+}
+
+fn guard_handler(
+    event: GuardEvent,
+    body: &[AlnItem],
+    pos: Position,
+) -> Result<GuardHandler, AlnError> {
+    let mut handler = GuardHandler {
+        event,
+        inspect: None,
+        forbid: Vec::new(),
+        rules: Vec::new(),
+        pos,
+    };
+    for item in body {
+        match &item.kind {
+            AlnItemKind::Field {
+                key,
+                value,
+                block: None,
+                target: None,
+            } => match key.as_str() {
+                "inspect" | "check" => handler.inspect = Some(expect_path(value, key, item.pos)?),
+                "forbid" => handler.forbid = expect_str_list(value, key, item.pos)?,
+                _ => return Err(unknown_key(key, "on_event block", item.pos)),
+            },
+            AlnItemKind::If { condition, body } => handler.rules.push(GuardRule {
+                condition: condition.clone(),
+                response: guard_response(body)?,
+                pos: item.pos,
+            }),
+            _ => {
+                return Err(AlnError::semantic(
+                    item.pos,
+                    "unexpected item in on_event block",
+                ))
+            }
+        }
+    }
+    Ok(handler)
+}
+
+fn guard_response(body: &[AlnItem]) -> Result<GuardResponse, AlnError> {
+    let mut r = GuardResponse::default();
+    for (key, value, item) in fields(body)? {
+        let pos = item.pos;
+        match key {
+            "trigger" => r.trigger = Some(expect_action(value, key, pos)?),
+            "action" => r.action = Some(expect_action(value, key, pos)?),
+            "deny" => r.deny = expect_bool(value, key, pos)?,
+            "reason" => r.reason = Some(expect_str(value, key, pos)?),
+            "enforce" => {
+                let name = expect_str(value, key, pos)?;
+                r.enforce = match name.as_str() {
+                    "observe_only" => Some(GuardEnforcement::ObserveOnly),
+                    _ => {
+                        return Err(AlnError::semantic(
+                            pos,
+                            format!("unknown enforcement '{}'", name),
+                        ))
+                    }
+                };
+            }
+            "disable" => r.disable = expect_str_list(value, key, pos)?,
+            "enable" => r.enable = expect_str_list(value, key, pos)?,
+            "log_level" => r.log_level = Some(expect_str(value, key, pos)?),
+            "audit_path" => r.audit_path = Some(expect_str(value, key, pos)?),
+            "notify" => r.notify = expect_str_list(value, key, pos)?,
+            _ => return Err(unknown_key(key, "guard response", pos)),
+        }
+    }
+    Ok(r)
+}
+
+fn policy(d: &AlnDirective) -> Result<PolicyDefinition, AlnError> {
+    let mut policy = PolicyDefinition {
+        name: label(d)?,
+        doc: d.doc.clone(),
+        region_filters: Vec::new(),
+        entity_filters: Vec::new(),
+        violation_response: None,
+        pos: d.pos,
+    };
+
+    for item in directive_body(d)? {
+        let (key, value, block) = match &item.kind {
+            AlnItemKind::Field {
+                key,
+                value,
+                block,
+                target: None,
+            } => (key.as_str(), value, block.as_deref()),
+            _ => return Err(AlnError::semantic(item.pos, "unexpected item in @policy")),
+        };
+        match (key, block) {
+            ("region_filter", Some(body)) => {
+                let mut filter = RegionFilter {
+                    region: expect_str(value, key, item.pos)?,
+                    enforce: Vec::new(),
+                    override_zone_classification: None,
+                    thresholds: PolicyThresholds::default(),
+                    pos: item.pos,
+                };
+                for (k, v, f) in fields(body)? {
+                    match k {
+                        "enforce" => filter.enforce.push(expect_str(v, k, f.pos)?),
+                        "override_zone_classification" => {
+                            filter.override_zone_classification = Some(expect_str(v, k, f.pos)?)
+                        }
+                        "allow_haunt_density_max" => {
+                            filter.thresholds.allow_haunt_density_max =
+                                Some(expect_num(v, k, f.pos)?)
+                        }
+                        "allow_risk_index_max" => {
+                            filter.thresholds.allow_risk_index_max = Some(expect_num(v, k, f.pos)?)
+                        }
+                        _ => return Err(unknown_key(k, "region_filter", f.pos)),
+                    }
+                }
+                policy.region_filters.push(filter);
+            }
+            ("entity_filter", Some(body)) => {
+                let mut filter = EntityFilter {
+                    entity: expect_str(value, key, item.pos)?,
+                    grant: Vec::new(),
+                    lease_duration_hours: None,
+                    auto_renew: None,
+                    pos: item.pos,
+                };
+                for (k, v, f) in fields(body)? {
+                    match k {
+                        "grant" => filter.grant.push(expect_str(v, k, f.pos)?),
+                        "lease_duration_hours" => {
+                            filter.lease_duration_hours = Some(expect_num(v, k, f.pos)?)
+                        }
+                        "auto_renew" => filter.auto_renew = Some(expect_bool(v, k, f.pos)?),
+                        _ => return Err(unknown_key(k, "entity_filter", f.pos)),
+                    }
+                }
+                policy.entity_filters.push(filter);
+            }
+            ("violation_response", None) => {
+                let body = match value {
+                    AlnValue::Block(body) => body,
+                    other => return Err(type_error(key, "a block", other, item.pos)),
+                };
+                let (mut threshold, mut action, mut notify) = (None, None, Vec::new());
+                for (k, v, f) in fields(body)? {
+                    match k {
+                        "threshold" => {
+                            let n = expect_uint(v, k, f.pos)?;
+                            threshold = Some(u32::try_from(n).map_err(|_| {
+                                AlnError::semantic(f.pos, "'threshold' out of range")
+                            })?);
+                        }
+                        "action" => action = Some(expect_action(v, k, f.pos)?),
+                        "notify" => notify = expect_str_list(v, k, f.pos)?,
+                        _ => return Err(unknown_key(k, "violation_response", f.pos)),
+                    }
+                }
+                policy.violation_response = Some(ViolationResponse {
+                    threshold: threshold.ok_or_else(|| {
+                        AlnError::semantic(item.pos, "violation_response requires 'threshold'")
+                    })?,
+                    action: action.ok_or_else(|| {
+                        AlnError::semantic(item.pos, "violation_response requires 'action'")
+                    })?,
+                    notify,
+                });
+            }
+            _ => return Err(unknown_key(key, "@policy", item.pos)),
+        }
+    }
+    Ok(policy)
+}
+
+fn runtime_settings(d: &AlnDirective) -> Result<RuntimeSettings, AlnError> {
+    let mut rt = RuntimeSettings::default();
+    for (key, value, item) in fields(directive_body(d)?)? {
+        let pos = item.pos;
+        match key {
+            "enforcement_mode" => {
+                let name = expect_str(value, key, pos)?;
+                rt.enforcement_mode = EnforcementMode::from_name(&name).ok_or_else(|| {
+                    AlnError::semantic(
+                        pos,
+                        format!(
+                            "enforcement_mode must be strict|advisory|disabled, found '{}'",
+                            name
+                        ),
+                    )
+                })?;
+            }
+            "audit_logging" => rt.audit_logging = expect_bool(value, key, pos)?,
+            "crypto_signing" => rt.crypto_signing = expect_bool(value, key, pos)?,
+            "max_violation_buffer" => {
+                rt.max_violation_buffer = u32::try_from(expect_uint(value, key, pos)?)
+                    .map_err(|_| AlnError::semantic(pos, "'max_violation_buffer' out of range"))?;
+            }
+            "flush_timeout_ms" => rt.flush_timeout_ms = expect_uint(value, key, pos)?,
+            "heartbeat_interval_s" => rt.heartbeat_interval_s = expect_uint(value, key, pos)?,
+            _ => return Err(unknown_key(key, "runtime_settings", pos)),
+        }
+    }
+    Ok(rt)
+}
+
+fn audit_log(d: &AlnDirective) -> Result<AuditLogDefinition, AlnError> {
+    let mut log = AuditLogDefinition {
+        name: label(d)?,
+        format: None,
+        encryption: None,
+        retention_policy: None,
+        fields: Vec::new(),
+        extra: BTreeMap::new(),
+        pos: d.pos,
+    };
+    for (key, value, item) in fields(directive_body(d)?)? {
+        match key {
+            "format" => log.format = Some(expect_str(value, key, item.pos)?),
+            "encryption" => log.encryption = Some(expect_str(value, key, item.pos)?),
+            "retention_policy" => log.retention_policy = Some(expect_str(value, key, item.pos)?),
+            "fields" => log.fields = expect_str_list(value, key, item.pos)?,
+            _ => {
+                log.extra.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+    Ok(log)
+}
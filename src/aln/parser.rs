@@ -0,0 +1,560 @@
+use serde::{Deserialize, Serialize};
+
+use crate::aln::lexer::{tokenize, Spanned, Token};
+use crate::aln::{AlnError, Position};
+
+/// ALN literal or nested value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AlnValue {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    /// Dotted identifier path, e.g. `payload.metadata_keys`.
+    Path(String),
+    List(Vec<AlnValue>),
+    Block(Vec<AlnItem>),
+}
+
+impl AlnValue {
+    /// Short description for error messages.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            AlnValue::Str(_) => "string",
+            AlnValue::Num(_) => "number",
+            AlnValue::Bool(_) => "bool",
+            AlnValue::Path(_) => "identifier",
+            AlnValue::List(_) => "list",
+            AlnValue::Block(_) => "block",
+        }
+    }
+}
+
+/// Guard condition, e.g. `intent == "reconstruction" OR intent == "simulation"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    /// Bare flag, e.g. `violation_detected`.
+    Flag(String),
+    Eq(String, AlnValue),
+    NotEq(String, AlnValue),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+/// One entry inside a `{ ... }` body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlnItem {
+    pub kind: AlnItemKind,
+    /// `//!` doc lines preceding the item.
+    pub doc: Vec<String>,
+    pub pos: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AlnItemKind {
+    /// `key: value`, optionally followed by `{ block }` or `=> verb value`.
+    Field {
+        key: String,
+        value: AlnValue,
+        block: Option<Vec<AlnItem>>,
+        /// `=> transition_to "X"` / `=> stay "X"`.
+        target: Option<(String, AlnValue)>,
+    },
+    /// `define Name => { ... }`
+    Define { name: String, body: Vec<AlnItem> },
+    /// `if <condition> => { ... }`
+    If {
+        condition: Condition,
+        body: Vec<AlnItem>,
+    },
+    /// `state "Name" { ... }`
+    State { name: String, body: Vec<AlnItem> },
+    /// `function name(a: type, ...) => (type, ...) { ... }`
+    Function {
+        name: String,
+        params: Vec<(String, String)>,
+        returns: Vec<String>,
+        body: Vec<AlnItem>,
+    },
+}
+
+/// Top-level `@name [label|value] [{ body }]` directive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlnDirective {
+    pub name: String,
+    pub label: Option<String>,
+    pub value: Option<AlnValue>,
+    pub body: Option<Vec<AlnItem>>,
+    pub doc: Vec<String>,
+    pub pos: Position,
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    idx: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.idx].token
+    }
+
+    fn pos(&self) -> Position {
+        self.tokens[self.idx].pos
+    }
+
+    fn next(&mut self) -> Spanned {
+        let t = self.tokens[self.idx].clone();
+        if self.idx + 1 < self.tokens.len() {
+            self.idx += 1;
+        }
+        t
+    }
+
+    fn unexpected(&self, expected: &str) -> AlnError {
+        AlnError::syntax(
+            self.pos(),
+            format!("expected {}, found {}", expected, self.peek().describe()),
+        )
+    }
+
+    fn expect(&mut self, token: Token, expected: &str) -> Result<Position, AlnError> {
+        if *self.peek() == token {
+            Ok(self.next().pos)
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn ident(&mut self, expected: &str) -> Result<String, AlnError> {
+        match self.peek().clone() {
+            Token::Ident(s) => {
+                self.next();
+                Ok(s)
+            }
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    fn docs(&mut self) -> Vec<String> {
+        let mut doc = Vec::new();
+        while let Token::Doc(text) = self.peek().clone() {
+            doc.push(text);
+            self.next();
+        }
+        doc
+    }
+
+    fn document(&mut self) -> Result<Vec<AlnDirective>, AlnError> {
+        let mut out = Vec::new();
+        loop {
+            let doc = self.docs();
+            if *self.peek() == Token::Eof {
+                return Ok(out);
+            }
+            let pos = self.expect(Token::At, "'@' directive")?;
+            let name = self.ident("directive name")?;
+
+            let (mut label, mut value) = (None, None);
+            match self.peek().clone() {
+                Token::Ident(s) => {
+                    self.next();
+                    label = Some(s);
+                }
+                Token::Str(_) | Token::Num(_) => value = Some(self.value()?),
+                _ => {}
+            }
+            let body = if *self.peek() == Token::LBrace {
+                Some(self.block()?)
+            } else {
+                None
+            };
+
+            out.push(AlnDirective {
+                name,
+                label,
+                value,
+                body,
+                doc,
+                pos,
+            });
+        }
+    }
+
+    /// `{ items }`
+    fn block(&mut self) -> Result<Vec<AlnItem>, AlnError> {
+        self.expect(Token::LBrace, "'{'")?;
+        let mut items = Vec::new();
+        loop {
+            let doc = self.docs();
+            match self.peek() {
+                Token::RBrace => {
+                    self.next();
+                    return Ok(items);
+                }
+                Token::Comma => {
+                    self.next();
+                }
+                Token::Ident(_) => {
+                    let mut item = self.item()?;
+                    item.doc = doc;
+                    items.push(item);
+                }
+                _ => return Err(self.unexpected("item or '}'")),
+            }
+        }
+    }
+
+    fn item(&mut self) -> Result<AlnItem, AlnError> {
+        let pos = self.pos();
+        let word = self.ident("item")?;
+        let kind = match word.as_str() {
+            "define" if matches!(self.peek(), Token::Ident(_)) => {
+                let name = self.ident("definition name")?;
+                self.expect(Token::FatArrow, "'=>'")?;
+                AlnItemKind::Define {
+                    name,
+                    body: self.block()?,
+                }
+            }
+            "if" if *self.peek() != Token::Colon => {
+                let condition = self.condition()?;
+                self.expect(Token::FatArrow, "'=>' after condition")?;
+                AlnItemKind::If {
+                    condition,
+                    body: self.block()?,
+                }
+            }
+            "state" if matches!(self.peek(), Token::Str(_)) => {
+                let name = match self.next().token {
+                    Token::Str(s) => s,
+                    _ => unreachable!(),
+                };
+                AlnItemKind::State {
+                    name,
+                    body: self.block()?,
+                }
+            }
+            "function" if matches!(self.peek(), Token::Ident(_)) => self.function()?,
+            _ => {
+                self.expect(Token::Colon, "':' after key")?;
+                let value = self.value()?;
+                let mut block = None;
+                let mut target = None;
+                match self.peek() {
+                    Token::LBrace => block = Some(self.block()?),
+                    Token::FatArrow => {
+                        self.next();
+                        let verb = self.ident("'transition_to' or 'stay'")?;
+                        target = Some((verb, self.value()?));
+                    }
+                    _ => {}
+                }
+                AlnItemKind::Field {
+                    key: word,
+                    value,
+                    block,
+                    target,
+                }
+            }
+        };
+        Ok(AlnItem {
+            kind,
+            doc: Vec::new(),
+            pos,
+        })
+    }
+
+    fn function(&mut self) -> Result<AlnItemKind, AlnError> {
+        let name = self.ident("function name")?;
+        self.expect(Token::LParen, "'('")?;
+        let mut params = Vec::new();
+        while *self.peek() != Token::RParen {
+            let p = self.ident("parameter name")?;
+            self.expect(Token::Colon, "':' after parameter")?;
+            let ty = self.ident("parameter type")?;
+            params.push((p, ty));
+            if *self.peek() == Token::Comma {
+                self.next();
+            } else {
+                break;
+            }
+        }
+        self.expect(Token::RParen, "')'")?;
+        self.expect(Token::FatArrow, "'=>'")?;
+        self.expect(Token::LParen, "'('")?;
+        let mut returns = Vec::new();
+        while *self.peek() != Token::RParen {
+            returns.push(self.ident("return type")?);
+            if *self.peek() == Token::Comma {
+                self.next();
+            } else {
+                break;
+            }
+        }
+        self.expect(Token::RParen, "')'")?;
+        Ok(AlnItemKind::Function {
+            name,
+            params,
+            returns,
+            body: self.block()?,
+        })
+    }
+
+    fn path(&mut self) -> Result<String, AlnError> {
+        let mut path = self.ident("identifier")?;
+        while *self.peek() == Token::Dot {
+            self.next();
+            path.push('.');
+            path.push_str(&self.ident("identifier after '.'")?);
+        }
+        Ok(path)
+    }
+
+    fn value(&mut self) -> Result<AlnValue, AlnError> {
+        match self.peek().clone() {
+            Token::Str(s) => {
+                self.next();
+                Ok(AlnValue::Str(s))
+            }
+            Token::Num(n) => {
+                self.next();
+                Ok(AlnValue::Num(n))
+            }
+            Token::LBracket => {
+                self.next();
+                let mut items = Vec::new();
+                loop {
+                    if *self.peek() == Token::RBracket {
+                        self.next();
+                        return Ok(AlnValue::List(items));
+                    }
+                    items.push(self.value()?);
+                    match self.peek() {
+                        Token::Comma => {
+                            self.next();
+                        }
+                        Token::RBracket => {}
+                        _ => return Err(self.unexpected("',' or ']'")),
+                    }
+                }
+            }
+            Token::LBrace => Ok(AlnValue::Block(self.block()?)),
+            Token::Ident(s) if s == "true" || s == "false" => {
+                self.next();
+                Ok(AlnValue::Bool(s == "true"))
+            }
+            Token::Ident(_) => Ok(AlnValue::Path(self.path()?)),
+            _ => Err(self.unexpected("value")),
+        }
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self.peek(), Token::Ident(s) if s.eq_ignore_ascii_case(kw))
+    }
+
+    fn condition(&mut self) -> Result<Condition, AlnError> {
+        let mut lhs = self.and_condition()?;
+        while self.is_keyword("or") {
+            self.next();
+            lhs = Condition::Or(Box::new(lhs), Box::new(self.and_condition()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and_condition(&mut self) -> Result<Condition, AlnError> {
+        let mut lhs = self.comparison()?;
+        while self.is_keyword("and") {
+            self.next();
+            lhs = Condition::And(Box::new(lhs), Box::new(self.comparison()?));
+        }
+        Ok(lhs)
+    }
+
+    fn comparison(&mut self) -> Result<Condition, AlnError> {
+        let lhs = self.path()?;
+        match self.peek() {
+            Token::EqEq => {
+                self.next();
+                Ok(Condition::Eq(lhs, self.value()?))
+            }
+            Token::NotEq => {
+                self.next();
+                Ok(Condition::NotEq(lhs, self.value()?))
+            }
+            _ => Ok(Condition::Flag(lhs)),
+        }
+    }
+}
+
+/// Parse ALN source into its top-level directives.
+pub fn parse_directives(src: &str) -> Result<Vec<AlnDirective>, AlnError> {
+    let tokens = tokenize(src)?;
+    Parser { tokens, idx: 0 }.document()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: usize, column: usize) -> Position {
+        Position { line, column }
+    }
+
+    fn field(
+        key: &str,
+        value: AlnValue,
+        target: Option<(&str, AlnValue)>,
+        at: Position,
+    ) -> AlnItem {
+        AlnItem {
+            kind: AlnItemKind::Field {
+                key: key.to_string(),
+                value,
+                block: None,
+                target: target.map(|(verb, v)| (verb.to_string(), v)),
+            },
+            doc: Vec::new(),
+            pos: at,
+        }
+    }
+
+    fn str(s: &str) -> AlnValue {
+        AlnValue::Str(s.to_string())
+    }
+
+    fn parse_err(src: &str) -> AlnError {
+        parse_directives(src).unwrap_err()
+    }
+
+    #[test]
+    fn test_directives_and_items() {
+        let src = "//! Top doc\n@module demo\n@version \"1.0\"\n@state_machine rights {\n    initial_state: \"Monitoring\"\n    state \"Monitoring\" {\n        on_input: \"violation\" => transition_to \"Investigating\"\n    }\n}\n";
+        let directives = parse_directives(src).unwrap();
+        assert_eq!(
+            directives,
+            vec![
+                AlnDirective {
+                    name: "module".into(),
+                    label: Some("demo".into()),
+                    value: None,
+                    body: None,
+                    doc: vec!["Top doc".into()],
+                    pos: pos(2, 1),
+                },
+                AlnDirective {
+                    name: "version".into(),
+                    label: None,
+                    value: Some(str("1.0")),
+                    body: None,
+                    doc: Vec::new(),
+                    pos: pos(3, 1),
+                },
+                AlnDirective {
+                    name: "state_machine".into(),
+                    label: Some("rights".into()),
+                    value: None,
+                    body: Some(vec![
+                        field("initial_state", str("Monitoring"), None, pos(5, 5)),
+                        AlnItem {
+                            kind: AlnItemKind::State {
+                                name: "Monitoring".into(),
+                                body: vec![field(
+                                    "on_input",
+                                    str("violation"),
+                                    Some(("transition_to", str("Investigating"))),
+                                    pos(7, 9),
+                                )],
+                            },
+                            doc: Vec::new(),
+                            pos: pos(6, 5),
+                        },
+                    ]),
+                    doc: Vec::new(),
+                    pos: pos(4, 1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conditions_functions_and_values() {
+        let src = "@guard g {\n  if a == \"x\" OR b AND c != 2 => { deny: true }\n  function f(x: f64, y: bool) => (bool) {}\n  k: [1, payload.keys, [false]]\n}";
+        let body = parse_directives(src).unwrap().remove(0).body.unwrap();
+        assert_eq!(body.len(), 3);
+
+        let AlnItemKind::If {
+            condition,
+            body: rule,
+        } = &body[0].kind
+        else {
+            panic!("expected if, got {:?}", body[0].kind);
+        };
+        assert_eq!(
+            *condition,
+            Condition::Or(
+                Box::new(Condition::Eq("a".into(), str("x"))),
+                Box::new(Condition::And(
+                    Box::new(Condition::Flag("b".into())),
+                    Box::new(Condition::NotEq("c".into(), AlnValue::Num(2.0))),
+                )),
+            )
+        );
+        assert_eq!(
+            *rule,
+            vec![field("deny", AlnValue::Bool(true), None, pos(2, 36))]
+        );
+
+        assert_eq!(
+            body[1].kind,
+            AlnItemKind::Function {
+                name: "f".into(),
+                params: vec![("x".into(), "f64".into()), ("y".into(), "bool".into())],
+                returns: vec!["bool".into()],
+                body: Vec::new(),
+            }
+        );
+        assert_eq!(
+            body[2],
+            field(
+                "k",
+                AlnValue::List(vec![
+                    AlnValue::Num(1.0),
+                    AlnValue::Path("payload.keys".into()),
+                    AlnValue::List(vec![AlnValue::Bool(false)]),
+                ]),
+                None,
+                pos(4, 3),
+            )
+        );
+    }
+
+    #[test]
+    fn test_malformed_input_reports_position() {
+        assert_eq!(
+            parse_err("@guard g {\n  inspect payload\n}"),
+            AlnError::syntax(
+                pos(2, 11),
+                "expected ':' after key, found identifier 'payload'"
+            )
+        );
+        assert_eq!(
+            parse_err("@p x {\n  k: [1 2]\n}"),
+            AlnError::syntax(pos(2, 9), "expected ',' or ']', found number 2")
+        );
+        assert_eq!(
+            parse_err("module demo"),
+            AlnError::syntax(
+                pos(1, 1),
+                "expected '@' directive, found identifier 'module'"
+            )
+        );
+        assert_eq!(
+            parse_err("@g x {\n  k: 1\n"),
+            AlnError::syntax(pos(3, 1), "expected item or '}', found end of input")
+        );
+        assert_eq!(
+            parse_err("@g x { if a => deny }"),
+            AlnError::syntax(pos(1, 16), "expected '{', found identifier 'deny'")
+        );
+    }
+}
@@ -0,0 +1,423 @@
+//! Raw multichannel time series → normalized per-band `(band, μ_B, σ_B)`
+//! input for `evaluate_spectral_vision`, via a Welch PSD per epoch.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::spectral_vision::{clamp01, BandRegistry};
+
+/// Sampled multichannel recording, one `Vec` per channel.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RawSignal {
+    pub sample_rate_hz: f64,
+    pub channels: Vec<Vec<f64>>,
+}
+
+/// Welch PSD settings.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WelchParams {
+    /// Segment length in samples; must be a power of two.
+    pub segment_len: usize,
+    /// Segment overlap fraction in [0,1).
+    pub overlap: f64,
+}
+
+impl Default for WelchParams {
+    fn default() -> Self {
+        Self {
+            segment_len: 256,
+            overlap: 0.5,
+        }
+    }
+}
+
+/// Reference each epoch's band power is normalized against before clamping to [0,1].
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum PowerReference {
+    /// Fraction of the epoch's total power across all registry bands.
+    #[default]
+    Relative,
+    /// Divide by one fixed power for every band.
+    Absolute(f64),
+    /// Divide by a per-band reference power (band_index, power).
+    PerBand(Vec<(i32, f64)>),
+}
+
+/// Settings for [`compute_band_power_inputs`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BandPowerParams {
+    /// Epoch length in seconds; trailing samples short of a full epoch are dropped.
+    pub epoch_secs: f64,
+    pub welch: WelchParams,
+    pub reference: PowerReference,
+}
+
+impl Default for BandPowerParams {
+    fn default() -> Self {
+        Self {
+            epoch_secs: 4.0,
+            welch: WelchParams::default(),
+            reference: PowerReference::Relative,
+        }
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum BandPowerError {
+    #[error("sample rate must be positive and finite, got {0}")]
+    InvalidSampleRate(f64),
+    #[error("signal has no channels")]
+    NoChannels,
+    #[error("channel {channel} has {len} samples, expected {expected}")]
+    ChannelLengthMismatch {
+        channel: usize,
+        len: usize,
+        expected: usize,
+    },
+    #[error("segment length {0} is not a power of two ≥ 2")]
+    InvalidSegmentLen(usize),
+    #[error("overlap must be in [0,1), got {0}")]
+    InvalidOverlap(f64),
+    #[error("epoch of {epoch_samples} samples is shorter than segment length {segment_len}")]
+    EpochTooShort {
+        epoch_samples: usize,
+        segment_len: usize,
+    },
+    #[error("signal is shorter than one epoch")]
+    NoCompleteEpoch,
+    #[error("no positive reference power for band {0}")]
+    MissingReference(i32),
+}
+
+/// One-sided Welch PSD of `samples` (Hann window), in power per Hz.
+///
+/// Returns `segment_len / 2 + 1` bins spaced `sample_rate_hz / segment_len` apart.
+pub fn welch_psd(
+    samples: &[f64],
+    sample_rate_hz: f64,
+    welch: &WelchParams,
+) -> Result<Vec<f64>, BandPowerError> {
+    validate_welch(welch)?;
+    let n = welch.segment_len;
+    if samples.len() < n {
+        return Err(BandPowerError::EpochTooShort {
+            epoch_samples: samples.len(),
+            segment_len: n,
+        });
+    }
+
+    let window: Vec<f64> = (0..n)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f64::consts::PI * i as f64 / n as f64).cos())
+        .collect();
+    let window_power: f64 = window.iter().map(|w| w * w).sum();
+    let step = ((n as f64) * (1.0 - welch.overlap)).round().max(1.0) as usize;
+
+    let mut psd = vec![0.0; n / 2 + 1];
+    let mut segments = 0usize;
+    let mut start = 0;
+    while start + n <= samples.len() {
+        let segment = &samples[start..start + n];
+        let mean = segment.iter().sum::<f64>() / n as f64;
+        let mut re: Vec<f64> = segment
+            .iter()
+            .zip(&window)
+            .map(|(x, w)| (x - mean) * w)
+            .collect();
+        let mut im = vec![0.0; n];
+        fft_in_place(&mut re, &mut im);
+        for (k, p) in psd.iter_mut().enumerate() {
+            *p += re[k] * re[k] + im[k] * im[k];
+        }
+        segments += 1;
+        start += step;
+    }
+
+    let scale = 1.0 / (sample_rate_hz * window_power * segments as f64);
+    for (k, p) in psd.iter_mut().enumerate() {
+        *p *= scale;
+        // Fold negative frequencies; DC and Nyquist appear once.
+        if k != 0 && k != n / 2 {
+            *p *= 2.0;
+        }
+    }
+    Ok(psd)
+}
+
+/// Compute normalized `(band_index, μ_B, σ_B)` from raw signals.
+///
+/// For each epoch, each channel's Welch PSD is averaged across channels and
+/// integrated over every registry band; band powers are normalized against
+/// `params.reference` and clamped to [0,1]. μ_B and σ_B are the mean and
+/// population stddev of that normalized power across epochs. Output follows
+/// registry order.
+pub fn compute_band_power_inputs(
+    signal: &RawSignal,
+    registry: &BandRegistry,
+    params: &BandPowerParams,
+) -> Result<Vec<(i32, f64, f64)>, BandPowerError> {
+    let fs = signal.sample_rate_hz;
+    if !fs.is_finite() || fs <= 0.0 {
+        return Err(BandPowerError::InvalidSampleRate(fs));
+    }
+    let expected = signal
+        .channels
+        .first()
+        .ok_or(BandPowerError::NoChannels)?
+        .len();
+    for (channel, samples) in signal.channels.iter().enumerate() {
+        if samples.len() != expected {
+            return Err(BandPowerError::ChannelLengthMismatch {
+                channel,
+                len: samples.len(),
+                expected,
+            });
+        }
+    }
+    validate_welch(&params.welch)?;
+
+    let epoch_samples = (params.epoch_secs * fs).round().max(0.0) as usize;
+    if epoch_samples < params.welch.segment_len {
+        return Err(BandPowerError::EpochTooShort {
+            epoch_samples,
+            segment_len: params.welch.segment_len,
+        });
+    }
+    let epochs = expected / epoch_samples;
+    if epochs == 0 {
+        return Err(BandPowerError::NoCompleteEpoch);
+    }
+
+    let references = registry
+        .bands
+        .iter()
+        .map(|b| reference_power(&params.reference, b.index))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let bin_hz = fs / params.welch.segment_len as f64;
+    let mut per_band: Vec<Vec<f64>> = vec![Vec::with_capacity(epochs); registry.bands.len()];
+    for e in 0..epochs {
+        let range = e * epoch_samples..(e + 1) * epoch_samples;
+        let mut psd = vec![0.0; params.welch.segment_len / 2 + 1];
+        for samples in &signal.channels {
+            let channel_psd = welch_psd(&samples[range.clone()], fs, &params.welch)?;
+            for (acc, p) in psd.iter_mut().zip(channel_psd) {
+                *acc += p / signal.channels.len() as f64;
+            }
+        }
+
+        let powers: Vec<f64> = registry
+            .bands
+            .iter()
+            .map(|b| {
+                psd.iter()
+                    .enumerate()
+                    .filter(|(k, _)| b.contains_hz(*k as f64 * bin_hz))
+                    .map(|(_, p)| p * bin_hz)
+                    .sum()
+            })
+            .collect();
+        let total: f64 = powers.iter().sum();
+
+        for ((power, reference), out) in powers.iter().zip(&references).zip(&mut per_band) {
+            let denom = reference.unwrap_or(total);
+            out.push(if denom > 0.0 {
+                clamp01(power / denom)
+            } else {
+                0.0
+            });
+        }
+    }
+
+    Ok(registry
+        .bands
+        .iter()
+        .zip(per_band)
+        .map(|(b, values)| {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+            (b.index, clamp01(mean), clamp01(var.sqrt()))
+        })
+        .collect())
+}
+
+/// Fixed reference for a band; None means relative to the epoch total.
+fn reference_power(reference: &PowerReference, band: i32) -> Result<Option<f64>, BandPowerError> {
+    let power = match reference {
+        PowerReference::Relative => return Ok(None),
+        PowerReference::Absolute(p) => Some(*p),
+        PowerReference::PerBand(refs) => refs.iter().find(|(b, _)| *b == band).map(|(_, p)| *p),
+    };
+    match power {
+        Some(p) if p.is_finite() && p > 0.0 => Ok(Some(p)),
+        _ => Err(BandPowerError::MissingReference(band)),
+    }
+}
+
+fn validate_welch(welch: &WelchParams) -> Result<(), BandPowerError> {
+    if welch.segment_len < 2 || !welch.segment_len.is_power_of_two() {
+        return Err(BandPowerError::InvalidSegmentLen(welch.segment_len));
+    }
+    if !(0.0..1.0).contains(&welch.overlap) {
+        return Err(BandPowerError::InvalidOverlap(welch.overlap));
+    }
+    Ok(())
+}
+
+/// Iterative radix-2 FFT; `re.len()` must be a power of two.
+fn fft_in_place(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * std::f64::consts::PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let tr = re[b] * cos - im[b] * sin;
+                let ti = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FS: f64 = 256.0;
+
+    fn sine(hz: f64, amplitude: f64, secs: f64) -> Vec<f64> {
+        (0..(secs * FS) as usize)
+            .map(|i| amplitude * (2.0 * std::f64::consts::PI * hz * i as f64 / FS).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_welch_psd_recovers_sinusoid_power() {
+        let psd = welch_psd(&sine(16.0, 2.0, 4.0), FS, &WelchParams::default()).unwrap();
+        let peak = psd
+            .iter()
+            .enumerate()
+            .fold((0, 0.0), |m, (k, p)| if *p > m.1 { (k, *p) } else { m });
+        assert_eq!(peak.0, 16);
+        // Sinusoid power A²/2, spread over the Hann main lobe.
+        let total: f64 = psd.iter().sum::<f64>() * (FS / 256.0);
+        assert!((total - 2.0).abs() < 1e-6, "total {}", total);
+    }
+
+    #[test]
+    fn test_alpha_sinusoid_dominates_relative_power() {
+        let signal = RawSignal {
+            sample_rate_hz: FS,
+            channels: vec![sine(10.0, 1.0, 8.0), sine(10.0, 0.5, 8.0)],
+        };
+        let registry = BandRegistry::eeg_standard();
+        let params = BandPowerParams::default();
+        let inputs = compute_band_power_inputs(&signal, &registry, &params).unwrap();
+
+        assert_eq!(
+            inputs.iter().map(|b| b.0).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4]
+        );
+        let (_, alpha_mu, alpha_sigma) = inputs[2];
+        assert!(alpha_mu > 0.95, "alpha μ {}", alpha_mu);
+        assert!(alpha_sigma < 1e-9);
+        assert!(inputs[4].1 < 0.01);
+        assert_eq!(
+            inputs,
+            compute_band_power_inputs(&signal, &registry, &params).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_epoch_variation_sets_sigma() {
+        // Epoch 1: theta only; epoch 2: equal theta + beta.
+        let mut samples = sine(6.0, 1.0, 4.0);
+        samples.extend(
+            sine(6.0, 1.0, 4.0)
+                .iter()
+                .zip(sine(20.0, 1.0, 4.0))
+                .map(|(a, b)| a + b),
+        );
+        let signal = RawSignal {
+            sample_rate_hz: FS,
+            channels: vec![samples],
+        };
+        let inputs = compute_band_power_inputs(
+            &signal,
+            &BandRegistry::eeg_standard(),
+            &BandPowerParams::default(),
+        )
+        .unwrap();
+        let (_, theta_mu, theta_sigma) = inputs[1];
+        let (_, beta_mu, beta_sigma) = inputs[3];
+        assert!((theta_mu - 0.75).abs() < 0.02);
+        assert!((theta_sigma - 0.25).abs() < 0.02);
+        assert!((beta_mu - 0.25).abs() < 0.02);
+        assert!((beta_sigma - 0.25).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_absolute_reference_and_errors() {
+        let signal = RawSignal {
+            sample_rate_hz: FS,
+            channels: vec![sine(10.0, 1.0, 4.0)],
+        };
+        let registry = BandRegistry::eeg_standard();
+        let params = BandPowerParams {
+            reference: PowerReference::Absolute(1.0),
+            ..BandPowerParams::default()
+        };
+        let inputs = compute_band_power_inputs(&signal, &registry, &params).unwrap();
+        assert!((inputs[2].1 - 0.5).abs() < 0.01);
+
+        let params = BandPowerParams {
+            reference: PowerReference::PerBand(vec![(2, 1.0)]),
+            ..BandPowerParams::default()
+        };
+        assert_eq!(
+            compute_band_power_inputs(&signal, &registry, &params),
+            Err(BandPowerError::MissingReference(0))
+        );
+
+        let params = BandPowerParams {
+            epoch_secs: 8.0,
+            ..BandPowerParams::default()
+        };
+        assert_eq!(
+            compute_band_power_inputs(&signal, &registry, &params),
+            Err(BandPowerError::NoCompleteEpoch)
+        );
+
+        let params = BandPowerParams {
+            welch: WelchParams {
+                segment_len: 200,
+                overlap: 0.5,
+            },
+            ..BandPowerParams::default()
+        };
+        assert_eq!(
+            compute_band_power_inputs(&signal, &registry, &params),
+            Err(BandPowerError::InvalidSegmentLen(200))
+        );
+    }
+}
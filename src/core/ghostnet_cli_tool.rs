@@ -23,21 +23,30 @@
 #![forbid(unsafe_code)]
 
 use std::env;
+use std::fs::OpenOptions;
 use std::io::{self, Write};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use chrono::{Utc, DateTime};
 use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
 // Import internal modules (in production, these would be separate crates)
-// use crate::sanity_meter_core::{SanityMeter, SanityAction};
+// use crate::sanity_meter_core::SanityAction;
 // use crate::mist_whisper_detector::MistWhisperDetector;
 // use crate::companion_space_manager::CompanionSpaceManager;
-// use crate::spectral_rights_engine::SpectralRightsEngine;
+use crate::aln::{load_aln_file, AlnDocument};
+use crate::governance::abort_flush::{AbortAndFlush, FlushReport, FlushSigner, Flushable};
+use crate::haunt_density_mapper::ThreadSafeMapper;
+use crate::sanity_meter_core::SanityMeter;
+use crate::spectral_reality_model::SpectralRealityModel;
+use crate::spectral_rights_engine::{
+    bundled_policy, ActionOutcome, GuardEngine, RightsAction, RightsEnforcementMachine,
+    RightsInput, RightsState, TransitionContext,
+};
+use crate::spectral_schema_excavator::ExcavationBuffer;
 // use crate::spectral_quantification_utils::SpectralQuantifier;
-// use crate::haunt_density_mapper::{HauntDensityMap, ThreadSafeMapper};
 
 /// Identity constants for verification
 pub mod identity {
@@ -251,7 +260,7 @@ impl CliState {
         println!("OPTIONS:");
         println!("    -v, --verbose          Enable verbose output");
         println!("    --format <FORMAT>      Output format (text, json, ndjson)");
-        println!("    --config <PATH>        Configuration file path");
+        println!("    --config <PATH>        ALN policy file (default: bundled policy)");
         println!("    --log <PATH>           Log file path");
         println!("    --session <ID>         Session identifier");
         println!("    --region <ID>          Region identifier");
@@ -381,17 +390,170 @@ impl OutputFormatter {
 pub struct CommandExecutor {
     state: Arc<CliState>,
     formatter: OutputFormatter,
+    rights: Mutex<RightsEnforcementMachine>,
+    flush: Arc<Mutex<AbortAndFlush>>,
+    // Report of the flush run by the last `trigger_AbortAndFlush` action
+    last_flush: Arc<Mutex<Option<FlushReport>>>,
+    // In-memory spectral state held for this session; the same handles are
+    // registered with `flush`.
+    sanity: Arc<Mutex<SanityMeter>>,
+    mapper: Arc<Mutex<ThreadSafeMapper>>,
+    catalog: Arc<Mutex<SpectralRealityModel>>,
+    excavation: Arc<Mutex<ExcavationBuffer>>,
 }
 
 impl CommandExecutor {
-    pub fn new(state: CliState) -> Self {
+    /// Build an executor under the `--config` ALN policy, or the bundled
+    /// policy when none is given
+    pub fn new(state: CliState) -> CliResult<Self> {
+        let policy = Self::load_policy(&state)?;
         let formatter = OutputFormatter::new(&state.output_format, state.verbose);
-        Self {
+        // Reports are signed with `GHOSTNET_FLUSH_KEY`; without it they are
+        // marked unsigned rather than keyed by a published stamp.
+        let mut flush = FlushSigner::from_env("GHOSTNET_FLUSH_KEY")
+            .map(AbortAndFlush::new)
+            .unwrap_or_else(AbortAndFlush::unsigned)
+            .with_timeout_ms(policy.runtime.flush_timeout_ms);
+
+        let sanity = Arc::new(Mutex::new(SanityMeter::new(
+            state.session_id.clone(),
+            state.region_id.clone(),
+        )));
+        let mapper = Arc::new(Mutex::new(ThreadSafeMapper::new(format!(
+            "cli_{}",
+            state.region_id
+        ))));
+        let catalog = Arc::new(Mutex::new(SpectralRealityModel::with_guards(
+            GuardEngine::from_document(&policy),
+        )));
+        let excavation = Arc::new(Mutex::new(ExcavationBuffer::default()));
+        flush.register(Arc::clone(&sanity));
+        flush.register(Arc::clone(&mapper));
+        flush.register(Arc::clone(&catalog));
+        flush.register(Arc::clone(&excavation));
+
+        let flush = Arc::new(Mutex::new(flush));
+        let last_flush = Arc::new(Mutex::new(None));
+        let rights = Self::rights_machine(&state, &flush, &last_flush);
+
+        Ok(Self {
             state: Arc::new(state),
             formatter,
+            rights: Mutex::new(rights),
+            flush,
+            last_flush,
+            sanity,
+            mapper,
+            catalog,
+            excavation,
+        })
+    }
+
+    fn load_policy(state: &CliState) -> CliResult<AlnDocument> {
+        match &state.config_path {
+            Some(path) => load_aln_file(path).map_err(|e| {
+                CliError::FileOperationFailed(format!("policy {}: {}", path.display(), e))
+            }),
+            None => bundled_policy()
+                .map_err(|e| CliError::SystemError(format!("bundled policy: {}", e))),
         }
     }
 
+    /// Rights machine with the actions this CLI can carry out. Freezing
+    /// components, audit snapshots, credential revocation and restoring
+    /// defaults belong to the services and stay unhandled here.
+    fn rights_machine(
+        state: &CliState,
+        flush: &Arc<Mutex<AbortAndFlush>>,
+        last_flush: &Arc<Mutex<Option<FlushReport>>>,
+    ) -> RightsEnforcementMachine {
+        let mut machine = RightsEnforcementMachine::new();
+
+        let (flush, last_flush) = (Arc::clone(flush), Arc::clone(last_flush));
+        machine.register_handler(
+            RightsAction::TriggerAbortAndFlush,
+            Box::new(move |_: RightsAction, ctx: &TransitionContext| {
+                let report = flush
+                    .lock()
+                    .map_err(|_| "flush lock poisoned".to_string())?
+                    .trigger(&ctx.reason);
+                let complied = report.all_complied();
+                *last_flush.lock().unwrap_or_else(|e| e.into_inner()) = Some(report);
+                if complied {
+                    Ok(())
+                } else {
+                    Err("not every participant flushed before the deadline".to_string())
+                }
+            }),
+        );
+
+        let log_path = state.log_path.clone();
+        machine.register_handler(
+            RightsAction::LogToImmutableLedger,
+            Box::new(move |action: RightsAction, ctx: &TransitionContext| {
+                let path = log_path
+                    .as_ref()
+                    .ok_or_else(|| "no --log path configured".to_string())?;
+                let entry = serde_json::json!({
+                    "action": action.as_str(),
+                    "transition": ctx,
+                    "timestamp": Utc::now().to_rfc3339(),
+                    "aln_stamp": identity::ALN_STAMP,
+                });
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| format!("{}: {}", path.display(), e))?;
+                writeln!(file, "{}", entry).map_err(|e| format!("{}: {}", path.display(), e))
+            }),
+        );
+
+        // The operator running the CLI is the administrator on duty.
+        machine.register_handler(
+            RightsAction::NotifyAdministrators,
+            Box::new(|_: RightsAction, ctx: &TransitionContext| {
+                eprintln!(
+                    "⚠️  rights_enforcement {} -> {}: {}",
+                    ctx.from.as_str(),
+                    ctx.to.as_str(),
+                    ctx.reason
+                );
+                Ok(())
+            }),
+        );
+
+        machine
+    }
+
+    /// SANITY meter for this session
+    pub fn sanity_meter(&self) -> &Arc<Mutex<SanityMeter>> {
+        &self.sanity
+    }
+
+    /// Haunt-density map for this session's region
+    pub fn mapper(&self) -> &Arc<Mutex<ThreadSafeMapper>> {
+        &self.mapper
+    }
+
+    /// In-memory spectral-object catalog
+    pub fn catalog(&self) -> &Arc<Mutex<SpectralRealityModel>> {
+        &self.catalog
+    }
+
+    /// Excavated objects not yet written
+    pub fn excavation_buffer(&self) -> &Arc<Mutex<ExcavationBuffer>> {
+        &self.excavation
+    }
+
+    /// Register a component to be flushed by the abort-flush command
+    pub fn register_flushable<T: Flushable + 'static>(&self, participant: Arc<Mutex<T>>) {
+        self.flush
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .register(participant);
+    }
+
     /// Parse command from arguments
     pub fn parse_command(&self, args: &[String]) -> CliResult<CliCommand> {
         if args.is_empty() {
@@ -714,7 +876,35 @@ impl CommandExecutor {
 
     /// Audit rights command implementation
     async fn cmd_audit_rights(&self, region_id: Option<String>) -> CliResult<()> {
-        let audit = serde_json::json!({
+        let audit = self.rights_audit(region_id)?;
+        println!(
+            "{}",
+            self.formatter.format_success("Rights audit complete", Some(&audit))
+        );
+        Ok(())
+    }
+
+    fn rights_audit(&self, region_id: Option<String>) -> CliResult<serde_json::Value> {
+        let rights = self
+            .rights
+            .lock()
+            .map_err(|_| CliError::SystemError("Rights engine lock poisoned".to_string()))?;
+        let export = rights.export_state();
+        let violations_found = rights
+            .audit_trail()
+            .iter()
+            .filter(|r| r.accepted && r.input == RightsInput::RightsViolationDetected)
+            .count();
+        // An empty trail means nothing was observed, which is not evidence of compliance.
+        let status = if rights.audit_trail().is_empty() {
+            "NO_EVIDENCE"
+        } else if export.state == RightsState::Monitoring {
+            "COMPLIANT"
+        } else {
+            "ENFORCEMENT_ACTIVE"
+        };
+
+        Ok(serde_json::json!({
             "audit_type": "spectral_rights_compliance",
             "region_filter": region_id,
             "checks_performed": [
@@ -724,19 +914,16 @@ impl CommandExecutor {
                 "spectral_privacy",
                 "spectral_integrity"
             ],
-            "violations_found": 0,
-            "compliance_score": 100.0,
-            "status": "COMPLIANT",
+            "enforcement_state": export.state,
+            "transitions_recorded": export.transitions,
+            "rejected_transitions": export.rejected_transitions,
+            "last_transition": export.last_transition,
+            "violations_found": violations_found,
+            "status": status,
             "timestamp": Utc::now().to_rfc3339(),
             "aln_stamp": identity::ALN_STAMP,
             "hex_stamp": identity::HEX_STAMP,
-        });
-
-        println!(
-            "{}",
-            self.formatter.format_success("Rights audit complete", Some(&audit))
-        );
-        Ok(())
+        }))
     }
 
     /// Abort and flush command implementation
@@ -747,23 +934,7 @@ impl CommandExecutor {
             eprintln!("  Severity: {}", severity);
         }
 
-        let result = serde_json::json!({
-            "protocol": "AbortAndFlush",
-            "reason": reason,
-            "severity": severity,
-            "actions_taken": [
-                "buffers_cleared",
-                "sessions_terminated",
-                "companion_spaces_suspended",
-                "audit_log_updated",
-                "administrators_notified"
-            ],
-            "status": "EXECUTED",
-            "timestamp": Utc::now().to_rfc3339(),
-            "aln_stamp": identity::ALN_STAMP,
-            "hex_stamp": identity::HEX_STAMP,
-        });
-
+        let result = self.abort_flush(&reason, &severity)?;
         println!(
             "{}",
             self.formatter
@@ -772,6 +943,76 @@ impl CommandExecutor {
         Ok(())
     }
 
+    fn abort_flush(&self, reason: &str, severity: &str) -> CliResult<serde_json::Value> {
+        let mut rights = self
+            .rights
+            .lock()
+            .map_err(|_| CliError::SystemError("Rights engine lock poisoned".to_string()))?;
+        let detail = format!("{} ({})", reason, severity);
+        let trail_start = rights.audit_trail().len();
+        // Operator-triggered AbortAndFlush is a confirmed violation by definition.
+        if rights.state() == RightsState::Monitoring {
+            rights
+                .apply(RightsInput::RightsViolationDetected, detail.clone())
+                .map_err(|e| CliError::RightsViolation(e.to_string()))?;
+        }
+        if rights.state() == RightsState::Investigating {
+            rights
+                .apply(RightsInput::ViolationConfirmed, detail.clone())
+                .map_err(|e| CliError::RightsViolation(e.to_string()))?;
+        }
+        // Entering Enforcing runs trigger_AbortAndFlush. A machine already
+        // past that point (an earlier incomplete flush) is flushed again
+        // directly.
+        let flush_report = match self
+            .last_flush
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        {
+            Some(report) => report,
+            None => self
+                .flush
+                .lock()
+                .map_err(|_| CliError::SystemError("Flush lock poisoned".to_string()))?
+                .trigger(&detail),
+        };
+        // Enforcing -> Recovery once every participant has flushed. Recovery ->
+        // Monitoring (system_secure) is left to the operator, who must confirm
+        // restore_safe_defaults actually took effect; a new CLI process starts
+        // in Monitoring.
+        if flush_report.all_complied() && rights.state() == RightsState::Enforcing {
+            rights
+                .apply(RightsInput::EnforcementComplete, reason)
+                .map_err(|e| CliError::RightsViolation(e.to_string()))?;
+        }
+        let mut actions_taken = Vec::new();
+        let mut actions_failed = Vec::new();
+        let mut actions_unhandled = Vec::new();
+        for record in rights.audit_trail()[trail_start..].iter().flat_map(|r| &r.actions) {
+            match &record.outcome {
+                ActionOutcome::Completed => actions_taken.push(record.action),
+                ActionOutcome::Failed(_) => actions_failed.push(record.clone()),
+                ActionOutcome::Unhandled => actions_unhandled.push(record.action),
+            }
+        }
+
+        Ok(serde_json::json!({
+            "protocol": "AbortAndFlush",
+            "reason": reason,
+            "severity": severity,
+            "enforcement_state": rights.state(),
+            "actions_taken": actions_taken,
+            "actions_failed": actions_failed,
+            "actions_unhandled": actions_unhandled,
+            "flush_report": flush_report,
+            "status": if flush_report.all_complied() { "EXECUTED" } else { "INCOMPLETE" },
+            "timestamp": Utc::now().to_rfc3339(),
+            "aln_stamp": identity::ALN_STAMP,
+            "hex_stamp": identity::HEX_STAMP,
+        }))
+    }
+
     /// Export ledger command implementation
     async fn cmd_export_ledger(&self, output_path: String, format: String) -> CliResult<()> {
         // Simulated export (in production, write to actual file)
@@ -915,7 +1156,13 @@ async fn main() {
         }
     };
 
-    let executor = CommandExecutor::new(state);
+    let executor = match CommandExecutor::new(state) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
     let args: Vec<String> = env::args().skip(1).collect();
 
     // Filter out global flags for command parsing
@@ -949,6 +1196,8 @@ async fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::spectral_reality_model::{Origin, SpectralKind, SpectralObject};
+    use std::collections::HashMap;
 
     #[test]
     fn test_cli_state_creation() {
@@ -962,7 +1211,7 @@ mod tests {
     #[test]
     fn test_command_parsing() {
         let state = CliState::new();
-        let executor = CommandExecutor::new(state);
+        let executor = CommandExecutor::new(state).unwrap();
 
         let args = vec!["version".to_string()];
         let cmd = executor.parse_command(&args);
@@ -974,6 +1223,102 @@ mod tests {
         assert!(cmd.is_ok());
     }
 
+    #[test]
+    fn test_executor_registers_flush_participants() {
+        let mut state = CliState::new();
+        state.session_id = "session_001".to_string();
+        state.region_id = "region_alpha".to_string();
+        let executor = CommandExecutor::new(state).unwrap();
+        assert_eq!(executor.flush.lock().unwrap().participant_count(), 4);
+
+        // Live session state the flush must reach
+        let guards = GuardEngine::default();
+        let mapper = executor.mapper().lock().unwrap().clone();
+        mapper.update_cell(3, 3, 0.9, 0.5, &[], &guards).unwrap();
+        mapper
+            .register_companion_space("space_001".to_string(), vec![(4, 4)])
+            .unwrap();
+        executor.catalog().lock().unwrap().upsert(SpectralObject::new(
+            SpectralKind::TracePattern,
+            Origin {
+                domain: "shop.example.com".to_string(),
+                system: "checkout".to_string(),
+                run_id: "r1".to_string(),
+                modality: "trace".to_string(),
+            },
+            HashMap::new(),
+        ));
+        executor.sanity_meter().lock().unwrap().start_interval().unwrap();
+
+        let report = executor.flush.lock().unwrap().trigger("test");
+        assert_eq!(report.deadline_ms, 50);
+        assert!(report.all_complied());
+        let names: Vec<_> = report.participants.iter().map(|p| p.participant.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "sanity_meter:session_001",
+                "haunt_density_map:cli_region_alpha",
+                "spectral_reality_model",
+                "spectral_schema_excavator",
+            ]
+        );
+        let flushed: Vec<_> = report
+            .participants
+            .iter()
+            .map(|p| p.outcome.as_ref().map_or(0, |o| o.cleared + o.quarantined))
+            .collect();
+        assert_eq!(flushed, [1, 3, 1, 0]);
+
+        let map = mapper.export_state();
+        assert_eq!(map.companion_space_count, 0);
+        assert_eq!(map.statistics.max_density, 0.0);
+        assert_eq!(executor.catalog().lock().unwrap().quarantined(), 1);
+        assert_eq!(executor.sanity_meter().lock().unwrap().export_state().sanity, 0.0);
+        assert!(executor.excavation_buffer().lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_abort_flush_runs_through_rights_handlers() {
+        let executor = CommandExecutor::new(CliState::new()).unwrap();
+        let audit = executor.rights_audit(None).unwrap();
+        assert_eq!(audit["status"], "NO_EVIDENCE");
+
+        executor.catalog().lock().unwrap().upsert(SpectralObject::new(
+            SpectralKind::TracePattern,
+            Origin {
+                domain: "shop.example.com".to_string(),
+                system: "checkout".to_string(),
+                run_id: "r1".to_string(),
+                modality: "trace".to_string(),
+            },
+            HashMap::new(),
+        ));
+        let result = executor.abort_flush("soul_modeling_detected", "CRITICAL").unwrap();
+        assert_eq!(result["status"], "EXECUTED");
+        assert_eq!(result["enforcement_state"], "Recovery");
+        assert_eq!(
+            result["actions_taken"],
+            serde_json::json!(["trigger_abort_and_flush", "notify_administrators"])
+        );
+        // No --log path: the ledger write fails and is not counted as taken.
+        assert_eq!(result["actions_failed"][0]["action"], "log_to_immutable_ledger");
+        assert_eq!(
+            result["actions_unhandled"],
+            serde_json::json!([
+                "freeze_affected_components",
+                "snapshot_state_for_audit",
+                "revoke_access_credentials",
+                "restore_safe_defaults"
+            ])
+        );
+        assert_eq!(executor.catalog().lock().unwrap().quarantined(), 1);
+
+        let audit = executor.rights_audit(None).unwrap();
+        assert_eq!(audit["status"], "ENFORCEMENT_ACTIVE");
+        assert_eq!(audit["violations_found"], 1);
+    }
+
     #[test]
     fn test_output_formatter_json() {
         let formatter = OutputFormatter::new("json", false);
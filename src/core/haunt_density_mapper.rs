@@ -22,6 +22,10 @@ use std::sync::{Arc, RwLock};
 use serde::{Deserialize, Serialize};
 use chrono::{Utc, DateTime};
 
+use crate::governance::abort_flush::{FlushOutcome, Flushable};
+use crate::governance::PolicyViolation;
+use crate::spectral_rights_engine::{GuardEngine, GuardEventInput, GuardVerdict};
+
 /// Identity constants for verification
 pub mod identity {
     pub const ALN_STAMP: &str = "aln18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
@@ -167,7 +171,7 @@ pub enum MapperError {
     #[error("Invalid spectral energy value: {0}")]
     InvalidSpectralEnergy(f64),
     #[error("Soul-modeling detected in cell metadata")]
-    SoulModelingDetected,
+    SoulModelingDetected(Vec<PolicyViolation>),
     #[error("Companion space conflict: {0}")]
     CompanionSpaceConflict(String),
     #[error("Grid lock poisoned")]
@@ -238,18 +242,18 @@ impl HauntDensityMap {
         Ok(&mut self.cells[idx])
     }
 
-    /// Update cell metrics
-    pub fn update_cell(
+    /// Write cell metrics; callers must have run the ingest guards first
+    fn write_cell(
         &mut self,
         x: usize,
         y: usize,
         haunt_density: f64,
         spectral_energy: f64,
     ) -> Result<(), MapperError> {
-        if haunt_density < 0.0 || haunt_density > 1.0 {
+        if !(0.0..=1.0).contains(&haunt_density) {
             return Err(MapperError::InvalidHauntDensity(haunt_density));
         }
-        if spectral_energy < 0.0 || spectral_energy > 1.0 {
+        if !(0.0..=1.0).contains(&spectral_energy) {
             return Err(MapperError::InvalidSpectralEnergy(spectral_energy));
         }
 
@@ -258,6 +262,42 @@ impl HauntDensityMap {
         Ok(())
     }
 
+    /// Update cell metrics after running the `data_ingest` guards over the
+    /// sample's metadata keys. Soul-modeling is non-waivable: a blocking
+    /// verdict rejects the update in every enforcement mode
+    pub fn update_cell(
+        &mut self,
+        x: usize,
+        y: usize,
+        haunt_density: f64,
+        spectral_energy: f64,
+        metadata_keys: &[String],
+        guards: &GuardEngine,
+    ) -> Result<GuardVerdict, MapperError> {
+        let mut violations = Vec::new();
+        let verdict = guards
+            .enforce_ingest(metadata_keys, &mut violations)
+            .map_err(|_| MapperError::SoulModelingDetected(violations))?;
+        self.write_cell(x, y, haunt_density, spectral_energy)?;
+        Ok(verdict)
+    }
+
+    /// Run the `zone_entry` guards for an entity entering a cell
+    pub fn zone_entry_verdict(
+        &self,
+        x: usize,
+        y: usize,
+        entity_id: &str,
+        guards: &GuardEngine,
+    ) -> Result<GuardVerdict, MapperError> {
+        let cell = self.get_cell(x, y)?;
+        let status = if cell.is_companion_space { "active" } else { "inactive" };
+        Ok(guards.evaluate(&GuardEventInput::ZoneEntry {
+            entity_id: entity_id.to_string(),
+            companion_space_status: status.to_string(),
+        }))
+    }
+
     /// Register companion space on cells
     pub fn register_companion_space(
         &mut self,
@@ -381,6 +421,32 @@ pub struct MapExport {
     pub timestamp: DateTime<Utc>,
 }
 
+impl Flushable for HauntDensityMap {
+    fn flush_name(&self) -> String {
+        format!("haunt_density_map:{}", self.map_id)
+    }
+
+    /// Suspend all companion spaces and zero every cell's metrics
+    fn abort_and_flush(&mut self, _reason: &str) -> Result<FlushOutcome, String> {
+        let quarantined = self.companion_spaces.len();
+        self.companion_spaces.clear();
+
+        let mut cleared = 0;
+        for cell in &mut self.cells {
+            if cell.haunt_density != 0.0 || cell.spectral_energy != 0.0 || cell.is_companion_space {
+                cleared += 1;
+            }
+            cell.deactivate_companion_space();
+            cell.update(0.0, 0.0);
+        }
+        Ok(FlushOutcome {
+            cleared,
+            quarantined,
+            detail: "cells zeroed, companion spaces suspended".to_string(),
+        })
+    }
+}
+
 /// Thread-safe haunt density mapper
 pub struct ThreadSafeMapper {
     inner: Arc<RwLock<HauntDensityMap>>,
@@ -394,16 +460,18 @@ impl ThreadSafeMapper {
         }
     }
 
-    /// Update cell (thread-safe)
+    /// Guarded cell update (thread-safe)
     pub fn update_cell(
         &self,
         x: usize,
         y: usize,
         haunt_density: f64,
         spectral_energy: f64,
-    ) -> Result<(), MapperError> {
+        metadata_keys: &[String],
+        guards: &GuardEngine,
+    ) -> Result<GuardVerdict, MapperError> {
         let mut map = self.inner.write().map_err(|_| MapperError::GridLockPoisoned)?;
-        map.update_cell(x, y, haunt_density, spectral_energy)
+        map.update_cell(x, y, haunt_density, spectral_energy, metadata_keys, guards)
     }
 
     /// Get zone (thread-safe)
@@ -429,6 +497,23 @@ impl ThreadSafeMapper {
     }
 }
 
+impl Flushable for ThreadSafeMapper {
+    fn flush_name(&self) -> String {
+        match self.inner.read() {
+            Ok(map) => map.flush_name(),
+            Err(_) => "haunt_density_map:<poisoned>".to_string(),
+        }
+    }
+
+    fn abort_and_flush(&mut self, reason: &str) -> Result<FlushOutcome, String> {
+        let mut map = self
+            .inner
+            .write()
+            .map_err(|_| MapperError::GridLockPoisoned.to_string())?;
+        map.abort_and_flush(reason)
+    }
+}
+
 impl Clone for ThreadSafeMapper {
     fn clone(&self) -> Self {
         Self {
@@ -440,6 +525,7 @@ impl Clone for ThreadSafeMapper {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::governance::EnforcementMode;
 
     #[test]
     fn test_zone_classification() {
@@ -461,7 +547,7 @@ mod tests {
     #[test]
     fn test_cell_update() {
         let mut map = HauntDensityMap::new("test".to_string());
-        assert!(map.update_cell(0, 0, 0.5, 0.6).is_ok());
+        assert!(map.update_cell(0, 0, 0.5, 0.6, &[], &GuardEngine::default()).is_ok());
         
         let cell = map.get_cell(0, 0).unwrap();
         assert_eq!(cell.haunt_density, 0.5);
@@ -486,10 +572,10 @@ mod tests {
         let mapper_clone = mapper.clone();
         
         let handle = std::thread::spawn(move || {
-            mapper_clone.update_cell(10, 10, 0.5, 0.5).unwrap();
+            mapper_clone.update_cell(10, 10, 0.5, 0.5, &[], &GuardEngine::default()).unwrap();
         });
         
-        mapper.update_cell(20, 20, 0.3, 0.3).unwrap();
+        mapper.update_cell(20, 20, 0.3, 0.3, &[], &GuardEngine::default()).unwrap();
         handle.join().unwrap();
         
         let zone1 = mapper.get_zone(10, 10).unwrap();
@@ -498,4 +584,32 @@ mod tests {
         assert_eq!(zone1, ZoneClassification::MonitoredHigh);
         assert_eq!(zone2, ZoneClassification::MonitoredLow);
     }
+
+    #[test]
+    fn test_guarded_update_and_zone_entry() {
+        let guards = GuardEngine::bundled().unwrap();
+        let mut map = HauntDensityMap::new("guarded".to_string());
+
+        let bad = vec!["essence".to_string()];
+        assert!(matches!(
+            map.update_cell(3, 3, 0.5, 0.5, &bad, &guards),
+            Err(MapperError::SoulModelingDetected(v)) if v[0].enforced
+        ));
+        assert_eq!(map.get_cell(3, 3).unwrap().haunt_density, 0.0);
+
+        let ok = vec!["sensor_id".to_string()];
+        assert!(map.update_cell(3, 3, 0.5, 0.5, &ok, &guards).unwrap().is_clear());
+        assert_eq!(map.get_cell(3, 3).unwrap().haunt_density, 0.5);
+
+        map.register_companion_space("space_002".to_string(), vec![(4, 4)]).unwrap();
+        assert!(map.zone_entry_verdict(4, 4, "visitor", &guards).unwrap().enforces_observe_only());
+        assert!(!map.zone_entry_verdict(3, 3, "visitor", &guards).unwrap().enforces_observe_only());
+
+        // Soul-modeling blocks even when the engine is advisory or disabled.
+        for mode in [EnforcementMode::Advisory, EnforcementMode::Disabled] {
+            let relaxed = guards.clone().with_mode(mode);
+            assert!(map.update_cell(5, 5, 0.7, 0.2, &bad, &relaxed).is_err());
+            assert_eq!(map.get_cell(5, 5).unwrap().haunt_density, 0.0);
+        }
+    }
 }
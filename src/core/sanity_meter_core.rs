@@ -17,6 +17,9 @@
 use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 
+use crate::governance::abort_flush::{FlushOutcome, Flushable};
+use crate::governance::{EnforcementMode, PolicyViolation};
+
 /// Maximum session duration in seconds (2 hours)
 pub const MAX_SESSION_SECONDS: f64 = 7200.0;
 
@@ -220,6 +223,8 @@ pub struct SanityMeter {
     pub session_id: String,
     /// Region ID for zoning
     pub region_id: String,
+    /// How escalation gates are applied (ALN `enforcement_mode`)
+    pub enforcement_mode: EnforcementMode,
     /// ALN identity stamp
     pub aln_stamp: String,
     /// Bostrom identity stamp
@@ -270,6 +275,8 @@ pub struct DrainResult {
     pub emotional_state: EmotionalState,
     /// Action required
     pub action: SanityAction,
+    /// Escalations that fired; unenforced entries mean advisory mode
+    pub violations: Vec<PolicyViolation>,
 }
 
 /// Action required based on SANITY state
@@ -302,6 +309,7 @@ impl SanityMeter {
             c_1: 1.0,
             session_id,
             region_id,
+            enforcement_mode: EnforcementMode::Strict,
             aln_stamp: "aln18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
             bostrom_stamp: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
             hex_stamp: "0x53414e4954595f4d455445525f5249534b5f5631".to_string(),
@@ -378,7 +386,8 @@ impl SanityMeter {
         self.interval_start = None;
 
         // Determine action required
-        let action = self.determine_action(risk_index, haunt_band);
+        let mut violations = Vec::new();
+        let action = self.determine_action(risk_index, haunt_band, &mut violations);
 
         Ok(DrainResult {
             base_drain,
@@ -389,28 +398,50 @@ impl SanityMeter {
             haunt_band,
             emotional_state,
             action,
+            violations,
         })
     }
 
-    /// Determine action based on SANITY state and risk
-    fn determine_action(&self, risk_index: f64, haunt_band: HauntDensityBand) -> SanityAction {
+    /// Determine action based on SANITY state and risk.
+    /// Escalations past MistWhisper follow the enforcement mode; in advisory
+    /// mode they are recorded in `violations` and the warning-level action is returned.
+    fn determine_action(
+        &self,
+        risk_index: f64,
+        haunt_band: HauntDensityBand,
+        violations: &mut Vec<PolicyViolation>,
+    ) -> SanityAction {
+        // Check if risk exceeds safe threshold for this band
+        let safe_threshold = haunt_band.safe_exposure_threshold();
+        let warning = if risk_index > safe_threshold {
+            SanityAction::MistWhisper
+        } else {
+            SanityAction::Continue
+        };
+
         // Critical threshold - immediate termination
         if self.sanity <= SANITY_CRITICAL_THRESHOLD {
-            return SanityAction::AbortAndFlush;
+            return self.enforcement_mode.resolve(
+                "sanity.critical",
+                format!("SANITY {:.3} requires AbortAndFlush", self.sanity),
+                SanityAction::AbortAndFlush,
+                warning,
+                violations,
+            );
         }
 
         // De-escalation threshold
         if self.sanity <= SANITY_DEESCALATE_THRESHOLD {
-            return SanityAction::Deescalate;
+            return self.enforcement_mode.resolve(
+                "sanity.deescalate",
+                format!("SANITY {:.3} requires Deescalate", self.sanity),
+                SanityAction::Deescalate,
+                warning,
+                violations,
+            );
         }
 
-        // Check if risk exceeds safe threshold for this band
-        let safe_threshold = haunt_band.safe_exposure_threshold();
-        if risk_index > safe_threshold {
-            return SanityAction::MistWhisper;
-        }
-
-        SanityAction::Continue
+        warning
     }
 
     /// Get current session duration
@@ -477,6 +508,25 @@ impl SanityMeter {
     }
 }
 
+impl Flushable for SanityMeter {
+    fn flush_name(&self) -> String {
+        format!("sanity_meter:{}", self.session_id)
+    }
+
+    /// Cancel any open haunt-interval and pin SANITY at zero so the session
+    /// cannot resume until `reset_session`
+    fn abort_and_flush(&mut self, _reason: &str) -> Result<FlushOutcome, String> {
+        let cleared = usize::from(self.interval_start.take().is_some());
+        self.current_interval_duration = Duration::ZERO;
+        self.sanity = 0.0;
+        Ok(FlushOutcome {
+            cleared,
+            quarantined: 0,
+            detail: "interval cancelled, session terminated".to_string(),
+        })
+    }
+}
+
 /// Exported state for ledger logging
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SanityStateExport {
@@ -544,4 +594,27 @@ mod tests {
         assert!(result.sanity_remaining < 1.0);
         assert!(result.sanity_remaining >= 0.0);
     }
+
+    #[test]
+    fn test_enforcement_mode_gates_escalation() {
+        let mut meter = SanityMeter::new("test".to_string(), "test".to_string());
+        meter.sanity = 0.04;
+        let mut violations = Vec::new();
+        let action = meter.determine_action(0.9, HauntDensityBand::Control, &mut violations);
+        assert_eq!(action, SanityAction::AbortAndFlush);
+        assert!(violations[0].enforced);
+
+        meter.enforcement_mode = EnforcementMode::Advisory;
+        let mut violations = Vec::new();
+        let action = meter.determine_action(0.9, HauntDensityBand::Control, &mut violations);
+        assert_eq!(action, SanityAction::MistWhisper);
+        assert_eq!(violations[0].gate, "sanity.critical");
+        assert!(!violations[0].enforced);
+
+        meter.enforcement_mode = EnforcementMode::Disabled;
+        let mut violations = Vec::new();
+        let action = meter.determine_action(0.0, HauntDensityBand::Control, &mut violations);
+        assert_eq!(action, SanityAction::Continue);
+        assert!(violations.is_empty());
+    }
 }
@@ -1,8 +1,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
 use uuid::Uuid;
 
+use crate::governance::abort_flush::{FlushOutcome, Flushable};
+use crate::governance::GovernanceMode;
+use crate::spectral_rights_engine::{GuardEngine, GuardEventInput};
+
+pub mod store;
+
 /// Kinds of spectral‑objects that can be excavated.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum SpectralKind {
@@ -91,53 +99,391 @@ impl SpectralObject {
     }
 }
 
+/// Declared purpose of a catalog or history read.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryIntent {
+    IntegrityCheck,
+    HauntDensityRecompute,
+    ZoningAudit,
+    /// Ordinary catalog lookup; not allowed on Dormant histories.
+    CatalogLookup,
+    /// Writing annotations into catalog objects; not allowed on Dormant histories.
+    Annotate,
+    /// Rebuilding a dormant entity; always denied.
+    Reconstruction,
+    /// Simulating a dormant entity; always denied.
+    Simulation,
+}
+
+impl QueryIntent {
+    /// Wire name, matched by the ALN `non_resurrection_right` guard.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueryIntent::IntegrityCheck => "integrity_check",
+            QueryIntent::HauntDensityRecompute => "haunt_density_recompute",
+            QueryIntent::ZoningAudit => "zoning_audit",
+            QueryIntent::CatalogLookup => "catalog_lookup",
+            QueryIntent::Annotate => "annotate",
+            QueryIntent::Reconstruction => "reconstruction",
+            QueryIntent::Simulation => "simulation",
+        }
+    }
+
+    /// True if this intent may read histories under `mode`.
+    ///
+    /// Dormant histories serve only integrity checks, HauntDensity recompute
+    /// and zoning audits (SpectralVision.md §3).
+    pub fn allowed_in(&self, mode: GovernanceMode) -> bool {
+        match self {
+            QueryIntent::IntegrityCheck
+            | QueryIntent::HauntDensityRecompute
+            | QueryIntent::ZoningAudit => true,
+            QueryIntent::CatalogLookup | QueryIntent::Annotate => mode != GovernanceMode::Dormant,
+            QueryIntent::Reconstruction | QueryIntent::Simulation => false,
+        }
+    }
+}
+
+impl fmt::Display for QueryIntent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Intent and governance mode every query must carry.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QueryContext {
+    pub intent: QueryIntent,
+    pub mode: GovernanceMode,
+}
+
+impl QueryContext {
+    pub fn new(intent: QueryIntent, mode: GovernanceMode) -> Self {
+        Self { intent, mode }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum QueryError {
+    #[error("query {query} denied for intent {intent} in {mode:?} mode: {reason}")]
+    Denied {
+        query: String,
+        intent: QueryIntent,
+        mode: GovernanceMode,
+        reason: String,
+    },
+}
+
+/// Logged record of a denied query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryDenial {
+    pub timestamp: DateTime<Utc>,
+    pub query: String,
+    pub intent: QueryIntent,
+    pub mode: GovernanceMode,
+    pub reason: String,
+    /// Cached result entries removed for this query.
+    pub purged_cache_entries: usize,
+}
+
+#[derive(Debug, Default)]
+struct QueryLedger {
+    /// Cached object IDs per query key.
+    cache: HashMap<String, Vec<String>>,
+    denials: Vec<QueryDenial>,
+}
+
 /// Catalog of spectral‑objects.
+///
+/// Every read carries a [`QueryContext`]; reads denied by the ALN query
+/// guards or the governance-mode allow-list fail with [`QueryError`], are
+/// logged, and purge the cached results for that query.
 #[derive(Debug, Default)]
 pub struct SpectralRealityModel {
     objects: HashMap<String, SpectralObject>,
+    /// Objects moved out of reach by AbortAndFlush, kept for audit.
+    quarantine: HashMap<String, SpectralObject>,
+    guards: GuardEngine,
+    ledger: Mutex<QueryLedger>,
 }
 
 impl SpectralRealityModel {
-    /// Inserts or updates a spectral‑object.
+    /// Creates an empty model whose queries are also checked by `guards`
+    /// (the `data_query` handlers, e.g. `non_resurrection_right`).
+    pub fn with_guards(guards: GuardEngine) -> Self {
+        Self {
+            guards,
+            ..Self::default()
+        }
+    }
+
+    /// Inserts or updates a spectral‑object. Invalidates cached query results.
     pub fn upsert(&mut self, obj: SpectralObject) -> &SpectralObject {
         let id = obj.id.clone();
-        self.objects.insert(id, obj);
+        self.objects.insert(id.clone(), obj);
+        self.ledger_mut().cache.clear();
         self.objects.get(&id).unwrap()
     }
 
     /// Retrieves a spectral‑object by ID.
-    pub fn get_by_id(&self, id: &str) -> Option<&SpectralObject> {
-        self.objects.get(id)
+    pub fn get_by_id(
+        &self,
+        id: &str,
+        ctx: &QueryContext,
+    ) -> Result<Option<&SpectralObject>, QueryError> {
+        self.authorize(&format!("id:{}", id), ctx)?;
+        Ok(self.objects.get(id))
     }
 
     /// Lists all objects of a given kind.
-    pub fn list_by_kind(&self, kind: &SpectralKind) -> Vec<&SpectralObject> {
-        self.objects
-            .values()
-            .filter(|o| &o.kind == kind)
-            .collect()
+    pub fn list_by_kind(
+        &self,
+        kind: &SpectralKind,
+        ctx: &QueryContext,
+    ) -> Result<Vec<&SpectralObject>, QueryError> {
+        self.cached_query(&format!("kind:{:?}", kind), ctx, |o| &o.kind == kind)
     }
 
     /// Lists objects with high stability and low drift.
-    pub fn list_high_stability(&self, threshold: f64) -> Vec<&SpectralObject> {
-        self.objects
-            .values()
-            .filter(|o| o.stability >= threshold && o.drift <= 1.0 - threshold)
-            .collect()
+    pub fn list_high_stability(
+        &self,
+        threshold: f64,
+        ctx: &QueryContext,
+    ) -> Result<Vec<&SpectralObject>, QueryError> {
+        self.cached_query(&format!("stability:{}", threshold), ctx, |o| {
+            o.stability >= threshold && o.drift <= 1.0 - threshold
+        })
     }
 
     /// Lists objects from a specific domain.
-    pub fn list_by_domain(&self, domain: &str) -> Vec<&SpectralObject> {
-        self.objects
-            .values()
-            .filter(|o| o.origin.domain == domain)
-            .collect()
+    pub fn list_by_domain(
+        &self,
+        domain: &str,
+        ctx: &QueryContext,
+    ) -> Result<Vec<&SpectralObject>, QueryError> {
+        self.cached_query(&format!("domain:{}", domain), ctx, |o| {
+            o.origin.domain == domain
+        })
+    }
+
+    /// Mutable access to every object, for in-place annotation such as
+    /// spectral-vision batch results. Only the [`QueryIntent::Annotate`]
+    /// intent may write, so audit intents stay read-only even when Dormant.
+    pub fn objects_mut(
+        &mut self,
+        ctx: &QueryContext,
+    ) -> Result<Vec<&mut SpectralObject>, QueryError> {
+        if ctx.intent != QueryIntent::Annotate {
+            return Err(self.deny(
+                "objects_mut",
+                ctx,
+                format!("intent {} is read-only", ctx.intent),
+            ));
+        }
+        self.authorize("objects_mut", ctx)?;
+        Ok(self.objects.values_mut().collect())
     }
 
     /// Exports a snapshot of the catalog as JSON.
-    pub fn snapshot(&self) -> serde_json::Value {
-        serde_json::to_value(self.objects.values().collect::<Vec<&SpectralObject>>()).unwrap()
+    pub fn snapshot(&self, ctx: &QueryContext) -> Result<serde_json::Value, QueryError> {
+        self.authorize("snapshot", ctx)?;
+        Ok(serde_json::to_value(self.objects.values().collect::<Vec<&SpectralObject>>()).unwrap())
+    }
+
+    /// Denied queries, oldest first.
+    pub fn denials(&self) -> Vec<QueryDenial> {
+        self.ledger().denials.clone()
+    }
+
+    /// Number of objects quarantined by AbortAndFlush.
+    pub fn quarantined(&self) -> usize {
+        self.quarantine.len()
+    }
+
+    /// Number of cached query results.
+    pub fn cached_queries(&self) -> usize {
+        self.ledger().cache.len()
+    }
+
+    /// Checks `ctx` against the query guards and the mode allow-list. On
+    /// denial, logs it and purges the cached results for `query`.
+    fn authorize(&self, query: &str, ctx: &QueryContext) -> Result<(), QueryError> {
+        let verdict = self.guards.evaluate(&GuardEventInput::DataQuery {
+            intent: ctx.intent.as_str().to_string(),
+        });
+        let reason = if verdict.is_blocking() {
+            verdict
+                .reasons
+                .first()
+                .cloned()
+                .unwrap_or_else(|| format!("denied by {}", verdict.triggered_by.join(", ")))
+        } else if !ctx.intent.allowed_in(ctx.mode) {
+            format!(
+                "intent not allowed in {:?} mode (non-resurrection)",
+                ctx.mode
+            )
+        } else {
+            return Ok(());
+        };
+        Err(self.deny(query, ctx, reason))
+    }
+
+    /// Logs a denied query and purges its cached results.
+    fn deny(&self, query: &str, ctx: &QueryContext, reason: String) -> QueryError {
+        let mut ledger = self.ledger();
+        let purged = ledger.cache.remove(query).map_or(0, |ids| ids.len());
+        ledger.denials.push(QueryDenial {
+            timestamp: Utc::now(),
+            query: query.to_string(),
+            intent: ctx.intent,
+            mode: ctx.mode,
+            reason: reason.clone(),
+            purged_cache_entries: purged,
+        });
+        QueryError::Denied {
+            query: query.to_string(),
+            intent: ctx.intent,
+            mode: ctx.mode,
+            reason,
+        }
+    }
+
+    fn cached_query(
+        &self,
+        query: &str,
+        ctx: &QueryContext,
+        filter: impl Fn(&SpectralObject) -> bool,
+    ) -> Result<Vec<&SpectralObject>, QueryError> {
+        self.authorize(query, ctx)?;
+        let mut ledger = self.ledger();
+        let ids = ledger.cache.entry(query.to_string()).or_insert_with(|| {
+            self.objects
+                .values()
+                .filter(|o| filter(o))
+                .map(|o| o.id.clone())
+                .collect()
+        });
+        Ok(ids.iter().filter_map(|id| self.objects.get(id)).collect())
+    }
+
+    fn ledger(&self) -> std::sync::MutexGuard<'_, QueryLedger> {
+        self.ledger.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn ledger_mut(&mut self) -> &mut QueryLedger {
+        self.ledger.get_mut().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Flushable for SpectralRealityModel {
+    fn flush_name(&self) -> String {
+        "spectral_reality_model".to_string()
+    }
+
+    /// Quarantine every catalog object and drop all cached query results.
+    fn abort_and_flush(&mut self, _reason: &str) -> Result<FlushOutcome, String> {
+        let quarantined = self.objects.len();
+        self.quarantine.extend(self.objects.drain());
+        let ledger = self.ledger_mut();
+        let cleared = ledger.cache.len();
+        ledger.cache.clear();
+        Ok(FlushOutcome {
+            cleared,
+            quarantined,
+            detail: "catalog quarantined, query cache purged".to_string(),
+        })
     }
 }
 
-// Example unit tests (omitted for brevity) would verify insertion, querying, and scoring logic.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(domain: &str) -> SpectralObject {
+        SpectralObject::new(
+            SpectralKind::TracePattern,
+            Origin {
+                domain: domain.to_string(),
+                system: "checkout".to_string(),
+                run_id: "r1".to_string(),
+                modality: "trace".to_string(),
+            },
+            HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_dormant_mode_allows_only_audit_intents() {
+        let mut model = SpectralRealityModel::default();
+        model.upsert(object("shop.example.com"));
+
+        let audit = QueryContext::new(QueryIntent::ZoningAudit, GovernanceMode::Dormant);
+        assert_eq!(
+            model
+                .list_by_domain("shop.example.com", &audit)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let lookup = QueryContext::new(QueryIntent::CatalogLookup, GovernanceMode::Dormant);
+        let err = model
+            .list_by_domain("shop.example.com", &lookup)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            QueryError::Denied {
+                intent: QueryIntent::CatalogLookup,
+                ..
+            }
+        ));
+
+        let active = QueryContext::new(QueryIntent::CatalogLookup, GovernanceMode::ActiveGoverned);
+        assert!(model.snapshot(&active).is_ok());
+    }
+
+    #[test]
+    fn test_reconstruction_denied_logged_and_cache_purged() {
+        let mut model = SpectralRealityModel::with_guards(GuardEngine::bundled().unwrap());
+        model.upsert(object("shop.example.com"));
+
+        let audit = QueryContext::new(QueryIntent::IntegrityCheck, GovernanceMode::ActiveGoverned);
+        model
+            .list_by_kind(&SpectralKind::TracePattern, &audit)
+            .unwrap();
+        assert_eq!(model.cached_queries(), 1);
+
+        let resurrect =
+            QueryContext::new(QueryIntent::Reconstruction, GovernanceMode::ActiveGoverned);
+        let err = model
+            .list_by_kind(&SpectralKind::TracePattern, &resurrect)
+            .unwrap_err();
+        assert!(err.to_string().contains("Violation of Spectral Continuity"));
+        assert_eq!(model.cached_queries(), 0);
+
+        let denials = model.denials();
+        assert_eq!(denials.len(), 1);
+        assert_eq!(denials[0].intent, QueryIntent::Reconstruction);
+        assert_eq!(denials[0].purged_cache_entries, 1);
+    }
+
+    #[test]
+    fn test_abort_and_flush_quarantines_catalog() {
+        let mut model = SpectralRealityModel::default();
+        model.upsert(object("shop.example.com"));
+        let lookup = QueryContext::new(QueryIntent::CatalogLookup, GovernanceMode::ActiveGoverned);
+        model.list_by_domain("shop.example.com", &lookup).unwrap();
+
+        let outcome = model.abort_and_flush("test").unwrap();
+        assert_eq!((outcome.cleared, outcome.quarantined), (1, 1));
+        assert_eq!(model.quarantined(), 1);
+        assert_eq!(model.cached_queries(), 0);
+        assert!(model
+            .snapshot(&lookup)
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,666 @@
+//! Durable catalog: append-only NDJSON journal plus compacted snapshots.
+//!
+//! Layout of the store directory:
+//! - `journal.ndjson`: one `{"seq", "op", "object", "sha256"}` line per upsert,
+//!   and one `{"seq", "op": "quarantine", "reason", "sha256"}` line per
+//!   AbortAndFlush, which moves every object journaled before it out of reach.
+//! - `snapshot.json`: every live and every quarantined object as of `seq`,
+//!   under one checksum.
+//!
+//! Checksums are SHA-256 over the canonical (key-sorted) JSON of the payload.
+//! Recovery loads the snapshot, then replays journal entries after its `seq`.
+//! A torn final line (crash mid-append) is truncated. Any other checksum,
+//! parse or sequence failure is an integrity error. Compaction writes the
+//! snapshot to a temp file, renames it into place, and only then truncates
+//! the journal, so a crash at any point recovers to the same catalog.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::governance::abort_flush::{FlushOutcome, Flushable};
+use crate::spectral_reality_model::{
+    QueryContext, QueryError, SpectralObject, SpectralRealityModel,
+};
+use crate::spectral_rights_engine::GuardEngine;
+
+/// Snapshot file format version.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+const JOURNAL_FILE: &str = "journal.ndjson";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+
+/// Errors opening, writing or recovering a catalog store.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum StoreError {
+    #[error("cannot access {path}: {message}")]
+    Io { path: String, message: String },
+    #[error("integrity check failed in {file}{}: {message}", .line.map(|l| format!(" line {}", l)).unwrap_or_default())]
+    Integrity {
+        file: String,
+        line: Option<usize>,
+        message: String,
+    },
+    #[error("snapshot format version {found} is not supported (expected {expected})")]
+    UnsupportedVersion { found: u32, expected: u32 },
+}
+
+impl StoreError {
+    fn io(path: &Path, e: io::Error) -> Self {
+        StoreError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        }
+    }
+
+    fn integrity(file: &str, line: Option<usize>, message: impl Into<String>) -> Self {
+        StoreError::Integrity {
+            file: file.to_string(),
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+/// Store settings.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StoreOptions {
+    /// Compact after this many journal entries; 0 disables auto-compaction.
+    pub snapshot_every: usize,
+    /// fsync journal appends and snapshots before acknowledging them.
+    pub fsync: bool,
+}
+
+impl Default for StoreOptions {
+    fn default() -> Self {
+        Self {
+            snapshot_every: 1000,
+            fsync: true,
+        }
+    }
+}
+
+/// What [`CatalogStore::open`] found on disk.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecoveryReport {
+    /// Sequence number covered by the snapshot; 0 without one.
+    pub snapshot_seq: u64,
+    pub snapshot_objects: usize,
+    /// Journal entries applied on top of the snapshot.
+    pub replayed: usize,
+    /// Journal entries already covered by the snapshot (crash before truncate).
+    pub skipped: usize,
+    /// A torn final journal line was discarded.
+    pub truncated_tail: bool,
+    /// Objects moved to quarantine by replayed AbortAndFlush entries.
+    #[serde(default)]
+    pub quarantined: usize,
+    pub objects: usize,
+}
+
+/// One verified journal operation.
+enum JournalOp {
+    Upsert(Box<SpectralObject>),
+    /// AbortAndFlush: quarantine everything journaled so far.
+    Quarantine,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    format_version: u32,
+    seq: u64,
+    created_at: DateTime<Utc>,
+    objects: Value,
+    /// Objects quarantined by AbortAndFlush. When present, `sha256` covers
+    /// `{"objects", "quarantine"}`; older snapshots checksum `objects` alone.
+    #[serde(default)]
+    quarantine: Option<Value>,
+    sha256: String,
+}
+
+impl Snapshot {
+    fn checksum(&self) -> String {
+        match &self.quarantine {
+            Some(quarantine) => checksum(&json!({
+                "objects": self.objects,
+                "quarantine": quarantine,
+            })),
+            None => checksum(&self.objects),
+        }
+    }
+}
+
+/// Objects sorted by id, as snapshot JSON.
+fn sorted_objects<'a>(
+    objects: impl Iterator<Item = &'a SpectralObject>,
+) -> Result<Value, StoreError> {
+    let mut objects: Vec<&SpectralObject> = objects.collect();
+    objects.sort_by(|a, b| a.id.cmp(&b.id));
+    serde_json::to_value(objects).map_err(|e| StoreError::Io {
+        path: SNAPSHOT_FILE.to_string(),
+        message: e.to_string(),
+    })
+}
+
+fn checksum(value: &Value) -> String {
+    Sha256::digest(value.to_string().as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// `SpectralRealityModel` persisted to a local directory.
+#[derive(Debug)]
+pub struct CatalogStore {
+    dir: PathBuf,
+    options: StoreOptions,
+    model: SpectralRealityModel,
+    journal: File,
+    seq: u64,
+    journal_entries: usize,
+}
+
+impl CatalogStore {
+    /// Open or create a store in `dir` and recover its catalog.
+    pub fn open(
+        dir: impl AsRef<Path>,
+        options: StoreOptions,
+    ) -> Result<(Self, RecoveryReport), StoreError> {
+        Self::open_model(dir.as_ref(), SpectralRealityModel::default(), options)
+    }
+
+    /// As [`CatalogStore::open`], checking queries with `guards`.
+    pub fn open_with_guards(
+        dir: impl AsRef<Path>,
+        guards: GuardEngine,
+        options: StoreOptions,
+    ) -> Result<(Self, RecoveryReport), StoreError> {
+        Self::open_model(
+            dir.as_ref(),
+            SpectralRealityModel::with_guards(guards),
+            options,
+        )
+    }
+
+    fn open_model(
+        dir: &Path,
+        mut model: SpectralRealityModel,
+        options: StoreOptions,
+    ) -> Result<(Self, RecoveryReport), StoreError> {
+        fs::create_dir_all(dir).map_err(|e| StoreError::io(dir, e))?;
+        let mut report = RecoveryReport::default();
+
+        let snapshot_path = dir.join(SNAPSHOT_FILE);
+        if snapshot_path.exists() {
+            let (seq, objects, quarantine) = read_snapshot(&snapshot_path)?;
+            report.snapshot_seq = seq;
+            report.snapshot_objects = objects.len();
+            for obj in objects {
+                model.objects.insert(obj.id.clone(), obj);
+            }
+            for obj in quarantine {
+                model.quarantine.insert(obj.id.clone(), obj);
+            }
+        }
+
+        let journal_path = dir.join(JOURNAL_FILE);
+        let mut seq = report.snapshot_seq;
+        let mut journal_entries = 0;
+        if journal_path.exists() {
+            let (entries, good_len, torn) = read_journal(&journal_path)?;
+            for (line, entry_seq, op) in entries {
+                journal_entries += 1;
+                if entry_seq <= report.snapshot_seq {
+                    report.skipped += 1;
+                    continue;
+                }
+                if entry_seq != seq + 1 {
+                    return Err(StoreError::integrity(
+                        JOURNAL_FILE,
+                        Some(line),
+                        format!("sequence {} follows {}", entry_seq, seq),
+                    ));
+                }
+                seq = entry_seq;
+                match op {
+                    JournalOp::Upsert(obj) => {
+                        model.objects.insert(obj.id.clone(), *obj);
+                    }
+                    JournalOp::Quarantine => {
+                        report.quarantined += model.objects.len();
+                        model.quarantine.extend(model.objects.drain());
+                    }
+                }
+                report.replayed += 1;
+            }
+            if torn {
+                let file = OpenOptions::new()
+                    .write(true)
+                    .open(&journal_path)
+                    .map_err(|e| StoreError::io(&journal_path, e))?;
+                file.set_len(good_len)
+                    .map_err(|e| StoreError::io(&journal_path, e))?;
+                report.truncated_tail = true;
+            }
+        }
+
+        let journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&journal_path)
+            .map_err(|e| StoreError::io(&journal_path, e))?;
+        report.objects = model.objects.len();
+        let store = Self {
+            dir: dir.to_path_buf(),
+            options,
+            model,
+            journal,
+            seq,
+            journal_entries,
+        };
+        Ok((store, report))
+    }
+
+    /// The recovered, in-memory catalog; reads go through its query guards.
+    pub fn model(&self) -> &SpectralRealityModel {
+        &self.model
+    }
+
+    /// Sequence number of the last durable upsert.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Journal an upsert, then apply it. Compacts when the journal reaches
+    /// `snapshot_every` entries.
+    pub fn upsert(&mut self, obj: SpectralObject) -> Result<&SpectralObject, StoreError> {
+        let object = serde_json::to_value(&obj).map_err(|e| StoreError::Io {
+            path: JOURNAL_FILE.to_string(),
+            message: e.to_string(),
+        })?;
+        self.append("upsert", "object", object)?;
+
+        let id = obj.id.clone();
+        self.model.upsert(obj);
+        if self.options.snapshot_every > 0 && self.journal_entries >= self.options.snapshot_every {
+            self.compact()?;
+        }
+        Ok(self
+            .model
+            .objects
+            .get(&id)
+            .expect("object was just upserted"))
+    }
+
+    /// Copies of every object for annotation, authorized as a write with
+    /// `ctx`. Write them back with [`CatalogStore::upsert`] so each change
+    /// is journaled before it is applied.
+    pub fn checkout(&mut self, ctx: &QueryContext) -> Result<Vec<SpectralObject>, QueryError> {
+        Ok(self
+            .model
+            .objects_mut(ctx)?
+            .into_iter()
+            .map(|o| o.clone())
+            .collect())
+    }
+
+    /// Durably append one `op` entry carrying `payload` under `field`.
+    fn append(&mut self, op: &str, field: &str, payload: Value) -> Result<(), StoreError> {
+        let mut entry = json!({
+            "seq": self.seq + 1,
+            "op": op,
+            "sha256": checksum(&payload),
+        });
+        entry[field] = payload;
+        let path = self.dir.join(JOURNAL_FILE);
+        let mut line = entry.to_string();
+        line.push('\n');
+        self.journal
+            .write_all(line.as_bytes())
+            .and_then(|_| self.journal.flush())
+            .map_err(|e| StoreError::io(&path, e))?;
+        if self.options.fsync {
+            self.journal
+                .sync_data()
+                .map_err(|e| StoreError::io(&path, e))?;
+        }
+        self.seq += 1;
+        self.journal_entries += 1;
+        Ok(())
+    }
+
+    /// Write a snapshot of the live and quarantined catalog and truncate
+    /// the journal.
+    pub fn compact(&mut self) -> Result<(), StoreError> {
+        let mut snapshot = Snapshot {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            seq: self.seq,
+            created_at: Utc::now(),
+            objects: sorted_objects(self.model.objects.values())?,
+            quarantine: Some(sorted_objects(self.model.quarantine.values())?),
+            sha256: String::new(),
+        };
+        snapshot.sha256 = snapshot.checksum();
+
+        let tmp = self.dir.join(format!("{}.tmp", SNAPSHOT_FILE));
+        let target = self.dir.join(SNAPSHOT_FILE);
+        let body = serde_json::to_vec(&snapshot).unwrap_or_default();
+        let mut file = File::create(&tmp).map_err(|e| StoreError::io(&tmp, e))?;
+        file.write_all(&body).map_err(|e| StoreError::io(&tmp, e))?;
+        if self.options.fsync {
+            file.sync_all().map_err(|e| StoreError::io(&tmp, e))?;
+        }
+        fs::rename(&tmp, &target).map_err(|e| StoreError::io(&target, e))?;
+        if self.options.fsync {
+            // Persist the rename; not supported on every platform.
+            let _ = File::open(&self.dir).and_then(|d| d.sync_all());
+        }
+
+        let journal_path = self.dir.join(JOURNAL_FILE);
+        self.journal
+            .set_len(0)
+            .map_err(|e| StoreError::io(&journal_path, e))?;
+        self.journal_entries = 0;
+        Ok(())
+    }
+}
+
+impl Flushable for CatalogStore {
+    fn flush_name(&self) -> String {
+        format!("catalog_store:{}", self.dir.display())
+    }
+
+    /// Quarantine the in-memory catalog, then journal the quarantine so a
+    /// reopen does not replay flushed objects back into the catalog.
+    fn abort_and_flush(&mut self, reason: &str) -> Result<FlushOutcome, String> {
+        let mut outcome = self.model.abort_and_flush(reason)?;
+        self.append("quarantine", "reason", json!(reason))
+            .map_err(|e| format!("catalog quarantined in memory only: {}", e))?;
+        outcome.detail = format!(
+            "{}; quarantine journaled at seq {}",
+            outcome.detail, self.seq
+        );
+        Ok(outcome)
+    }
+}
+
+/// The snapshot's seq, live objects and quarantined objects.
+#[allow(clippy::type_complexity)]
+fn read_snapshot(
+    path: &Path,
+) -> Result<(u64, Vec<SpectralObject>, Vec<SpectralObject>), StoreError> {
+    let src = fs::read_to_string(path).map_err(|e| StoreError::io(path, e))?;
+    let raw: Value = serde_json::from_str(&src)
+        .map_err(|e| StoreError::integrity(SNAPSHOT_FILE, None, e.to_string()))?;
+    let found = raw["format_version"].as_u64().unwrap_or(0) as u32;
+    if found != SNAPSHOT_FORMAT_VERSION {
+        return Err(StoreError::UnsupportedVersion {
+            found,
+            expected: SNAPSHOT_FORMAT_VERSION,
+        });
+    }
+    let snapshot: Snapshot = serde_json::from_value(raw)
+        .map_err(|e| StoreError::integrity(SNAPSHOT_FILE, None, e.to_string()))?;
+    if snapshot.checksum() != snapshot.sha256 {
+        return Err(StoreError::integrity(
+            SNAPSHOT_FILE,
+            None,
+            "object checksum mismatch",
+        ));
+    }
+    let parse = |value: Value| {
+        serde_json::from_value(value)
+            .map_err(|e| StoreError::integrity(SNAPSHOT_FILE, None, e.to_string()))
+    };
+    let objects = parse(snapshot.objects)?;
+    let quarantine = match snapshot.quarantine {
+        Some(value) => parse(value)?,
+        None => Vec::new(),
+    };
+    Ok((snapshot.seq, objects, quarantine))
+}
+
+/// Verified entries as (line, seq, op), the byte length of the intact
+/// prefix, and whether a torn final line follows it.
+#[allow(clippy::type_complexity)]
+fn read_journal(path: &Path) -> Result<(Vec<(usize, u64, JournalOp)>, u64, bool), StoreError> {
+    let file = File::open(path).map_err(|e| StoreError::io(path, e))?;
+    let mut reader = BufReader::new(file);
+    let mut entries = Vec::new();
+    let mut good_len = 0u64;
+    let mut buf = String::new();
+    let mut line = 0;
+    loop {
+        buf.clear();
+        let n = reader
+            .read_line(&mut buf)
+            .map_err(|e| StoreError::io(path, e))?;
+        if n == 0 {
+            return Ok((entries, good_len, false));
+        }
+        line += 1;
+        if !buf.ends_with('\n') {
+            // Crash mid-append: the final line never completed.
+            return Ok((entries, good_len, true));
+        }
+        good_len += n as u64;
+        if buf.trim().is_empty() {
+            continue;
+        }
+
+        let fail = |message: String| StoreError::integrity(JOURNAL_FILE, Some(line), message);
+        let raw: Value = serde_json::from_str(&buf).map_err(|e| fail(e.to_string()))?;
+        let seq = raw["seq"]
+            .as_u64()
+            .ok_or_else(|| fail("missing seq".to_string()))?;
+        let field = match raw["op"].as_str() {
+            Some("upsert") => "object",
+            Some("quarantine") => "reason",
+            _ => return Err(fail(format!("unknown op {}", raw["op"]))),
+        };
+        if raw["sha256"].as_str() != Some(checksum(&raw[field]).as_str()) {
+            return Err(fail(format!("{} checksum mismatch", field)));
+        }
+        let op = if field == "object" {
+            let obj =
+                serde_json::from_value(raw["object"].clone()).map_err(|e| fail(e.to_string()))?;
+            JournalOp::Upsert(Box::new(obj))
+        } else {
+            JournalOp::Quarantine
+        };
+        entries.push((line, seq, op));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::governance::GovernanceMode;
+    use crate::spectral_reality_model::{Origin, QueryContext, QueryIntent, SpectralKind};
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("catalog-store-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn object(domain: &str) -> SpectralObject {
+        SpectralObject::new(
+            SpectralKind::TracePattern,
+            Origin {
+                domain: domain.to_string(),
+                system: "checkout".to_string(),
+                run_id: "r1".to_string(),
+                modality: "trace".to_string(),
+            },
+            HashMap::from([("summary".to_string(), json!({"route": "/cart", "p95": 0.1}))]),
+        )
+    }
+
+    fn ids(store: &CatalogStore) -> Vec<String> {
+        let ctx = QueryContext::new(QueryIntent::IntegrityCheck, GovernanceMode::ActiveGoverned);
+        let mut ids: Vec<String> = store
+            .model()
+            .list_by_kind(&SpectralKind::TracePattern, &ctx)
+            .unwrap()
+            .iter()
+            .map(|o| o.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_reopen_replays_journal_and_snapshot() {
+        let dir = temp_dir();
+        let options = StoreOptions {
+            snapshot_every: 2,
+            fsync: false,
+        };
+        let (mut store, report) = CatalogStore::open(&dir, options.clone()).unwrap();
+        assert_eq!(report.objects, 0);
+        let mut updated = object("a.example.com");
+        store.upsert(updated.clone()).unwrap();
+        store.upsert(object("b.example.com")).unwrap();
+        updated.touch(Some(0.75), None, None, None, None);
+        store.upsert(updated.clone()).unwrap();
+        let before = ids(&store);
+        drop(store);
+
+        let (store, report) = CatalogStore::open(&dir, options).unwrap();
+        assert_eq!(
+            (
+                report.snapshot_seq,
+                report.snapshot_objects,
+                report.replayed
+            ),
+            (2, 2, 1)
+        );
+        assert_eq!(ids(&store), before);
+        assert_eq!(store.seq(), 3);
+        let ctx = QueryContext::new(QueryIntent::CatalogLookup, GovernanceMode::ActiveGoverned);
+        let restored = store.model().get_by_id(&updated.id, &ctx).unwrap().unwrap();
+        assert_eq!(restored.stability, 0.75);
+        assert_eq!(restored.signature["summary"]["route"], "/cart");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_torn_tail_is_truncated_and_stale_entries_skipped() {
+        let dir = temp_dir();
+        let options = StoreOptions {
+            snapshot_every: 0,
+            fsync: false,
+        };
+        let (mut store, _) = CatalogStore::open(&dir, options.clone()).unwrap();
+        store.upsert(object("a.example.com")).unwrap();
+        let journal = fs::read_to_string(dir.join(JOURNAL_FILE)).unwrap();
+        store.compact().unwrap();
+        store.upsert(object("b.example.com")).unwrap();
+        drop(store);
+
+        // Crash after the snapshot rename but before truncation, and mid-append.
+        let tail = fs::read_to_string(dir.join(JOURNAL_FILE)).unwrap();
+        fs::write(
+            dir.join(JOURNAL_FILE),
+            format!("{}{}{{\"seq\":3,\"op\":\"ups", journal, tail),
+        )
+        .unwrap();
+
+        let (store, report) = CatalogStore::open(&dir, options).unwrap();
+        assert_eq!((report.skipped, report.replayed), (1, 1));
+        assert!(report.truncated_tail);
+        assert_eq!(report.objects, 2);
+        assert_eq!(store.seq(), 2);
+        let journal = fs::read_to_string(dir.join(JOURNAL_FILE)).unwrap();
+        assert!(journal.ends_with('\n'));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_flushed_objects_stay_quarantined_on_reopen() {
+        let dir = temp_dir();
+        let options = StoreOptions {
+            snapshot_every: 0,
+            fsync: false,
+        };
+        let (mut store, _) = CatalogStore::open(&dir, options.clone()).unwrap();
+        store.upsert(object("a.example.com")).unwrap();
+        store.compact().unwrap();
+        store.upsert(object("b.example.com")).unwrap();
+        let outcome = store.abort_and_flush("operator abort").unwrap();
+        assert_eq!(outcome.quarantined, 2);
+        assert!(ids(&store).is_empty());
+        let kept = store.upsert(object("c.example.com")).unwrap().id.clone();
+        drop(store);
+
+        let (mut store, report) = CatalogStore::open(&dir, options.clone()).unwrap();
+        assert_eq!((report.replayed, report.quarantined), (3, 2));
+        assert_eq!(ids(&store), [kept.clone()]);
+        assert_eq!(store.model().quarantined(), 2);
+
+        // Compaction keeps the quarantine out of reach but on record.
+        store.compact().unwrap();
+        drop(store);
+        let (store, report) = CatalogStore::open(&dir, options.clone()).unwrap();
+        assert_eq!((report.snapshot_objects, report.objects), (1, 1));
+        assert_eq!(ids(&store), [kept]);
+        assert_eq!(store.model().quarantined(), 2);
+        drop(store);
+
+        // The quarantine is covered by the snapshot checksum.
+        let snapshot_path = dir.join(SNAPSHOT_FILE);
+        let mut snapshot: Value =
+            serde_json::from_str(&fs::read_to_string(&snapshot_path).unwrap()).unwrap();
+        snapshot["quarantine"] = json!([]);
+        fs::write(&snapshot_path, snapshot.to_string()).unwrap();
+        assert!(matches!(
+            CatalogStore::open(&dir, options),
+            Err(StoreError::Integrity { line: None, .. })
+        ));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_tampering_fails_integrity_check() {
+        let dir = temp_dir();
+        let options = StoreOptions {
+            snapshot_every: 0,
+            fsync: false,
+        };
+        let (mut store, _) = CatalogStore::open(&dir, options.clone()).unwrap();
+        store.upsert(object("a.example.com")).unwrap();
+        store.upsert(object("b.example.com")).unwrap();
+        drop(store);
+
+        let journal_path = dir.join(JOURNAL_FILE);
+        let journal = fs::read_to_string(&journal_path).unwrap();
+        fs::write(
+            &journal_path,
+            journal.replacen("a.example.com", "evil.example.com", 1),
+        )
+        .unwrap();
+        let err = CatalogStore::open(&dir, options.clone()).unwrap_err();
+        assert!(matches!(err, StoreError::Integrity { line: Some(1), .. }));
+        assert!(err.to_string().contains("journal.ndjson line 1"));
+
+        fs::write(&journal_path, journal).unwrap();
+        let (mut store, _) = CatalogStore::open(&dir, options.clone()).unwrap();
+        store.compact().unwrap();
+        drop(store);
+        let snapshot_path = dir.join(SNAPSHOT_FILE);
+        let snapshot = fs::read_to_string(&snapshot_path).unwrap();
+        fs::write(&snapshot_path, snapshot.replacen("checkout", "checkin", 1)).unwrap();
+        assert!(matches!(
+            CatalogStore::open(&dir, options),
+            Err(StoreError::Integrity { line: None, .. })
+        ));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,876 @@
+//! GhostNet Spectral Rights Engine (Rust)
+//! ======================================
+//! Rust mirror of `spectral_rights_engine.aln`. Implements the
+//! `@state_machine rights_enforcement` block as a typed state machine:
+//! Monitoring → Investigating → Enforcing → Recovery → Monitoring.
+//!
+//! Every transition attempt, accepted or rejected, is appended to an
+//! audit trail together with the outcome of each entry action.
+//!
+//! `GuardEngine` evaluates the file's `@guard` rules against typed
+//! ingest, zone-entry and query events and returns a `GuardVerdict`.
+//!
+//! Hex-Stamp: 0x535045435452414c5f5249474854535f454e47494e455f5631
+//! ALN Identity: aln18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7
+//! Bostrom Identity: bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7
+//! Version: 1.0.0
+//! License: ALN-Sovereign-v1
+
+#![deny(clippy::all)]
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+
+use std::collections::HashMap;
+use std::fmt;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::aln::{
+    parse_aln, AlnAction, AlnDocument, AlnError, AlnValue, Condition, GuardDefinition,
+    GuardEnforcement, GuardEvent, GuardResponse,
+};
+use crate::governance::{EnforcementMode, PolicyViolation};
+
+/// Identity constants for verification
+pub mod identity {
+    /// ALN identity of the engine
+    pub const ALN_STAMP: &str = "aln18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+    /// Bostrom identity of the engine
+    pub const BOSTROM_STAMP: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+    /// Hex-stamp of the engine
+    pub const HEX_STAMP: &str = "0x535045435452414c5f5249474854535f454e47494e455f5631";
+    /// Engine version
+    pub const VERSION: &str = "1.0.0";
+}
+
+/// States of `@state_machine rights_enforcement`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum RightsState {
+    /// Normal operation, watching for violations
+    Monitoring = 0,
+    /// Violation reported, components frozen pending confirmation
+    Investigating = 1,
+    /// Violation confirmed, enforcement actions running
+    Enforcing = 2,
+    /// Enforcement complete, restoring safe defaults
+    Recovery = 3,
+}
+
+impl RightsState {
+    /// ALN state name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Monitoring => "Monitoring",
+            Self::Investigating => "Investigating",
+            Self::Enforcing => "Enforcing",
+            Self::Recovery => "Recovery",
+        }
+    }
+
+    /// Entry actions declared for this state, in ALN order
+    pub fn entry_actions(&self) -> &'static [RightsAction] {
+        match self {
+            Self::Monitoring => &[],
+            Self::Investigating => &[
+                RightsAction::FreezeAffectedComponents,
+                RightsAction::SnapshotStateForAudit,
+            ],
+            Self::Enforcing => &[
+                RightsAction::TriggerAbortAndFlush,
+                RightsAction::RevokeAccessCredentials,
+                RightsAction::LogToImmutableLedger,
+            ],
+            Self::Recovery => &[
+                RightsAction::RestoreSafeDefaults,
+                RightsAction::NotifyAdministrators,
+            ],
+        }
+    }
+}
+
+impl fmt::Display for RightsState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Transition inputs (`on_input`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RightsInput {
+    /// `rights_violation_detected`
+    RightsViolationDetected,
+    /// `normal_operation`
+    NormalOperation,
+    /// `violation_confirmed`
+    ViolationConfirmed,
+    /// `false_positive`
+    FalsePositive,
+    /// `enforcement_complete`
+    EnforcementComplete,
+    /// `system_secure`
+    SystemSecure,
+}
+
+impl RightsInput {
+    /// ALN input name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RightsViolationDetected => "rights_violation_detected",
+            Self::NormalOperation => "normal_operation",
+            Self::ViolationConfirmed => "violation_confirmed",
+            Self::FalsePositive => "false_positive",
+            Self::EnforcementComplete => "enforcement_complete",
+            Self::SystemSecure => "system_secure",
+        }
+    }
+}
+
+/// State entry actions (`action:`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RightsAction {
+    /// `freeze_affected_components`
+    FreezeAffectedComponents,
+    /// `snapshot_state_for_audit`
+    SnapshotStateForAudit,
+    /// `trigger_AbortAndFlush`
+    TriggerAbortAndFlush,
+    /// `revoke_access_credentials`
+    RevokeAccessCredentials,
+    /// `log_to_immutable_ledger`
+    LogToImmutableLedger,
+    /// `restore_safe_defaults`
+    RestoreSafeDefaults,
+    /// `notify_administrators`
+    NotifyAdministrators,
+}
+
+impl RightsAction {
+    /// ALN action name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FreezeAffectedComponents => "freeze_affected_components",
+            Self::SnapshotStateForAudit => "snapshot_state_for_audit",
+            Self::TriggerAbortAndFlush => "trigger_AbortAndFlush",
+            Self::RevokeAccessCredentials => "revoke_access_credentials",
+            Self::LogToImmutableLedger => "log_to_immutable_ledger",
+            Self::RestoreSafeDefaults => "restore_safe_defaults",
+            Self::NotifyAdministrators => "notify_administrators",
+        }
+    }
+}
+
+/// Rights engine errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RightsError {
+    /// Input not accepted in the current state
+    #[error("Illegal transition: {input:?} in state {from}")]
+    IllegalTransition {
+        /// State the machine was in
+        from: RightsState,
+        /// Rejected input
+        input: RightsInput,
+    },
+}
+
+/// Context passed to action handlers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionContext {
+    /// Sequence number of the transition in the audit trail
+    pub seq: u64,
+    /// State being left
+    pub from: RightsState,
+    /// State being entered
+    pub to: RightsState,
+    /// Input that caused the transition
+    pub input: RightsInput,
+    /// Free-form reason supplied by the caller
+    pub reason: String,
+}
+
+/// Pluggable handler for one or more rights actions
+pub trait RightsActionHandler: Send {
+    /// Execute `action`; an `Err` is recorded in the audit trail but does not
+    /// roll back the transition (enforcement must not be skippable).
+    fn handle(&mut self, action: RightsAction, ctx: &TransitionContext) -> Result<(), String>;
+}
+
+impl<F> RightsActionHandler for F
+where
+    F: FnMut(RightsAction, &TransitionContext) -> Result<(), String> + Send,
+{
+    fn handle(&mut self, action: RightsAction, ctx: &TransitionContext) -> Result<(), String> {
+        self(action, ctx)
+    }
+}
+
+/// Outcome of one entry action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum ActionOutcome {
+    /// Handler ran successfully
+    Completed,
+    /// Handler reported a failure
+    Failed(String),
+    /// No handler registered for this action
+    Unhandled,
+}
+
+/// Record of one executed action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionRecord {
+    /// Action name
+    pub action: RightsAction,
+    /// Outcome
+    pub outcome: ActionOutcome,
+}
+
+/// Audit-trail entry for one transition attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionRecord {
+    /// Monotonic sequence number
+    pub seq: u64,
+    /// Time of the attempt
+    pub timestamp: DateTime<Utc>,
+    /// State before the attempt
+    pub from: RightsState,
+    /// Input applied
+    pub input: RightsInput,
+    /// State after the attempt (equals `from` when rejected)
+    pub to: RightsState,
+    /// False if the transition was rejected as illegal
+    pub accepted: bool,
+    /// Caller-supplied reason
+    pub reason: String,
+    /// Entry actions executed on acceptance
+    pub actions: Vec<ActionRecord>,
+}
+
+/// Typed `rights_enforcement` state machine
+pub struct RightsEnforcementMachine {
+    state: RightsState,
+    handlers: HashMap<RightsAction, Box<dyn RightsActionHandler>>,
+    audit_trail: Vec<TransitionRecord>,
+    next_seq: u64,
+}
+
+impl RightsEnforcementMachine {
+    /// Create a machine in the ALN `initial_state` (Monitoring)
+    pub fn new() -> Self {
+        Self {
+            state: RightsState::Monitoring,
+            handlers: HashMap::new(),
+            audit_trail: Vec::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Current state
+    pub fn state(&self) -> RightsState {
+        self.state
+    }
+
+    /// Register (or replace) the handler for an action
+    pub fn register_handler(
+        &mut self,
+        action: RightsAction,
+        handler: Box<dyn RightsActionHandler>,
+    ) -> Option<Box<dyn RightsActionHandler>> {
+        self.handlers.insert(action, handler)
+    }
+
+    /// Transition table from the ALN spec; `None` if the input is illegal
+    pub fn next_state(from: RightsState, input: RightsInput) -> Option<RightsState> {
+        use RightsInput::*;
+        use RightsState::*;
+        match (from, input) {
+            (Monitoring, RightsViolationDetected) => Some(Investigating),
+            (Monitoring, NormalOperation) => Some(Monitoring),
+            (Investigating, ViolationConfirmed) => Some(Enforcing),
+            (Investigating, FalsePositive) => Some(Monitoring),
+            (Enforcing, EnforcementComplete) => Some(Recovery),
+            (Recovery, SystemSecure) => Some(Monitoring),
+            _ => None,
+        }
+    }
+
+    /// Apply an input. Entry actions of the target state run when the state
+    /// changes; `stay` transitions run no actions.
+    pub fn apply(
+        &mut self,
+        input: RightsInput,
+        reason: impl Into<String>,
+    ) -> Result<RightsState, RightsError> {
+        let from = self.state;
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let reason = reason.into();
+
+        let to = match Self::next_state(from, input) {
+            Some(to) => to,
+            None => {
+                self.audit_trail.push(TransitionRecord {
+                    seq,
+                    timestamp: Utc::now(),
+                    from,
+                    input,
+                    to: from,
+                    accepted: false,
+                    reason,
+                    actions: Vec::new(),
+                });
+                return Err(RightsError::IllegalTransition { from, input });
+            }
+        };
+
+        let ctx = TransitionContext {
+            seq,
+            from,
+            to,
+            input,
+            reason,
+        };
+
+        let mut actions = Vec::new();
+        if to != from {
+            for action in to.entry_actions() {
+                let outcome = match self.handlers.get_mut(action) {
+                    Some(handler) => match handler.handle(*action, &ctx) {
+                        Ok(()) => ActionOutcome::Completed,
+                        Err(e) => ActionOutcome::Failed(e),
+                    },
+                    None => ActionOutcome::Unhandled,
+                };
+                actions.push(ActionRecord {
+                    action: *action,
+                    outcome,
+                });
+            }
+        }
+
+        self.state = to;
+        self.audit_trail.push(TransitionRecord {
+            seq,
+            timestamp: Utc::now(),
+            from,
+            input,
+            to,
+            accepted: true,
+            reason: ctx.reason,
+            actions,
+        });
+        Ok(to)
+    }
+
+    /// Full audit trail, oldest first
+    pub fn audit_trail(&self) -> &[TransitionRecord] {
+        &self.audit_trail
+    }
+
+    /// Number of rejected transition attempts in the trail
+    pub fn rejected_count(&self) -> usize {
+        self.audit_trail.iter().filter(|r| !r.accepted).count()
+    }
+
+    /// Export current state and trail for ledger/CLI reporting
+    pub fn export_state(&self) -> RightsEngineExport {
+        RightsEngineExport {
+            state: self.state,
+            transitions: self.audit_trail.len(),
+            rejected_transitions: self.rejected_count(),
+            last_transition: self.audit_trail.last().cloned(),
+            aln_stamp: identity::ALN_STAMP.to_string(),
+            hex_stamp: identity::HEX_STAMP.to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+impl Default for RightsEnforcementMachine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Exported rights-engine state for ledger logging
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RightsEngineExport {
+    /// Current state
+    pub state: RightsState,
+    /// Number of transition attempts recorded
+    pub transitions: usize,
+    /// Number of rejected attempts
+    pub rejected_transitions: usize,
+    /// Most recent trail entry
+    pub last_transition: Option<TransitionRecord>,
+    /// ALN stamp
+    pub aln_stamp: String,
+    /// Hex stamp
+    pub hex_stamp: String,
+    /// Export time
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Typed event evaluated by `@guard` handlers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GuardEventInput {
+    /// `data_ingest`: metadata keys of the incoming payload
+    DataIngest {
+        /// `payload.metadata_keys`
+        metadata_keys: Vec<String>,
+    },
+    /// `zone_entry`: an entity entering a mapped zone
+    ZoneEntry {
+        /// Entity or space identifier
+        entity_id: String,
+        /// `entity.companion_space_status` ("active", "inactive", ...)
+        companion_space_status: String,
+    },
+    /// `data_query`: a catalog or history read
+    DataQuery {
+        /// `query.intent` ("integrity_check", "reconstruction", ...)
+        intent: String,
+    },
+}
+
+impl GuardEventInput {
+    /// ALN event kind handled by this input
+    pub fn kind(&self) -> GuardEvent {
+        match self {
+            Self::DataIngest { .. } => GuardEvent::DataIngest,
+            Self::ZoneEntry { .. } => GuardEvent::ZoneEntry,
+            Self::DataQuery { .. } => GuardEvent::DataQuery,
+        }
+    }
+
+    /// Resolve a condition variable (`status`, `intent`, or the full
+    /// inspect path) against this event.
+    fn variable(&self, name: &str) -> Option<&str> {
+        match self {
+            Self::ZoneEntry {
+                companion_space_status,
+                entity_id,
+            } => match name {
+                "status" | "companion_space_status" | "entity.companion_space_status" => {
+                    Some(companion_space_status)
+                }
+                "entity_id" | "entity.id" => Some(entity_id),
+                _ => None,
+            },
+            Self::DataQuery { intent } => match name {
+                "intent" | "query.intent" => Some(intent),
+                _ => None,
+            },
+            Self::DataIngest { .. } => None,
+        }
+    }
+
+    /// Metadata keys matching the handler's forbid list. Keys are compared
+    /// case-insensitively on their last dotted segment.
+    fn forbidden_keys(&self, forbid: &[String]) -> Vec<String> {
+        match self {
+            Self::DataIngest { metadata_keys } => metadata_keys
+                .iter()
+                .filter(|key| {
+                    let leaf = key.rsplit('.').next().unwrap_or(key);
+                    forbid.iter().any(|f| f.eq_ignore_ascii_case(leaf))
+                })
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Result of evaluating all guards against one event
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuardVerdict {
+    /// Enforcement mode the verdict was produced under
+    pub mode: EnforcementMode,
+    /// Guards whose rules fired
+    pub triggered_by: Vec<String>,
+    /// Triggered ALN actions, in guard order, deduplicated
+    pub actions: Vec<AlnAction>,
+    /// A rule set `deny: true`
+    pub deny: bool,
+    /// A rule set `enforce: "observe_only"`
+    pub observe_only: bool,
+    /// Features to disable (`disable:`)
+    pub disable: Vec<String>,
+    /// Features to enable (`enable:`)
+    pub enable: Vec<String>,
+    /// Denial reasons
+    pub reasons: Vec<String>,
+    /// Ingest keys that matched a forbid list
+    pub forbidden_keys: Vec<String>,
+    /// Ledger paths to write the violation to
+    pub audit_paths: Vec<String>,
+    /// Components to notify
+    pub notify: Vec<String>,
+}
+
+impl GuardVerdict {
+    /// True if the event must not be accepted: a blocking rule fired and
+    /// the verdict was produced in strict mode
+    pub fn is_blocking(&self) -> bool {
+        self.mode == EnforcementMode::Strict && self.would_block()
+    }
+
+    /// True if a rule denied the event or triggered AbortAndFlush or
+    /// TerminateSafe, regardless of enforcement mode
+    pub fn would_block(&self) -> bool {
+        self.deny
+            || self.triggers(AlnAction::AbortAndFlush)
+            || self.triggers(AlnAction::TerminateSafe)
+    }
+
+    /// True if observe-only must be applied (strict mode only)
+    pub fn enforces_observe_only(&self) -> bool {
+        self.mode == EnforcementMode::Strict && self.observe_only
+    }
+
+    /// True if `action` was triggered
+    pub fn triggers(&self, action: AlnAction) -> bool {
+        self.actions.contains(&action)
+    }
+
+    /// True if no guard rule fired
+    pub fn is_clear(&self) -> bool {
+        self.triggered_by.is_empty()
+    }
+
+    fn absorb(&mut self, guard: &str, response: &GuardResponse) {
+        if !self.triggered_by.iter().any(|g| g == guard) {
+            self.triggered_by.push(guard.to_string());
+        }
+        for action in response.actions() {
+            if !self.triggers(action) {
+                self.actions.push(action);
+            }
+        }
+        self.deny |= response.deny;
+        self.observe_only |= response.enforce == Some(GuardEnforcement::ObserveOnly);
+        extend_unique(&mut self.disable, &response.disable);
+        extend_unique(&mut self.enable, &response.enable);
+        extend_unique(&mut self.reasons, response.reason.as_slice());
+        extend_unique(&mut self.audit_paths, response.audit_path.as_slice());
+        extend_unique(&mut self.notify, &response.notify);
+    }
+}
+
+fn extend_unique(dst: &mut Vec<String>, src: &[String]) {
+    for s in src {
+        if !dst.contains(s) {
+            dst.push(s.clone());
+        }
+    }
+}
+
+/// Evaluates ALN `@guard` rules against typed events
+#[derive(Debug, Clone)]
+pub struct GuardEngine {
+    guards: Vec<GuardDefinition>,
+    mode: EnforcementMode,
+}
+
+impl Default for GuardEngine {
+    /// The bundled policy; an empty engine would allow everything
+    fn default() -> Self {
+        Self::bundled().expect("bundled spectral_rights_engine.aln must parse")
+    }
+}
+
+impl GuardEngine {
+    /// Strict engine over an explicit guard list
+    pub fn new(guards: Vec<GuardDefinition>) -> Self {
+        Self {
+            guards,
+            mode: EnforcementMode::Strict,
+        }
+    }
+
+    /// Engine over the guards and `enforcement_mode` of a loaded ALN document
+    pub fn from_document(doc: &AlnDocument) -> Self {
+        Self::new(doc.guards.clone()).with_mode(doc.runtime.enforcement_mode)
+    }
+
+    /// Override the enforcement mode. Advisory verdicts record fired rules
+    /// but never block; disabled engines skip evaluation entirely.
+    pub fn with_mode(mut self, mode: EnforcementMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Enforcement mode applied to verdicts
+    pub fn mode(&self) -> EnforcementMode {
+        self.mode
+    }
+
+    /// Engine over the guards shipped in `spectral_rights_engine.aln`
+    pub fn bundled() -> Result<Self, AlnError> {
+        Ok(Self::from_document(&bundled_policy()?))
+    }
+
+    /// Loaded guards
+    pub fn guards(&self) -> &[GuardDefinition] {
+        &self.guards
+    }
+
+    /// Evaluate every handler subscribed to the event's kind
+    pub fn evaluate(&self, event: &GuardEventInput) -> GuardVerdict {
+        self.evaluate_in(self.mode, event)
+    }
+
+    /// Run the `data_ingest` guards as a non-waivable gate. Soul-modeling is
+    /// the hard boundary between telemetry and soul-data, so the rules are
+    /// evaluated in strict mode whatever the engine's mode, and a blocking
+    /// verdict is recorded in `violations` and returned as `Err`
+    pub fn enforce_ingest(
+        &self,
+        metadata_keys: &[String],
+        violations: &mut Vec<PolicyViolation>,
+    ) -> Result<GuardVerdict, Box<GuardVerdict>> {
+        let verdict = self.evaluate_in(
+            EnforcementMode::Strict,
+            &GuardEventInput::DataIngest {
+                metadata_keys: metadata_keys.to_vec(),
+            },
+        );
+        if !verdict.would_block() {
+            return Ok(verdict);
+        }
+        let detail = format!(
+            "{} rejects metadata keys {:?}",
+            verdict.triggered_by.join(", "),
+            verdict.forbidden_keys
+        );
+        PolicyViolation::enforce(
+            "guard.data_ingest",
+            detail,
+            Err(Box::new(verdict)),
+            violations,
+        )
+    }
+
+    fn evaluate_in(&self, mode: EnforcementMode, event: &GuardEventInput) -> GuardVerdict {
+        let mut verdict = GuardVerdict {
+            mode,
+            ..GuardVerdict::default()
+        };
+        if mode == EnforcementMode::Disabled {
+            return verdict;
+        }
+        for guard in &self.guards {
+            for handler in guard.handlers.iter().filter(|h| h.event == event.kind()) {
+                let forbidden = event.forbidden_keys(&handler.forbid);
+                extend_unique(&mut verdict.forbidden_keys, &forbidden);
+                for rule in &handler.rules {
+                    if condition_holds(&rule.condition, event, !forbidden.is_empty()) {
+                        verdict.absorb(&guard.name, &rule.response);
+                    }
+                }
+            }
+        }
+        verdict
+    }
+}
+
+/// Policy file this module mirrors
+const BUNDLED_POLICY: &str = include_str!("spectral_rights_engine.aln");
+
+/// Parsed `spectral_rights_engine.aln`, for callers that need its runtime
+/// settings as well as its guards
+pub fn bundled_policy() -> Result<AlnDocument, AlnError> {
+    parse_aln(BUNDLED_POLICY)
+}
+
+fn condition_holds(condition: &Condition, event: &GuardEventInput, violation: bool) -> bool {
+    match condition {
+        Condition::Flag(name) if name == "violation_detected" => violation,
+        Condition::Flag(name) => event.variable(name) == Some("true"),
+        Condition::Eq(name, value) => matches_value(event.variable(name), value),
+        Condition::NotEq(name, value) => !matches_value(event.variable(name), value),
+        Condition::And(a, b) => {
+            condition_holds(a, event, violation) && condition_holds(b, event, violation)
+        }
+        Condition::Or(a, b) => {
+            condition_holds(a, event, violation) || condition_holds(b, event, violation)
+        }
+    }
+}
+
+fn matches_value(actual: Option<&str>, expected: &AlnValue) -> bool {
+    match (actual, expected) {
+        (Some(a), AlnValue::Str(e)) | (Some(a), AlnValue::Path(e)) => a.eq_ignore_ascii_case(e),
+        (Some(a), AlnValue::Bool(e)) => a == if *e { "true" } else { "false" },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_full_enforcement_cycle() {
+        let mut machine = RightsEnforcementMachine::new();
+        assert_eq!(machine.state(), RightsState::Monitoring);
+
+        machine.apply(RightsInput::NormalOperation, "tick").unwrap();
+        machine.apply(RightsInput::RightsViolationDetected, "soul_id key").unwrap();
+        machine.apply(RightsInput::ViolationConfirmed, "confirmed").unwrap();
+        machine.apply(RightsInput::EnforcementComplete, "done").unwrap();
+        assert_eq!(machine.state(), RightsState::Recovery);
+        machine.apply(RightsInput::SystemSecure, "secure").unwrap();
+        assert_eq!(machine.state(), RightsState::Monitoring);
+
+        let trail = machine.audit_trail();
+        assert_eq!(trail.len(), 5);
+        assert!(trail[0].actions.is_empty());
+        assert_eq!(trail[1].actions.len(), 2);
+        assert_eq!(trail[2].actions[0].action, RightsAction::TriggerAbortAndFlush);
+        assert_eq!(trail[2].actions[0].outcome, ActionOutcome::Unhandled);
+    }
+
+    #[test]
+    fn test_illegal_transition_rejected_and_recorded() {
+        let mut machine = RightsEnforcementMachine::new();
+        let err = machine.apply(RightsInput::EnforcementComplete, "skip").unwrap_err();
+        assert_eq!(
+            err,
+            RightsError::IllegalTransition {
+                from: RightsState::Monitoring,
+                input: RightsInput::EnforcementComplete,
+            }
+        );
+        assert_eq!(machine.state(), RightsState::Monitoring);
+        assert_eq!(machine.rejected_count(), 1);
+        assert!(!machine.audit_trail()[0].accepted);
+    }
+
+    #[test]
+    fn test_handlers_invoked_and_failures_recorded() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&calls);
+
+        let mut machine = RightsEnforcementMachine::new();
+        machine.register_handler(
+            RightsAction::FreezeAffectedComponents,
+            Box::new(move |action: RightsAction, ctx: &TransitionContext| {
+                sink.lock().unwrap().push((action, ctx.reason.clone()));
+                Ok(())
+            }),
+        );
+        machine.register_handler(
+            RightsAction::SnapshotStateForAudit,
+            Box::new(|_: RightsAction, _: &TransitionContext| Err("disk full".to_string())),
+        );
+
+        machine.apply(RightsInput::RightsViolationDetected, "essence key").unwrap();
+        assert_eq!(
+            calls.lock().unwrap().as_slice(),
+            &[(RightsAction::FreezeAffectedComponents, "essence key".to_string())]
+        );
+
+        let record = &machine.audit_trail()[0];
+        assert_eq!(record.actions[0].outcome, ActionOutcome::Completed);
+        assert_eq!(record.actions[1].outcome, ActionOutcome::Failed("disk full".to_string()));
+        assert_eq!(machine.state(), RightsState::Investigating);
+    }
+
+    #[test]
+    fn test_bundled_guards_block_soul_metadata() {
+        let engine = GuardEngine::bundled().unwrap();
+        let verdict = engine.evaluate(&GuardEventInput::DataIngest {
+            metadata_keys: vec!["run_id".to_string(), "attributes.Soul_ID".to_string()],
+        });
+        assert!(verdict.is_blocking());
+        assert!(verdict.triggers(AlnAction::AbortAndFlush));
+        assert_eq!(verdict.forbidden_keys, vec!["attributes.Soul_ID".to_string()]);
+        assert_eq!(verdict.triggered_by, vec!["soul_modeling_forbidden".to_string()]);
+
+        let clean = engine.evaluate(&GuardEventInput::DataIngest {
+            metadata_keys: vec!["run_id".to_string()],
+        });
+        assert!(clean.is_clear());
+        assert!(!clean.is_blocking());
+    }
+
+    #[test]
+    fn test_default_engine_is_bundled_policy() {
+        let engine = GuardEngine::default();
+        assert_eq!(engine.guards(), GuardEngine::bundled().unwrap().guards());
+        let verdict = engine.evaluate(&GuardEventInput::DataIngest {
+            metadata_keys: vec!["attributes.Soul_ID".to_string()],
+        });
+        assert!(verdict.is_blocking());
+    }
+
+    #[test]
+    fn test_ingest_guard_blocks_in_every_mode() {
+        let keys = vec!["run_id".to_string(), "essence".to_string()];
+        for mode in [
+            EnforcementMode::Strict,
+            EnforcementMode::Advisory,
+            EnforcementMode::Disabled,
+        ] {
+            let engine = GuardEngine::bundled().unwrap().with_mode(mode);
+            let mut violations = Vec::new();
+            let verdict = engine.enforce_ingest(&keys, &mut violations).unwrap_err();
+            assert_eq!(verdict.forbidden_keys, vec!["essence".to_string()]);
+            assert_eq!(violations.len(), 1);
+            assert_eq!(violations[0].gate, "guard.data_ingest");
+            assert!(violations[0].enforced);
+
+            let mut violations = Vec::new();
+            assert!(engine
+                .enforce_ingest(&["run_id".to_string()], &mut violations)
+                .unwrap()
+                .is_clear());
+            assert!(violations.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_bundled_guards_zone_entry_and_query() {
+        let engine = GuardEngine::bundled().unwrap();
+        let entry = engine.evaluate(&GuardEventInput::ZoneEntry {
+            entity_id: "space-7".to_string(),
+            companion_space_status: "active".to_string(),
+        });
+        assert!(entry.observe_only);
+        assert!(!entry.is_blocking());
+        assert!(entry.disable.contains(&"fear_optimization".to_string()));
+
+        let query = engine.evaluate(&GuardEventInput::DataQuery {
+            intent: "simulation".to_string(),
+        });
+        assert!(query.deny);
+        assert!(query.triggers(AlnAction::PurgeQueryCache));
+
+        let audit = engine.evaluate(&GuardEventInput::DataQuery {
+            intent: "integrity_check".to_string(),
+        });
+        assert!(audit.is_clear());
+    }
+
+    #[test]
+    fn test_advisory_guards_record_without_blocking() {
+        let engine = GuardEngine::bundled().unwrap().with_mode(EnforcementMode::Advisory);
+        let ingest = GuardEventInput::DataIngest {
+            metadata_keys: vec!["karma_score".to_string()],
+        };
+        let verdict = engine.evaluate(&ingest);
+        assert!(verdict.would_block());
+        assert!(!verdict.is_blocking());
+        assert_eq!(verdict.triggered_by, vec!["soul_modeling_forbidden".to_string()]);
+
+        let disabled = engine.with_mode(EnforcementMode::Disabled).evaluate(&ingest);
+        assert!(disabled.is_clear());
+        assert!(!disabled.would_block());
+    }
+}
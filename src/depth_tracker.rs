@@ -0,0 +1,316 @@
+//! Stateful excavation depth per spectral-object, with hysteresis and dwell.
+//!
+//! Downgrades follow the stateless decision immediately; escalation needs
+//! safety_min above the raised "up" thresholds for a continuous dwell time.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::governance::abort_flush::{FlushOutcome, Flushable};
+use crate::spectral_vision::{ExcavationDepth, SpectralVisionDecision, SpectralVisionParams};
+
+/// Up/down thresholds and dwell for [`DepthTracker`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HysteresisParams {
+    /// Added to safety_slow / safety_shigh to get the escalation thresholds.
+    pub up_margin: f64,
+    /// How long an escalation must stay eligible before it is applied.
+    pub min_dwell_ms: i64,
+}
+
+impl Default for HysteresisParams {
+    fn default() -> Self {
+        Self {
+            up_margin: 0.05,
+            min_dwell_ms: 30_000,
+        }
+    }
+}
+
+/// Direction of a depth change.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionKind {
+    Escalate,
+    Downgrade,
+}
+
+/// One applied depth change, for audit export.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DepthTransition {
+    pub object_id: String,
+    pub at: DateTime<Utc>,
+    pub from: ExcavationDepth,
+    pub to: ExcavationDepth,
+    pub kind: TransitionKind,
+    /// safety_min as read by the gates at this evaluation.
+    pub safety_min: f64,
+}
+
+#[derive(Clone, Debug)]
+struct TrackedDepth {
+    depth: ExcavationDepth,
+    /// Each level above `depth` that is eligible for escalation, ascending,
+    /// with when it became continuously eligible.
+    eligible_since: Vec<(ExcavationDepth, DateTime<Utc>)>,
+}
+
+/// Per-object excavation depth with hysteresis.
+#[derive(Debug, Default)]
+pub struct DepthTracker {
+    params: HysteresisParams,
+    objects: HashMap<String, TrackedDepth>,
+    transitions: Vec<DepthTransition>,
+}
+
+impl DepthTracker {
+    /// Empty tracker.
+    pub fn new(params: HysteresisParams) -> Self {
+        Self {
+            params,
+            ..Self::default()
+        }
+    }
+
+    /// Current depth for an object; Sniff if never observed.
+    pub fn depth(&self, object_id: &str) -> ExcavationDepth {
+        self.objects
+            .get(object_id)
+            .map_or(ExcavationDepth::Sniff, |t| t.depth)
+    }
+
+    /// Feed one stateless decision and return the tracked depth.
+    ///
+    /// New objects start at Sniff. A decision below the tracked depth is
+    /// applied at once; one above it is capped by the up thresholds and only
+    /// applied once that target has held for `min_dwell_ms`. Each level keeps
+    /// its own clock, so a DigLight dwell never counts towards DigFull.
+    pub fn observe(
+        &mut self,
+        object_id: &str,
+        decision: &SpectralVisionDecision,
+        params: &SpectralVisionParams,
+        now: DateTime<Utc>,
+    ) -> ExcavationDepth {
+        let safety_min = decision
+            .band_profile
+            .safety_min_bound(params.decision_bound);
+        let up_level = if safety_min >= params.safety_shigh + self.params.up_margin {
+            ExcavationDepth::DigFull
+        } else if safety_min >= params.safety_slow + self.params.up_margin {
+            ExcavationDepth::DigLight
+        } else {
+            ExcavationDepth::Sniff
+        };
+        let proposed = decision.excavation_depth;
+
+        let tracked = self
+            .objects
+            .entry(object_id.to_string())
+            .or_insert(TrackedDepth {
+                depth: ExcavationDepth::Sniff,
+                eligible_since: Vec::new(),
+            });
+        let from = tracked.depth;
+
+        let kind = if proposed < tracked.depth {
+            tracked.depth = proposed;
+            tracked.eligible_since.clear();
+            Some(TransitionKind::Downgrade)
+        } else {
+            let target = proposed.min(up_level);
+            // Levels that dropped out of reach restart their clocks; newly
+            // reachable levels start theirs now.
+            tracked.eligible_since.retain(|&(level, _)| level <= target);
+            for level in [ExcavationDepth::DigLight, ExcavationDepth::DigFull] {
+                if level > tracked.depth
+                    && level <= target
+                    && !tracked.eligible_since.iter().any(|&(l, _)| l == level)
+                {
+                    tracked.eligible_since.push((level, now));
+                }
+            }
+            // Only escalate as far as has held for the whole dwell.
+            let dwell = Duration::milliseconds(self.params.min_dwell_ms);
+            let held = tracked
+                .eligible_since
+                .iter()
+                .filter(|&&(_, since)| now - since >= dwell)
+                .map(|&(level, _)| level)
+                .max();
+            match held {
+                Some(level) => {
+                    tracked.depth = level;
+                    tracked.eligible_since.retain(|&(l, _)| l > level);
+                    Some(TransitionKind::Escalate)
+                }
+                None => None,
+            }
+        };
+
+        let to = tracked.depth;
+        if let Some(kind) = kind {
+            self.transitions.push(DepthTransition {
+                object_id: object_id.to_string(),
+                at: now,
+                from,
+                to,
+                kind,
+                safety_min,
+            });
+        }
+        to
+    }
+
+    /// Transitions recorded since the last drain.
+    pub fn transitions(&self) -> &[DepthTransition] {
+        &self.transitions
+    }
+
+    /// Write and clear recorded transitions as NDJSON; returns the count.
+    pub fn export_transitions<W: Write>(&mut self, out: &mut W) -> io::Result<usize> {
+        for t in &self.transitions {
+            serde_json::to_writer(&mut *out, t)?;
+            out.write_all(b"\n")?;
+        }
+        out.flush()?;
+        let n = self.transitions.len();
+        self.transitions.clear();
+        Ok(n)
+    }
+
+    /// Stop tracking an object.
+    pub fn forget(&mut self, object_id: &str) -> bool {
+        self.objects.remove(object_id).is_some()
+    }
+}
+
+impl Flushable for DepthTracker {
+    fn flush_name(&self) -> String {
+        "depth_tracker".to_string()
+    }
+
+    fn abort_and_flush(&mut self, _reason: &str) -> Result<FlushOutcome, String> {
+        let cleared = self.objects.len() + self.transitions.len();
+        self.objects.clear();
+        self.transitions.clear();
+        Ok(FlushOutcome {
+            cleared,
+            detail: "tracked depths and transitions dropped".to_string(),
+            ..FlushOutcome::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::GovernanceStateV1;
+    use crate::spectral_vision::{evaluate_spectral_vision, SpectralVisionInputs};
+
+    fn decision(safety: f64, params: &SpectralVisionParams) -> SpectralVisionDecision {
+        let gov = GovernanceStateV1::active_governed();
+        let raw = [(0, 1.0 - safety, 0.0)];
+        let inputs = SpectralVisionInputs {
+            stability_score: 1.0,
+            confidence_score: 1.0,
+            ..Default::default()
+        };
+        evaluate_spectral_vision(&raw, inputs, params, &gov)
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_800_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_oscillation_around_slow_does_not_flap() {
+        let params = SpectralVisionParams::default();
+        let mut tracker = DepthTracker::new(HysteresisParams::default());
+        for (i, s) in [0.41, 0.39, 0.42, 0.38, 0.41].iter().enumerate() {
+            let depth = tracker.observe("obj", &decision(*s, &params), &params, at(60 * i as i64));
+            assert_eq!(depth, ExcavationDepth::Sniff);
+        }
+        assert!(tracker.transitions().is_empty());
+    }
+
+    #[test]
+    fn test_escalates_after_dwell_and_downgrades_immediately() {
+        let params = SpectralVisionParams::default();
+        let mut tracker = DepthTracker::new(HysteresisParams::default());
+
+        assert_eq!(
+            tracker.observe("obj", &decision(0.8, &params), &params, at(0)),
+            ExcavationDepth::Sniff
+        );
+        assert_eq!(
+            tracker.observe("obj", &decision(0.8, &params), &params, at(20)),
+            ExcavationDepth::Sniff
+        );
+        assert_eq!(
+            tracker.observe("obj", &decision(0.8, &params), &params, at(30)),
+            ExcavationDepth::DigFull
+        );
+
+        // Dropping below safety_slow downgrades at once, without dwell.
+        assert_eq!(
+            tracker.observe("obj", &decision(0.3, &params), &params, at(31)),
+            ExcavationDepth::Sniff
+        );
+        // Falling below the up threshold restarts the dwell.
+        tracker.observe("obj", &decision(0.5, &params), &params, at(40));
+        tracker.observe("obj", &decision(0.44, &params), &params, at(60));
+        assert_eq!(
+            tracker.observe("obj", &decision(0.5, &params), &params, at(75)),
+            ExcavationDepth::Sniff
+        );
+        assert_eq!(
+            tracker.observe("obj", &decision(0.5, &params), &params, at(105)),
+            ExcavationDepth::DigLight
+        );
+
+        let kinds: Vec<_> = tracker.transitions().iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TransitionKind::Escalate,
+                TransitionKind::Downgrade,
+                TransitionKind::Escalate
+            ]
+        );
+        let mut out = Vec::new();
+        assert_eq!(tracker.export_transitions(&mut out).unwrap(), 3);
+        let first: DepthTransition =
+            serde_json::from_str(String::from_utf8(out).unwrap().lines().next().unwrap()).unwrap();
+        assert_eq!(first.to, ExcavationDepth::DigFull);
+        assert!(tracker.transitions().is_empty());
+    }
+
+    #[test]
+    fn test_each_level_dwells_on_its_own_clock() {
+        let params = SpectralVisionParams::default();
+        let mut tracker = DepthTracker::new(HysteresisParams::default());
+
+        tracker.observe("obj", &decision(0.5, &params), &params, at(0));
+        assert_eq!(
+            tracker.observe("obj", &decision(0.8, &params), &params, at(20)),
+            ExcavationDepth::Sniff
+        );
+        // DigLight has held since t0; DigFull only since t20.
+        assert_eq!(
+            tracker.observe("obj", &decision(0.8, &params), &params, at(30)),
+            ExcavationDepth::DigLight
+        );
+        assert_eq!(
+            tracker.observe("obj", &decision(0.8, &params), &params, at(49)),
+            ExcavationDepth::DigLight
+        );
+        assert_eq!(
+            tracker.observe("obj", &decision(0.8, &params), &params, at(50)),
+            ExcavationDepth::DigFull
+        );
+    }
+}
@@ -0,0 +1,5 @@
+//! GhostNet spectral join keys and token types shared by XR grid, SANITY
+//! sessions and haunt-density mapping.
+
+pub mod spectral;
+pub mod tokens;
@@ -0,0 +1,434 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Current binary encoding version of RegionSessionKey.
+pub const REGION_SESSION_KEY_VERSION: u8 = 1;
+
+/// Prefix of the string encoding.
+const STRING_PREFIX: &str = "rsk1";
+
+/// Region-session key errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RegionKeyError {
+    #[error("Invalid cell size: {0}m (must be > 0)")]
+    InvalidCellSize(u32),
+    #[error("Invalid time window: {0}s (must be > 0)")]
+    InvalidWindow(u32),
+    #[error("Non-finite location: ({0}, {1})")]
+    NonFiniteLocation(f64, f64),
+    #[error("Location cell out of range: ({0}, {1})")]
+    LocationOutOfRange(f64, f64),
+    #[error("Time window out of range: index {0} of {1}s windows")]
+    WindowOutOfRange(i64, u32),
+    #[error("Session ID too long: {0} bytes")]
+    SessionTooLong(usize),
+    #[error("Malformed region-session key: {0}")]
+    Malformed(String),
+    #[error("Unsupported key version: {0}")]
+    UnsupportedVersion(u8),
+}
+
+/// Configurable spatial and temporal bucketing for RegionSessionKey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RegionBucketing {
+    /// Edge length of a square region cell, in meters.
+    pub cell_size_m: u32,
+    /// Length of a time window, in seconds.
+    pub window_secs: u32,
+}
+
+impl RegionBucketing {
+    /// 100 m cells, 5-minute windows.
+    pub const FIVE_MINUTES: Self = Self {
+        cell_size_m: 100,
+        window_secs: 300,
+    };
+
+    /// 100 m cells, 1-hour windows.
+    pub const ONE_HOUR: Self = Self {
+        cell_size_m: 100,
+        window_secs: 3600,
+    };
+
+    /// Create a validated bucketing configuration.
+    pub fn new(cell_size_m: u32, window_secs: u32) -> Result<Self, RegionKeyError> {
+        let cfg = Self {
+            cell_size_m,
+            window_secs,
+        };
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    /// Validate that both bucket sizes are non-zero.
+    pub fn validate(&self) -> Result<(), RegionKeyError> {
+        if self.cell_size_m == 0 {
+            return Err(RegionKeyError::InvalidCellSize(self.cell_size_m));
+        }
+        if self.window_secs == 0 {
+            return Err(RegionKeyError::InvalidWindow(self.window_secs));
+        }
+        Ok(())
+    }
+}
+
+impl Default for RegionBucketing {
+    fn default() -> Self {
+        Self::FIVE_MINUTES
+    }
+}
+
+/// Coarse region / time-window / session join key.
+///
+/// Joins SanityMeter sessions, HauntDensityMap regions and the hex-stamp
+/// location/time buckets. Keys only compare meaningfully under the same
+/// RegionBucketing; ordering is bucketing, then time window, then region
+/// cell, then session.
+///
+/// String form: `rsk1:<cell_size_m>m:<cell_x>:<cell_y>:<window_secs>s:<window_index>:<session_id>`.
+/// Binary form (big-endian): version u8, cell_size_m u32, window_secs u32,
+/// window_index i64, cell_x i32, cell_y i32, session length u16, session bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RegionSessionKey {
+    pub cell_size_m: u32,
+    pub window_secs: u32,
+    /// floor(unix_secs / window_secs).
+    pub window_index: i64,
+    /// floor(x_m / cell_size_m).
+    pub cell_x: i32,
+    /// floor(y_m / cell_size_m).
+    pub cell_y: i32,
+    /// Session identifier (may be empty for region-only keys).
+    pub session_id: String,
+}
+
+impl RegionSessionKey {
+    /// Bucket a raw planar location (meters) and unix time (seconds).
+    pub fn from_raw(
+        x_m: f64,
+        y_m: f64,
+        unix_secs: i64,
+        session_id: impl Into<String>,
+        bucketing: &RegionBucketing,
+    ) -> Result<Self, RegionKeyError> {
+        bucketing.validate()?;
+        if !x_m.is_finite() || !y_m.is_finite() {
+            return Err(RegionKeyError::NonFiniteLocation(x_m, y_m));
+        }
+
+        let cell = f64::from(bucketing.cell_size_m);
+        let cx = (x_m / cell).floor();
+        let cy = (y_m / cell).floor();
+        let range = f64::from(i32::MIN)..=f64::from(i32::MAX);
+        if !range.contains(&cx) || !range.contains(&cy) {
+            return Err(RegionKeyError::LocationOutOfRange(x_m, y_m));
+        }
+
+        let window_index = unix_secs.div_euclid(i64::from(bucketing.window_secs));
+        window_start(window_index, bucketing.window_secs)?;
+
+        let session_id = session_id.into();
+        if session_id.len() > usize::from(u16::MAX) {
+            return Err(RegionKeyError::SessionTooLong(session_id.len()));
+        }
+
+        Ok(Self {
+            cell_size_m: bucketing.cell_size_m,
+            window_secs: bucketing.window_secs,
+            window_index,
+            cell_x: cx as i32,
+            cell_y: cy as i32,
+            session_id,
+        })
+    }
+
+    /// Bucketing this key was built with.
+    pub fn bucketing(&self) -> RegionBucketing {
+        RegionBucketing {
+            cell_size_m: self.cell_size_m,
+            window_secs: self.window_secs,
+        }
+    }
+
+    /// Same region and window, different session.
+    pub fn with_session(&self, session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            ..self.clone()
+        }
+    }
+
+    /// Region-only identifier, usable as SanityMeter / HauntDensityMap region_id.
+    pub fn region_id(&self) -> String {
+        format!("{}m:{}:{}", self.cell_size_m, self.cell_x, self.cell_y)
+    }
+
+    /// Hex-stamp location bucket: cell_x (high 32 bits) and cell_y (low 32 bits).
+    pub fn location_bucket(&self) -> u64 {
+        (u64::from(self.cell_x as u32) << 32) | u64::from(self.cell_y as u32)
+    }
+
+    /// Hex-stamp time bucket: window start in unix seconds as two's
+    /// complement, so pre-epoch windows stay distinct.
+    pub fn time_bucket(&self) -> u64 {
+        let start = window_start(self.window_index, self.window_secs).unwrap_or_else(|_| {
+            self.window_index
+                .saturating_mul(i64::from(self.window_secs))
+        });
+        start as u64
+    }
+
+    /// Stable binary encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(27 + self.session_id.len());
+        out.push(REGION_SESSION_KEY_VERSION);
+        out.extend_from_slice(&self.cell_size_m.to_be_bytes());
+        out.extend_from_slice(&self.window_secs.to_be_bytes());
+        out.extend_from_slice(&self.window_index.to_be_bytes());
+        out.extend_from_slice(&self.cell_x.to_be_bytes());
+        out.extend_from_slice(&self.cell_y.to_be_bytes());
+        out.extend_from_slice(&(self.session_id.len() as u16).to_be_bytes());
+        out.extend_from_slice(self.session_id.as_bytes());
+        out
+    }
+
+    /// Decode the binary encoding produced by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RegionKeyError> {
+        fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8], RegionKeyError> {
+            if bytes.len() < n {
+                return Err(RegionKeyError::Malformed("truncated binary key".to_string()));
+            }
+            let (head, tail) = bytes.split_at(n);
+            *bytes = tail;
+            Ok(head)
+        }
+        fn array<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N], RegionKeyError> {
+            let mut out = [0u8; N];
+            out.copy_from_slice(take(bytes, N)?);
+            Ok(out)
+        }
+
+        let mut rest = bytes;
+        let version = array::<1>(&mut rest)?[0];
+        if version != REGION_SESSION_KEY_VERSION {
+            return Err(RegionKeyError::UnsupportedVersion(version));
+        }
+        let cell_size_m = u32::from_be_bytes(array(&mut rest)?);
+        let window_secs = u32::from_be_bytes(array(&mut rest)?);
+        let window_index = i64::from_be_bytes(array(&mut rest)?);
+        let cell_x = i32::from_be_bytes(array(&mut rest)?);
+        let cell_y = i32::from_be_bytes(array(&mut rest)?);
+        let len = usize::from(u16::from_be_bytes(array(&mut rest)?));
+        let session_id = std::str::from_utf8(take(&mut rest, len)?)
+            .map_err(|_| RegionKeyError::Malformed("session is not UTF-8".to_string()))?
+            .to_string();
+        if !rest.is_empty() {
+            return Err(RegionKeyError::Malformed("trailing bytes".to_string()));
+        }
+
+        RegionBucketing {
+            cell_size_m,
+            window_secs,
+        }
+        .validate()?;
+        window_start(window_index, window_secs)?;
+
+        Ok(Self {
+            cell_size_m,
+            window_secs,
+            window_index,
+            cell_x,
+            cell_y,
+            session_id,
+        })
+    }
+}
+
+/// Window start in unix seconds; keys whose start overflows i64 are rejected.
+fn window_start(window_index: i64, window_secs: u32) -> Result<i64, RegionKeyError> {
+    window_index
+        .checked_mul(i64::from(window_secs))
+        .ok_or(RegionKeyError::WindowOutOfRange(window_index, window_secs))
+}
+
+impl fmt::Display for RegionSessionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}m:{}:{}:{}s:{}:{}",
+            STRING_PREFIX,
+            self.cell_size_m,
+            self.cell_x,
+            self.cell_y,
+            self.window_secs,
+            self.window_index,
+            self.session_id
+        )
+    }
+}
+
+impl FromStr for RegionSessionKey {
+    type Err = RegionKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || RegionKeyError::Malformed(s.to_string());
+
+        // Session is last and may itself contain ':'.
+        let parts: Vec<&str> = s.splitn(7, ':').collect();
+        if parts.len() != 7 || parts[0] != STRING_PREFIX {
+            return Err(malformed());
+        }
+        let cell_size_m = parts[1]
+            .strip_suffix('m')
+            .and_then(|v| v.parse::<u32>().ok())
+            .ok_or_else(malformed)?;
+        let cell_x = parts[2].parse::<i32>().map_err(|_| malformed())?;
+        let cell_y = parts[3].parse::<i32>().map_err(|_| malformed())?;
+        let window_secs = parts[4]
+            .strip_suffix('s')
+            .and_then(|v| v.parse::<u32>().ok())
+            .ok_or_else(malformed)?;
+        let window_index = parts[5].parse::<i64>().map_err(|_| malformed())?;
+
+        RegionBucketing {
+            cell_size_m,
+            window_secs,
+        }
+        .validate()?;
+        window_start(window_index, window_secs)?;
+
+        Ok(Self {
+            cell_size_m,
+            window_secs,
+            window_index,
+            cell_x,
+            cell_y,
+            session_id: parts[6].to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for RegionSessionKey {
+    type Error = RegionKeyError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<RegionSessionKey> for String {
+    fn from(key: RegionSessionKey) -> Self {
+        key.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucketing() {
+        let key =
+            RegionSessionKey::from_raw(250.0, -1.0, 1_000, "s1", &RegionBucketing::FIVE_MINUTES)
+                .unwrap();
+        assert_eq!((key.cell_x, key.cell_y), (2, -1));
+        assert_eq!(key.window_index, 3);
+        assert_eq!(key.time_bucket(), 900);
+        assert_eq!(key.location_bucket(), (2u64 << 32) | 0xffff_ffff);
+
+        let hourly =
+            RegionSessionKey::from_raw(250.0, -1.0, 1_000, "s1", &RegionBucketing::ONE_HOUR)
+                .unwrap();
+        assert_eq!(hourly.window_index, 0);
+        assert_eq!(
+            RegionSessionKey::from_raw(0.0, 0.0, -1, "", &RegionBucketing::ONE_HOUR)
+                .unwrap()
+                .window_index,
+            -1
+        );
+
+        // Pre-epoch windows keep distinct time buckets.
+        let at = |t| {
+            RegionSessionKey::from_raw(0.0, 0.0, t, "", &RegionBucketing::FIVE_MINUTES)
+                .unwrap()
+                .time_bucket()
+        };
+        assert_eq!(at(-1), -300i64 as u64);
+        assert_eq!(at(-301), -600i64 as u64);
+        assert_ne!(at(-1), at(0));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let cfg = RegionBucketing::FIVE_MINUTES;
+        assert!(RegionBucketing::new(0, 300).is_err());
+        assert!(RegionBucketing::new(10, 0).is_err());
+        assert!(RegionSessionKey::from_raw(f64::NAN, 0.0, 0, "", &cfg).is_err());
+        assert!(RegionSessionKey::from_raw(1e30, 0.0, 0, "", &cfg).is_err());
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        let key = RegionSessionKey::from_raw(-150.0, 420.0, 7_200, "sess:01", &RegionBucketing::ONE_HOUR)
+            .unwrap();
+        let s = key.to_string();
+        assert_eq!(s, "rsk1:100m:-2:4:3600s:2:sess:01");
+        assert_eq!(s.parse::<RegionSessionKey>().unwrap(), key);
+
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(json, format!("\"{}\"", s));
+        assert_eq!(serde_json::from_str::<RegionSessionKey>(&json).unwrap(), key);
+
+        assert!("rsk1:0m:0:0:300s:0:".parse::<RegionSessionKey>().is_err());
+        assert!("rsk2:100m:0:0:300s:0:".parse::<RegionSessionKey>().is_err());
+        assert!("rsk1:100m:0:0:300s:0".parse::<RegionSessionKey>().is_err());
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let key = RegionSessionKey::from_raw(99.0, 101.0, 601, "s", &RegionBucketing::FIVE_MINUTES)
+            .unwrap();
+        let bytes = key.to_bytes();
+        assert_eq!(bytes.len(), 28);
+        assert_eq!(RegionSessionKey::from_bytes(&bytes).unwrap(), key);
+        assert!(RegionSessionKey::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut bad = bytes.clone();
+        bad[0] = 9;
+        assert_eq!(
+            RegionSessionKey::from_bytes(&bad),
+            Err(RegionKeyError::UnsupportedVersion(9))
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_window_is_rejected() {
+        let max = format!("rsk1:100m:0:0:300s:{}:x", i64::MAX);
+        assert_eq!(
+            max.parse::<RegionSessionKey>(),
+            Err(RegionKeyError::WindowOutOfRange(i64::MAX, 300))
+        );
+        let mut key =
+            RegionSessionKey::from_raw(0.0, 0.0, 0, "x", &RegionBucketing::FIVE_MINUTES).unwrap();
+        key.window_index = i64::MIN;
+        assert!(RegionSessionKey::from_bytes(&key.to_bytes()).is_err());
+        assert!(RegionSessionKey::from_raw(0.0, 0.0, i64::MIN, "", &RegionBucketing::ONE_HOUR).is_err());
+
+        // Built by hand: no overflow panic.
+        assert_eq!(key.time_bucket(), i64::MIN as u64);
+        key.window_index = i64::MAX / 300;
+        assert_eq!(key.time_bucket(), (i64::MAX / 300 * 300) as u64);
+    }
+
+    #[test]
+    fn test_ordering_is_time_major() {
+        let cfg = RegionBucketing::FIVE_MINUTES;
+        let early_far = RegionSessionKey::from_raw(5_000.0, 0.0, 0, "", &cfg).unwrap();
+        let late_near = RegionSessionKey::from_raw(0.0, 0.0, 600, "", &cfg).unwrap();
+        assert!(early_far < late_near);
+        assert!(early_far < early_far.with_session("a"));
+    }
+}
@@ -0,0 +1,251 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::ghostnet::spectral::RegionSessionKey;
+use crate::governance::spectral_governance_audit::SpectralGovernanceAudit;
+use crate::governance::PolicyViolation;
+use crate::hexstamp_quantum_roaming::{HexStampQuantumRoamingV1, RoamingStamp, XRZone};
+use crate::sanity_meter_core::EmotionalState;
+use crate::xr_grid_core::{XRGridDecision, XRGridState, XRRouteAction};
+
+/// Psych-risk band, shared with XRGridRiskIndex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PsychRiskBand {
+    /// X ∈ [0.0, 0.4)
+    Normal,
+    /// X ∈ [0.4, 0.7)
+    Moderate,
+    /// X ∈ [0.7, 1.0]
+    High,
+}
+
+impl PsychRiskBand {
+    /// Classify a composite risk index X; non-finite input maps to Normal.
+    pub fn from_index(x: f32) -> Self {
+        let x = if x.is_finite() { x.clamp(0.0, 1.0) } else { 0.0 };
+        if x < 0.4 {
+            Self::Normal
+        } else if x < 0.7 {
+            Self::Moderate
+        } else {
+            Self::High
+        }
+    }
+
+    /// Wire label ("NORMAL" | "MODERATE" | "HIGH").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "NORMAL",
+            Self::Moderate => "MODERATE",
+            Self::High => "HIGH",
+        }
+    }
+}
+
+/// GhostNet PsychRiskView: nonsoul, routing-only view of a risk index.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PsychRiskView {
+    /// Risk index X ∈ [0,1].
+    pub index: f32,
+    /// Band, same thresholds as XRGridRiskIndex.
+    pub band: PsychRiskBand,
+    /// SANITY-meter emotional state for the same load.
+    pub emotional_state: EmotionalState,
+}
+
+impl PsychRiskView {
+    /// Build a view from a composite risk index X.
+    pub fn from_index(x: f32) -> Self {
+        let index = if x.is_finite() { x.clamp(0.0, 1.0) } else { 0.0 };
+        Self {
+            index,
+            band: PsychRiskBand::from_index(index),
+            emotional_state: EmotionalState::from_load(f64::from(index)),
+        }
+    }
+}
+
+/// Token event kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenEventKind {
+    /// One XR routing decision.
+    XrRouteDecision,
+}
+
+/// GhostNet token event, one NDJSON line per record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenEvent {
+    pub event_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub kind: TokenEventKind,
+    pub region_session: RegionSessionKey,
+    pub xr_zone: XRZone,
+    pub action: XRRouteAction,
+    pub psych_risk: PsychRiskView,
+    /// Governance snapshot the decision was taken under.
+    pub gov: SpectralGovernanceAudit,
+    /// Roaming stamp, logged for audit only.
+    pub roaming_stamp: Option<HexStampQuantumRoamingV1>,
+    /// True if the stamp was issued under non-interference.
+    pub stamp_audit_only: bool,
+    /// Routing gates that fired, including advisory-only ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<PolicyViolation>,
+}
+
+impl TokenEvent {
+    /// Record an XR routing decision as a token event.
+    pub fn from_xr_decision(state: &XRGridState, decision: &XRGridDecision) -> Self {
+        Self {
+            event_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            kind: TokenEventKind::XrRouteDecision,
+            region_session: state.region_session.clone(),
+            xr_zone: decision.xr_zone,
+            action: decision.action,
+            psych_risk: PsychRiskView::from_index(decision.risk.x),
+            gov: state.gov.clone(),
+            roaming_stamp: decision.hex_stamp.as_ref().map(|s| s.for_audit().clone()),
+            stamp_audit_only: matches!(decision.hex_stamp, Some(RoamingStamp::AuditOnly(_))),
+            violations: decision.violations.clone(),
+        }
+    }
+}
+
+/// Append-only NDJSON writer for token events.
+pub struct TokenEventWriter<W: Write> {
+    out: W,
+    written: u64,
+}
+
+impl TokenEventWriter<File> {
+    /// Open (or create) a log file in append mode; existing lines are never rewritten.
+    pub fn open_append(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(file))
+    }
+}
+
+impl<W: Write> TokenEventWriter<W> {
+    /// Wrap any writer.
+    pub fn new(out: W) -> Self {
+        Self { out, written: 0 }
+    }
+
+    /// Append one event as a single line and flush it.
+    pub fn append(&mut self, event: &TokenEvent) -> io::Result<()> {
+        let line = serde_json::to_string(event)?;
+        writeln!(self.out, "{}", line)?;
+        self.out.flush()?;
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Number of events appended through this writer.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Consume the writer, returning the inner sink.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// Read back an NDJSON token-event log, skipping blank lines.
+pub fn read_token_events<R: io::Read>(input: R) -> io::Result<Vec<TokenEvent>> {
+    let mut events = Vec::new();
+    for line in BufReader::new(input).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        events.push(serde_json::from_str(&line)?);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ghostnet::spectral::RegionBucketing;
+    use crate::governance::{EnforcementMode, GovernanceMode};
+    use crate::hauntdensity::HauntDensityScore;
+    use crate::hexstamp_quantum_roaming::maybe_stamp_for_roaming;
+    use crate::sleep_state::SleepGateParams;
+    use crate::xr_grid_core::decide_xr_route_for_state;
+
+    #[test]
+    fn test_bands_match_thresholds() {
+        assert_eq!(PsychRiskBand::from_index(0.39), PsychRiskBand::Normal);
+        assert_eq!(PsychRiskBand::from_index(0.4), PsychRiskBand::Moderate);
+        assert_eq!(PsychRiskBand::from_index(0.7), PsychRiskBand::High);
+        assert_eq!(PsychRiskBand::from_index(f32::NAN), PsychRiskBand::Normal);
+
+        let view = PsychRiskView::from_index(0.8);
+        assert_eq!(view.band, PsychRiskBand::High);
+        assert_eq!(view.emotional_state, EmotionalState::Distressed);
+        assert_eq!(serde_json::to_string(&view.band).unwrap(), "\"HIGH\"");
+    }
+
+    #[test]
+    fn test_xr_decision_logged_as_token_event() {
+        let gov = SpectralGovernanceAudit {
+            spectralroamingactive: true,
+            noninterferencerequired: true,
+            spectralquantificationactive: true,
+            soulmodelingforbidden: true,
+            channelactive: true,
+            bindingstrength: 0.5,
+            mode: GovernanceMode::ActiveFree,
+        };
+        let state = XRGridState {
+            region_session: RegionSessionKey::from_raw(
+                10.0,
+                20.0,
+                600,
+                "s1",
+                &RegionBucketing::FIVE_MINUTES,
+            )
+            .unwrap(),
+            haunt: Some(HauntDensityScore::new(0.3)),
+            fear_level: 0.2,
+            fear_rate_norm: 0.1,
+            psych_load: 0.3,
+            gov: gov.clone(),
+            enforcement: EnforcementMode::Strict,
+            sleep: None,
+            sleep_gate: SleepGateParams::default(),
+        };
+        let stamp = maybe_stamp_for_roaming(
+            &gov,
+            XRZone::XRMONITORED,
+            0.3,
+            state.region_session.location_bucket(),
+            state.region_session.time_bucket(),
+        );
+        let decision = decide_xr_route_for_state(&state, state.haunt.as_ref(), stamp);
+
+        let mut writer = TokenEventWriter::new(Vec::new());
+        let event = TokenEvent::from_xr_decision(&state, &decision);
+        writer.append(&event).unwrap();
+        writer.append(&event).unwrap();
+        assert_eq!(writer.written(), 2);
+
+        let bytes = writer.into_inner();
+        assert_eq!(bytes.iter().filter(|b| **b == b'\n').count(), 2);
+        let events = read_token_events(bytes.as_slice()).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].action, XRRouteAction::ObserveOnly);
+        assert!(events[0].stamp_audit_only);
+        assert_eq!(events[0].violations[0].gate, "xr_grid.non_interference");
+        assert!(events[0].violations[0].enforced);
+        assert_eq!(events[0].region_session, state.region_session);
+    }
+}
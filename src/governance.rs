@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+
+pub mod abort_flush;
+pub mod spectral_governance_audit;
+
+use spectral_governance_audit::SpectralGovernanceAudit;
+
+/// Governance mode wrapping every spectral-vision and XR routing decision.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum GovernanceMode {
+    /// Roaming + non-interference: observe-only, no steering.
+    ActiveFree,
+    /// Active channel under full governance gates.
+    ActiveGoverned,
+    /// Non-resurrection: histories only for integrity, recompute and audits.
+    Dormant,
+    /// Technical telemetry only, no spectral channel bound.
+    TechnicalOnly,
+}
+
+/// Enforcement mode from the ALN `@config runtime_settings` block.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EnforcementMode {
+    /// Violations are blocked.
+    #[default]
+    Strict,
+    /// Violations are recorded and annotated but not blocked.
+    Advisory,
+    /// Gates are not evaluated.
+    Disabled,
+}
+
+impl EnforcementMode {
+    /// Parse the ALN spelling ("strict" | "advisory" | "disabled").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "strict" => Some(Self::Strict),
+            "advisory" => Some(Self::Advisory),
+            "disabled" => Some(Self::Disabled),
+            _ => None,
+        }
+    }
+
+    /// Resolve a soft gate that fired. Strict returns `enforced`; advisory
+    /// records the violation and returns `permissive`; disabled returns
+    /// `permissive` without recording anything. Non-waivable gates use
+    /// [`PolicyViolation::enforce`] instead.
+    pub fn resolve<T>(
+        self,
+        gate: &str,
+        detail: impl Into<String>,
+        enforced: T,
+        permissive: T,
+        violations: &mut Vec<PolicyViolation>,
+    ) -> T {
+        if self == Self::Disabled {
+            return permissive;
+        }
+        let strict = self == Self::Strict;
+        violations.push(PolicyViolation {
+            gate: gate.to_string(),
+            detail: detail.into(),
+            enforced: strict,
+        });
+        if strict {
+            enforced
+        } else {
+            permissive
+        }
+    }
+}
+
+/// A gate that fired, annotated on the result it would have changed.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PolicyViolation {
+    /// Gate identifier, e.g. `xr_grid.non_interference`.
+    pub gate: String,
+    /// What the gate would do and why.
+    pub detail: String,
+    /// False when the gate was only recorded (advisory mode).
+    pub enforced: bool,
+}
+
+impl PolicyViolation {
+    /// Record a non-waivable gate and return `enforced` in every
+    /// enforcement mode, including disabled.
+    pub fn enforce<T>(
+        gate: &str,
+        detail: impl Into<String>,
+        enforced: T,
+        violations: &mut Vec<PolicyViolation>,
+    ) -> T {
+        violations.push(PolicyViolation {
+            gate: gate.to_string(),
+            detail: detail.into(),
+            enforced: true,
+        });
+        enforced
+    }
+}
+
+/// Governance envelope (SpectralVision.md §3).
+///
+/// These bits are non-waivable across all modules: if
+/// spectral_quantification_active or soul_modeling_forbidden are false,
+/// callers must AbortAndFlush.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GovernanceStateV1 {
+    pub spectral_roaming_active: bool,
+    pub non_interference_required: bool,
+    pub spectral_quantification_active: bool,
+    pub soul_modeling_forbidden: bool,
+    pub channel_active: bool,
+    /// Binding strength in [0,1].
+    pub binding_strength: f32,
+    pub mode: GovernanceMode,
+}
+
+impl GovernanceStateV1 {
+    /// True if the constitutional bits allow any spectral-vision at all.
+    pub fn allows_spectral_vision(&self) -> bool {
+        self.spectral_quantification_active && self.soul_modeling_forbidden
+    }
+
+    /// True if roaming + non-interference force observe-only behaviour.
+    pub fn requires_observe_only(&self) -> bool {
+        self.spectral_roaming_active && self.non_interference_required
+    }
+
+    /// Snapshot this state for audit/XR routing.
+    pub fn to_audit(&self) -> SpectralGovernanceAudit {
+        SpectralGovernanceAudit::from(self)
+    }
+}
+
+#[cfg(test)]
+impl GovernanceStateV1 {
+    /// Test fixture: constitutional bits set, no roaming, ActiveGoverned.
+    pub(crate) fn active_governed() -> Self {
+        Self {
+            spectral_roaming_active: false,
+            non_interference_required: false,
+            spectral_quantification_active: true,
+            soul_modeling_forbidden: true,
+            channel_active: true,
+            binding_strength: 0.5,
+            mode: GovernanceMode::ActiveGoverned,
+        }
+    }
+}
+
+/// Stable 64-bit mixer shared by all governance hex-stamps.
+///
+/// FNV-1a over the input bytes, finished with the splitmix64 avalanche.
+/// Pure integer arithmetic, so results are identical on every platform.
+pub fn stable_mix64(bytes: &[u8]) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut h = FNV_OFFSET;
+    for b in bytes {
+        h ^= u64::from(*b);
+        h = h.wrapping_mul(FNV_PRIME);
+    }
+
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^= h >> 31;
+    h
+}
+
+impl From<&SpectralGovernanceAudit> for GovernanceStateV1 {
+    fn from(audit: &SpectralGovernanceAudit) -> Self {
+        Self {
+            spectral_roaming_active: audit.spectralroamingactive,
+            non_interference_required: audit.noninterferencerequired,
+            spectral_quantification_active: audit.spectralquantificationactive,
+            soul_modeling_forbidden: audit.soulmodelingforbidden,
+            channel_active: audit.channelactive,
+            binding_strength: audit.bindingstrength,
+            mode: audit.mode,
+        }
+    }
+}
+
+impl From<SpectralGovernanceAudit> for GovernanceStateV1 {
+    fn from(audit: SpectralGovernanceAudit) -> Self {
+        Self::from(&audit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_state() -> GovernanceStateV1 {
+        GovernanceStateV1 {
+            spectral_roaming_active: true,
+            non_interference_required: false,
+            spectral_quantification_active: true,
+            soul_modeling_forbidden: true,
+            channel_active: true,
+            binding_strength: 0.37,
+            mode: GovernanceMode::ActiveGoverned,
+        }
+    }
+
+    #[test]
+    fn test_state_audit_round_trip() {
+        let state = sample_state();
+        let audit = state.to_audit();
+        assert_eq!(audit.bindingstrength, 0.37);
+        assert_eq!(audit.mode, GovernanceMode::ActiveGoverned);
+        assert_eq!(GovernanceStateV1::from(&audit), state);
+        assert_eq!(audit.to_state().to_audit(), audit);
+    }
+
+    #[test]
+    fn test_observe_only_and_abort_bits() {
+        let mut state = sample_state();
+        assert!(state.allows_spectral_vision());
+        assert!(!state.requires_observe_only());
+
+        state.non_interference_required = true;
+        assert!(state.requires_observe_only());
+        assert!(state.to_audit().requires_observe_only());
+
+        state.soul_modeling_forbidden = false;
+        assert!(!state.allows_spectral_vision());
+        assert!(!state.to_audit().allows_spectral_vision());
+    }
+
+    #[test]
+    fn test_enforcement_mode_resolve() {
+        let mut v = Vec::new();
+        assert_eq!(EnforcementMode::Strict.resolve("g", "d", 1, 0, &mut v), 1);
+        assert!(v[0].enforced);
+        assert_eq!(EnforcementMode::Advisory.resolve("g", "d", 1, 0, &mut v), 0);
+        assert!(!v[1].enforced);
+        assert_eq!(EnforcementMode::Disabled.resolve("g", "d", 1, 0, &mut v), 0);
+        assert_eq!(v.len(), 2);
+        assert_eq!(EnforcementMode::from_name("advisory"), Some(EnforcementMode::Advisory));
+        assert_eq!(EnforcementMode::from_name("lenient"), None);
+
+        assert_eq!(PolicyViolation::enforce("hard", "d", 1, &mut v), 1);
+        assert_eq!(v.len(), 3);
+        assert!(v[2].enforced);
+    }
+}
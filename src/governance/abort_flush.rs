@@ -0,0 +1,375 @@
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// ALN `runtime_settings.flush_timeout_ms` default.
+pub const DEFAULT_FLUSH_TIMEOUT_MS: u64 = 50;
+
+/// `key_id` of reports produced without a configured signing key.
+pub const UNSIGNED_KEY_ID: &str = "unsigned";
+
+/// What a participant cleared or quarantined during a flush.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FlushOutcome {
+    /// Records dropped outright.
+    pub cleared: usize,
+    /// Records moved out of reach of reads, kept for audit.
+    pub quarantined: usize,
+    pub detail: String,
+}
+
+/// A component holding in-memory spectral state that AbortAndFlush must clear.
+pub trait Flushable: Send {
+    /// Stable name used in the flush report.
+    fn flush_name(&self) -> String;
+
+    /// Clear or quarantine all in-memory state. Must not block on I/O.
+    fn abort_and_flush(&mut self, reason: &str) -> Result<FlushOutcome, String>;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum FlushStatus {
+    Complied,
+    /// The participant reported an error.
+    Failed(String),
+    /// No answer before the deadline; its state must be treated as unflushed.
+    TimedOut,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ParticipantFlush {
+    pub participant: String,
+    pub status: FlushStatus,
+    pub outcome: Option<FlushOutcome>,
+    /// Time from trigger to answer; None when timed out.
+    pub elapsed_ms: Option<f64>,
+}
+
+/// Signed AbortAndFlush report.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FlushReport {
+    pub flush_id: String,
+    pub reason: String,
+    pub triggered_at: DateTime<Utc>,
+    pub deadline_ms: u64,
+    pub participants: Vec<ParticipantFlush>,
+    /// Signing key name, or [`UNSIGNED_KEY_ID`] when no key was configured.
+    pub key_id: String,
+    /// Hex HMAC-SHA256 over the report with this field empty; empty when unsigned.
+    pub signature: String,
+}
+
+impl FlushReport {
+    /// True if there was at least one participant and every participant
+    /// complied before the deadline. Flushing nothing is not compliance.
+    pub fn all_complied(&self) -> bool {
+        !self.participants.is_empty()
+            && self
+                .participants
+                .iter()
+                .all(|p| p.status == FlushStatus::Complied)
+    }
+
+    /// True if the report carries no signature.
+    pub fn is_unsigned(&self) -> bool {
+        self.signature.is_empty()
+    }
+
+    /// Participants that did not answer in time.
+    pub fn timed_out(&self) -> Vec<&str> {
+        self.participants
+            .iter()
+            .filter(|p| p.status == FlushStatus::TimedOut)
+            .map(|p| p.participant.as_str())
+            .collect()
+    }
+
+    /// Check the signature against `signer`'s key; unsigned reports never verify.
+    pub fn verify(&self, signer: &FlushSigner) -> bool {
+        !self.is_unsigned() && self.key_id == signer.key_id && signer.sign(self) == self.signature
+    }
+}
+
+/// HMAC-SHA256 key used to sign flush reports.
+#[derive(Clone)]
+pub struct FlushSigner {
+    key_id: String,
+    key: Vec<u8>,
+}
+
+impl FlushSigner {
+    pub fn new(key_id: impl Into<String>, key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key_id: key_id.into(),
+            key: key.into(),
+        }
+    }
+
+    /// Signer keyed by environment variable `var`, named after it. None when
+    /// the variable is unset or empty: there is no built-in fallback key.
+    pub fn from_env(var: &str) -> Option<Self> {
+        match std::env::var(var) {
+            Ok(key) if !key.is_empty() => Some(Self::new(var, key.into_bytes())),
+            _ => None,
+        }
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn sign(&self, report: &FlushReport) -> String {
+        let mut unsigned = report.clone();
+        unsigned.signature.clear();
+        let payload = serde_json::to_vec(&unsigned).unwrap_or_default();
+        hmac_sha256(&self.key, &payload)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for FlushSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlushSigner")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+fn hmac_sha256(key: &[u8], msg: &[u8]) -> [u8; 32] {
+    const BLOCK: usize = 64;
+    let mut k = [0u8; BLOCK];
+    if key.len() > BLOCK {
+        k[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        k[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(k.map(|b| b ^ 0x36));
+    inner.update(msg);
+    let mut outer = Sha256::new();
+    outer.update(k.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+/// Runs AbortAndFlush across all registered participants.
+///
+/// Each participant is flushed on its own thread so one stuck lock cannot
+/// delay the others; anything not answering within the deadline is
+/// reported as timed out.
+pub struct AbortAndFlush {
+    /// Participants with the name captured at registration, so a participant
+    /// that is still holding its lock can be named when it times out.
+    participants: Vec<(String, Arc<Mutex<dyn Flushable>>)>,
+    timeout: Duration,
+    signer: Option<FlushSigner>,
+}
+
+impl AbortAndFlush {
+    pub fn new(signer: FlushSigner) -> Self {
+        Self {
+            participants: Vec::new(),
+            timeout: Duration::from_millis(DEFAULT_FLUSH_TIMEOUT_MS),
+            signer: Some(signer),
+        }
+    }
+
+    /// Coordinator without a signing key; reports carry [`UNSIGNED_KEY_ID`]
+    /// and an empty signature.
+    pub fn unsigned() -> Self {
+        Self {
+            signer: None,
+            ..Self::new(FlushSigner::new(UNSIGNED_KEY_ID, Vec::new()))
+        }
+    }
+
+    /// Override the deadline, e.g. from the loaded ALN `flush_timeout_ms`.
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout = Duration::from_millis(timeout_ms);
+        self
+    }
+
+    pub fn register<T: Flushable + 'static>(&mut self, participant: Arc<Mutex<T>>) {
+        let name = participant
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .flush_name();
+        self.participants.push((name, participant));
+    }
+
+    pub fn participant_count(&self) -> usize {
+        self.participants.len()
+    }
+
+    /// Flush every participant and return the signed report.
+    pub fn trigger(&self, reason: &str) -> FlushReport {
+        let triggered_at = Utc::now();
+        let started = Instant::now();
+        let deadline = started + self.timeout;
+        let (tx, rx) = mpsc::channel();
+
+        for (idx, (_, participant)) in self.participants.iter().enumerate() {
+            let participant = Arc::clone(participant);
+            let tx = tx.clone();
+            let reason = reason.to_string();
+            thread::spawn(move || {
+                let mut p = participant.lock().unwrap_or_else(|e| e.into_inner());
+                let result = p.abort_and_flush(&reason);
+                let _ = tx.send((idx, result, started.elapsed()));
+            });
+        }
+        drop(tx);
+
+        let mut answers: Vec<Option<ParticipantFlush>> = vec![None; self.participants.len()];
+        let mut pending = self.participants.len();
+        while pending > 0 {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match rx.recv_timeout(remaining) {
+                Ok((idx, result, elapsed)) => {
+                    let (status, outcome) = match result {
+                        Ok(outcome) => (FlushStatus::Complied, Some(outcome)),
+                        Err(e) => (FlushStatus::Failed(e), None),
+                    };
+                    answers[idx] = Some(ParticipantFlush {
+                        participant: self.participants[idx].0.clone(),
+                        status,
+                        outcome,
+                        elapsed_ms: Some(elapsed.as_secs_f64() * 1000.0),
+                    });
+                    pending -= 1;
+                }
+                Err(_) => break,
+            }
+        }
+
+        let participants = answers
+            .into_iter()
+            .enumerate()
+            .map(|(idx, answer)| {
+                answer.unwrap_or_else(|| ParticipantFlush {
+                    participant: self.participants[idx].0.clone(),
+                    status: FlushStatus::TimedOut,
+                    outcome: None,
+                    elapsed_ms: None,
+                })
+            })
+            .collect();
+
+        let mut report = FlushReport {
+            flush_id: Uuid::new_v4().to_string(),
+            reason: reason.to_string(),
+            triggered_at,
+            deadline_ms: self.timeout.as_millis() as u64,
+            participants,
+            key_id: self
+                .signer
+                .as_ref()
+                .map_or(UNSIGNED_KEY_ID.to_string(), |s| s.key_id.clone()),
+            signature: String::new(),
+        };
+        if let Some(signer) = &self.signer {
+            report.signature = signer.sign(&report);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Buffer {
+        name: &'static str,
+        items: Vec<u32>,
+        delay: Duration,
+    }
+
+    impl Flushable for Buffer {
+        fn flush_name(&self) -> String {
+            self.name.to_string()
+        }
+
+        fn abort_and_flush(&mut self, _reason: &str) -> Result<FlushOutcome, String> {
+            thread::sleep(self.delay);
+            let cleared = self.items.len();
+            self.items.clear();
+            Ok(FlushOutcome {
+                cleared,
+                ..FlushOutcome::default()
+            })
+        }
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231_case_2() {
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        let hex: String = mac.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(
+            hex,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_flush_reports_compliance_and_timeouts() {
+        let fast = Arc::new(Mutex::new(Buffer {
+            name: "fast",
+            items: vec![1, 2, 3],
+            delay: Duration::ZERO,
+        }));
+        let slow = Arc::new(Mutex::new(Buffer {
+            name: "slow",
+            items: vec![4],
+            delay: Duration::from_millis(300),
+        }));
+
+        let signer = FlushSigner::new("test-key", b"secret".to_vec());
+        let mut flush = AbortAndFlush::new(signer.clone());
+        flush.register(Arc::clone(&fast));
+        flush.register(slow);
+
+        let report = flush.trigger("soul_id key ingested");
+        assert_eq!(report.deadline_ms, DEFAULT_FLUSH_TIMEOUT_MS);
+        assert_eq!(report.participants[0].status, FlushStatus::Complied);
+        assert_eq!(report.participants[0].outcome.as_ref().unwrap().cleared, 3);
+        assert_eq!(report.timed_out(), vec!["slow"]);
+        assert!(!report.all_complied());
+        assert!(fast.lock().unwrap().items.is_empty());
+
+        assert!(report.verify(&signer));
+        assert!(!report.verify(&FlushSigner::new("test-key", b"other".to_vec())));
+        let mut tampered = report.clone();
+        tampered.participants[1].status = FlushStatus::Complied;
+        assert!(!tampered.verify(&signer));
+    }
+
+    #[test]
+    fn test_empty_or_unsigned_flush_is_not_trusted() {
+        let signer = FlushSigner::new("test-key", b"secret".to_vec());
+        let report = AbortAndFlush::new(signer.clone()).trigger("nothing registered");
+        assert!(report.participants.is_empty());
+        assert!(!report.all_complied());
+
+        let mut flush = AbortAndFlush::unsigned();
+        flush.register(Arc::new(Mutex::new(Buffer {
+            name: "buffer",
+            items: vec![1],
+            delay: Duration::ZERO,
+        })));
+        let report = flush.trigger("no key configured");
+        assert!(report.all_complied());
+        assert_eq!(report.key_id, UNSIGNED_KEY_ID);
+        assert!(report.is_unsigned());
+        assert!(!report.verify(&signer));
+        assert!(!report.verify(&FlushSigner::new(UNSIGNED_KEY_ID, Vec::new())));
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::governance::{GovernanceMode, GovernanceStateV1};
+
+/// Audit snapshot of GovernanceStateV1, using the ALN-style field names
+/// from SpectralVision.md §3 so it can be logged and replayed as-is.
+///
+/// Conversion to and from GovernanceStateV1 is lossless, so XR routing and
+/// excavation depth always read the same source of truth.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SpectralGovernanceAudit {
+    pub spectralroamingactive: bool,
+    pub noninterferencerequired: bool,
+    pub spectralquantificationactive: bool,
+    pub soulmodelingforbidden: bool,
+    pub channelactive: bool,
+    /// Binding strength in [0,1].
+    pub bindingstrength: f32,
+    pub mode: GovernanceMode,
+}
+
+impl SpectralGovernanceAudit {
+    /// True if the constitutional bits allow any spectral-vision at all.
+    pub fn allows_spectral_vision(&self) -> bool {
+        self.spectralquantificationactive && self.soulmodelingforbidden
+    }
+
+    /// True if roaming + non-interference force observe-only behaviour.
+    pub fn requires_observe_only(&self) -> bool {
+        self.spectralroamingactive && self.noninterferencerequired
+    }
+
+    /// Rebuild the governance state this snapshot was taken from.
+    pub fn to_state(&self) -> GovernanceStateV1 {
+        GovernanceStateV1::from(self)
+    }
+}
+
+impl From<&GovernanceStateV1> for SpectralGovernanceAudit {
+    fn from(state: &GovernanceStateV1) -> Self {
+        Self {
+            spectralroamingactive: state.spectral_roaming_active,
+            noninterferencerequired: state.non_interference_required,
+            spectralquantificationactive: state.spectral_quantification_active,
+            soulmodelingforbidden: state.soul_modeling_forbidden,
+            channelactive: state.channel_active,
+            bindingstrength: state.binding_strength,
+            mode: state.mode,
+        }
+    }
+}
+
+impl From<GovernanceStateV1> for SpectralGovernanceAudit {
+    fn from(state: GovernanceStateV1) -> Self {
+        Self::from(&state)
+    }
+}
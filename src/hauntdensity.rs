@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// HauntDensity zone bands over Hnorm ∈ [0,1].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum HauntZone {
+    /// Hnorm ∈ [0.0, 0.2)
+    Control,
+    /// Hnorm ∈ [0.2, 0.5)
+    Monitored,
+    /// Hnorm ∈ [0.5, 0.8)
+    Restricted,
+    /// Hnorm ∈ [0.8, 1.0]
+    Containment,
+}
+
+impl HauntZone {
+    /// Classify a normalized haunt-density value; non-finite input maps to Control.
+    pub fn from_hnorm(hnorm: f64) -> Self {
+        let h = if hnorm.is_finite() { hnorm.clamp(0.0, 1.0) } else { 0.0 };
+        if h < 0.2 {
+            Self::Control
+        } else if h < 0.5 {
+            Self::Monitored
+        } else if h < 0.8 {
+            Self::Restricted
+        } else {
+            Self::Containment
+        }
+    }
+}
+
+/// Normalized haunt-density score for one region-session window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HauntDensityScore {
+    /// Hnorm ∈ [0,1].
+    pub hnorm: f64,
+    /// Zone derived from hnorm.
+    pub zone: HauntZone,
+}
+
+impl HauntDensityScore {
+    /// Build a score from raw Hnorm, clamping and deriving the zone.
+    pub fn new(hnorm: f64) -> Self {
+        let hnorm = if hnorm.is_finite() { hnorm.clamp(0.0, 1.0) } else { 0.0 };
+        Self {
+            hnorm,
+            zone: HauntZone::from_hnorm(hnorm),
+        }
+    }
+}
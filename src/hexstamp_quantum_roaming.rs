@@ -0,0 +1,271 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::governance::spectral_governance_audit::SpectralGovernanceAudit;
+use crate::governance::stable_mix64;
+use crate::hauntdensity::HauntZone;
+use crate::hexstamp_spectral_vision::{governance_bits, quantize_u16};
+
+/// Stamp name, also mixed into every stamp as a domain separator.
+pub const HEXSTAMP_QUANTUM_ROAMING_V1: &str = "HEXSTAMP_QUANTUM_ROAMING_V1";
+
+/// XR zones, mirroring HauntZone bands.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum XRZone {
+    XRCONTROL,
+    XRMONITORED,
+    XRRESTRICTED,
+    XRCONTAINMENT,
+}
+
+impl XRZone {
+    /// Stable wire code used in stamps.
+    pub fn code(&self) -> u8 {
+        match self {
+            XRZone::XRCONTROL => 0,
+            XRZone::XRMONITORED => 1,
+            XRZone::XRRESTRICTED => 2,
+            XRZone::XRCONTAINMENT => 3,
+        }
+    }
+}
+
+impl From<HauntZone> for XRZone {
+    fn from(zone: HauntZone) -> Self {
+        match zone {
+            HauntZone::Control => XRZone::XRCONTROL,
+            HauntZone::Monitored => XRZone::XRMONITORED,
+            HauntZone::Restricted => XRZone::XRRESTRICTED,
+            HauntZone::Containment => XRZone::XRCONTAINMENT,
+        }
+    }
+}
+
+/// HEXSTAMP_QUANTUM_ROAMING_V1: telemetry-only roaming stamp keyed by
+/// location and time bucket. Never used for person scoring or soul inference.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct HexStampQuantumRoamingV1 {
+    pub location_bucket: u64,
+    pub time_bucket: u64,
+    pub xr_zone: XRZone,
+    /// Packed governance bits at stamping time.
+    pub gov_bits: u16,
+    /// 16 lowercase hex chars.
+    pub stamp: String,
+}
+
+impl HexStampQuantumRoamingV1 {
+    /// Compute the stamp unconditionally. Prefer `maybe_stamp_for_roaming`,
+    /// which applies the governance gate and audit-only wrapping.
+    ///
+    /// Mixer input = name || location_bucket (u64 BE) || time_bucket (u64 BE)
+    /// || gov_bits (u16 BE) || zone code (u8) || hnorm (u16 BE).
+    pub fn compute(
+        gov: &SpectralGovernanceAudit,
+        xr_zone: XRZone,
+        hnorm: f64,
+        location_bucket: u64,
+        time_bucket: u64,
+    ) -> Self {
+        let gov_bits = governance_bits(gov);
+
+        let mut input = Vec::with_capacity(HEXSTAMP_QUANTUM_ROAMING_V1.len() + 21);
+        input.extend_from_slice(HEXSTAMP_QUANTUM_ROAMING_V1.as_bytes());
+        input.extend_from_slice(&location_bucket.to_be_bytes());
+        input.extend_from_slice(&time_bucket.to_be_bytes());
+        input.extend_from_slice(&gov_bits.to_be_bytes());
+        input.push(xr_zone.code());
+        input.extend_from_slice(&quantize_u16(hnorm).to_be_bytes());
+
+        Self {
+            location_bucket,
+            time_bucket,
+            xr_zone,
+            gov_bits,
+            stamp: format!("{:016x}", stable_mix64(&input)),
+        }
+    }
+}
+
+impl fmt::Display for HexStampQuantumRoamingV1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.stamp)
+    }
+}
+
+/// Stamp issued under non-interference: readable for audit logging only.
+///
+/// There is deliberately no conversion into `SteerableStamp`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AuditOnlyStamp(HexStampQuantumRoamingV1);
+
+impl AuditOnlyStamp {
+    /// Stamp contents for audit records.
+    pub fn for_audit(&self) -> &HexStampQuantumRoamingV1 {
+        &self.0
+    }
+}
+
+/// Stamp issued when governance permits steering.
+///
+/// Only constructible inside this module, so any steering API taking
+/// `&SteerableStamp` is statically unreachable under non-interference.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SteerableStamp(HexStampQuantumRoamingV1);
+
+impl SteerableStamp {
+    /// Stamp contents, for steering or audit.
+    pub fn stamp(&self) -> &HexStampQuantumRoamingV1 {
+        &self.0
+    }
+}
+
+/// Governed roaming stamp as returned by `maybe_stamp_for_roaming`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub enum RoamingStamp {
+    /// Non-interference required: log for audit, never steer.
+    AuditOnly(AuditOnlyStamp),
+    /// Steering permitted by governance.
+    Steerable(SteerableStamp),
+}
+
+impl RoamingStamp {
+    fn wrap(stamp: HexStampQuantumRoamingV1, gov: &SpectralGovernanceAudit) -> Self {
+        if gov.noninterferencerequired {
+            RoamingStamp::AuditOnly(AuditOnlyStamp(stamp))
+        } else {
+            RoamingStamp::Steerable(SteerableStamp(stamp))
+        }
+    }
+
+    /// Stamp contents for audit records, regardless of variant.
+    pub fn for_audit(&self) -> &HexStampQuantumRoamingV1 {
+        match self {
+            RoamingStamp::AuditOnly(s) => s.for_audit(),
+            RoamingStamp::Steerable(s) => s.stamp(),
+        }
+    }
+
+    /// Steering handle, if governance permits steering.
+    pub fn steerable(&self) -> Option<&SteerableStamp> {
+        match self {
+            RoamingStamp::AuditOnly(_) => None,
+            RoamingStamp::Steerable(s) => Some(s),
+        }
+    }
+
+    /// Re-apply a (possibly newer) governance snapshot. A stamp can only be
+    /// downgraded to audit-only here, never upgraded to steerable; if that
+    /// snapshot no longer allows spectral vision or roaming, the stamp is
+    /// refused (None) exactly as [`maybe_stamp_for_roaming`] would.
+    pub fn constrain_to(self, gov: &SpectralGovernanceAudit) -> Option<Self> {
+        if !gov.allows_spectral_vision() || !gov.spectralroamingactive {
+            return None;
+        }
+        Some(match self {
+            RoamingStamp::Steerable(SteerableStamp(s)) if gov.noninterferencerequired => {
+                RoamingStamp::AuditOnly(AuditOnlyStamp(s))
+            }
+            other => other,
+        })
+    }
+}
+
+/// Return a roaming stamp only when governance allows it.
+///
+/// - spectralquantificationactive false or soulmodelingforbidden false → None
+///   (caller should AbortAndFlush upstream).
+/// - spectralroamingactive false → None (nothing to stamp).
+/// - noninterferencerequired → `RoamingStamp::AuditOnly`.
+/// - otherwise → `RoamingStamp::Steerable`.
+pub fn maybe_stamp_for_roaming(
+    gov: &SpectralGovernanceAudit,
+    xr_zone: XRZone,
+    hnorm: f64,
+    location_bucket: u64,
+    time_bucket: u64,
+) -> Option<RoamingStamp> {
+    if !gov.allows_spectral_vision() || !gov.spectralroamingactive {
+        return None;
+    }
+    let stamp = HexStampQuantumRoamingV1::compute(gov, xr_zone, hnorm, location_bucket, time_bucket);
+    Some(RoamingStamp::wrap(stamp, gov))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::GovernanceMode;
+
+    fn gov(non_interference: bool) -> SpectralGovernanceAudit {
+        SpectralGovernanceAudit {
+            spectralroamingactive: true,
+            noninterferencerequired: non_interference,
+            spectralquantificationactive: true,
+            soulmodelingforbidden: true,
+            channelactive: true,
+            bindingstrength: 0.5,
+            mode: if non_interference {
+                GovernanceMode::ActiveFree
+            } else {
+                GovernanceMode::ActiveGoverned
+            },
+        }
+    }
+
+    #[test]
+    fn test_non_interference_yields_audit_only() {
+        let stamp = maybe_stamp_for_roaming(&gov(true), XRZone::XRMONITORED, 0.3, 7, 9).unwrap();
+        assert!(matches!(stamp, RoamingStamp::AuditOnly(_)));
+        assert!(stamp.steerable().is_none());
+        assert_eq!(stamp.for_audit().stamp.len(), 16);
+    }
+
+    #[test]
+    fn test_governed_yields_steerable_until_constrained() {
+        let stamp = maybe_stamp_for_roaming(&gov(false), XRZone::XRCONTROL, 0.1, 7, 9).unwrap();
+        assert!(stamp.steerable().is_some());
+
+        let constrained = stamp.clone().constrain_to(&gov(true)).unwrap();
+        assert!(constrained.steerable().is_none());
+        assert_eq!(constrained.for_audit(), stamp.for_audit());
+    }
+
+    #[test]
+    fn test_constrain_to_revoked_governance_refuses_stamp() {
+        let stamp = maybe_stamp_for_roaming(&gov(false), XRZone::XRCONTROL, 0.1, 7, 9).unwrap();
+
+        let mut g = gov(false);
+        g.soulmodelingforbidden = false;
+        assert!(stamp.clone().constrain_to(&g).is_none());
+
+        let mut g = gov(false);
+        g.spectralquantificationactive = false;
+        assert!(stamp.clone().constrain_to(&g).is_none());
+
+        let mut g = gov(false);
+        g.spectralroamingactive = false;
+        assert!(stamp.constrain_to(&g).is_none());
+    }
+
+    #[test]
+    fn test_forbidden_governance_yields_none() {
+        let mut g = gov(false);
+        g.soulmodelingforbidden = false;
+        assert!(maybe_stamp_for_roaming(&g, XRZone::XRCONTROL, 0.1, 7, 9).is_none());
+
+        let mut g = gov(false);
+        g.spectralroamingactive = false;
+        assert!(maybe_stamp_for_roaming(&g, XRZone::XRCONTROL, 0.1, 7, 9).is_none());
+    }
+
+    #[test]
+    fn test_xrzone_mirrors_haunt_zone() {
+        assert_eq!(XRZone::from(HauntZone::from_hnorm(0.1)), XRZone::XRCONTROL);
+        assert_eq!(XRZone::from(HauntZone::from_hnorm(0.3)), XRZone::XRMONITORED);
+        assert_eq!(XRZone::from(HauntZone::from_hnorm(0.6)), XRZone::XRRESTRICTED);
+        assert_eq!(XRZone::from(HauntZone::from_hnorm(0.9)), XRZone::XRCONTAINMENT);
+    }
+}
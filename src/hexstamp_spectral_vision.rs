@@ -0,0 +1,262 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::governance::spectral_governance_audit::SpectralGovernanceAudit;
+use crate::governance::{stable_mix64, GovernanceMode};
+use crate::spectral_vision::{clamp01, SpectralVisionDecision};
+use crate::xr_grid_core::XRGridState;
+
+/// Stamp name, also mixed into every stamp as a domain separator.
+pub const HEXSTAMP_SPECTRAL_VISION_V1: &str = "HEXSTAMP_SPECTRAL_VISION_V1";
+
+/// Number of quantized channels following the governance bits.
+pub const FEATURE_CHANNELS: usize = 9;
+
+/// Bounded feature vector for HEXSTAMP_SPECTRAL_VISION_V1 (SpectralVision.md §6).
+///
+/// Telemetry-only: never used for person scoring or soul inference. Under
+/// roaming non-interference the stamp may be logged for audit but must not
+/// steer XR or behaviour.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SpectralVisionFeatureVector {
+    /// Packed governance bits, see `governance_bits`.
+    pub gov_bits: u16,
+    /// Sleep indices in [0,1].
+    pub in1_index: f64,
+    pub in2n3_index: f64,
+    pub unknown_mass: f64,
+    /// HauntDensity Hnorm in [0,1].
+    pub hnorm: f64,
+    /// Band-safety min/max in [0,1].
+    pub safety_min: f64,
+    pub safety_max: f64,
+    /// Spectral-psych channels in [0,1].
+    pub fear_level: f64,
+    pub fear_rate_norm: f64,
+    pub psych_load: f64,
+}
+
+/// HEXSTAMP_SPECTRAL_VISION_V1 value with the buckets it is keyed by.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct HexStampSpectralVisionV1 {
+    pub location_bucket: u64,
+    pub time_bucket: u64,
+    /// 16 lowercase hex chars.
+    pub stamp: String,
+}
+
+impl fmt::Display for HexStampSpectralVisionV1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.stamp)
+    }
+}
+
+/// Pack governance flags and mode into 16 bits.
+///
+/// bit 0 spectralroamingactive, bit 1 noninterferencerequired,
+/// bit 2 spectralquantificationactive, bit 3 soulmodelingforbidden,
+/// bit 4 channelactive, bits 8..=9 mode
+/// (ActiveFree=0, ActiveGoverned=1, Dormant=2, TechnicalOnly=3).
+pub fn governance_bits(gov: &SpectralGovernanceAudit) -> u16 {
+    let mut bits = 0u16;
+    if gov.spectralroamingactive {
+        bits |= 1 << 0;
+    }
+    if gov.noninterferencerequired {
+        bits |= 1 << 1;
+    }
+    if gov.spectralquantificationactive {
+        bits |= 1 << 2;
+    }
+    if gov.soulmodelingforbidden {
+        bits |= 1 << 3;
+    }
+    if gov.channelactive {
+        bits |= 1 << 4;
+    }
+    let mode: u16 = match gov.mode {
+        GovernanceMode::ActiveFree => 0,
+        GovernanceMode::ActiveGoverned => 1,
+        GovernanceMode::Dormant => 2,
+        GovernanceMode::TechnicalOnly => 3,
+    };
+    bits | (mode << 8)
+}
+
+/// Quantize a [0,1] value to u16, rounding half away from zero.
+pub(crate) fn quantize_u16(x: f64) -> u16 {
+    (clamp01(x) * f64::from(u16::MAX)).round() as u16
+}
+
+impl SpectralVisionFeatureVector {
+    /// Build the feature vector from a spectral-vision decision and XR grid state.
+    /// Sleep indices come from `state.sleep` and default to 0 when no sleep
+    /// channel is bound; `with_sleep_indices` overrides them.
+    pub fn from_decision(decision: &SpectralVisionDecision, state: &XRGridState) -> Self {
+        let bands = &decision.band_profile.bands;
+        let safety_max = bands
+            .iter()
+            .map(|b| b.safety_score)
+            .fold(None, |acc: Option<f64>, s| Some(acc.map_or(s, |m| m.max(s))))
+            .unwrap_or(0.0);
+
+        let sleep = state.sleep.clone().unwrap_or_default();
+
+        Self {
+            gov_bits: governance_bits(&state.gov),
+            in1_index: sleep.in1_index,
+            in2n3_index: sleep.in2n3_index,
+            unknown_mass: sleep.unknown_mass,
+            hnorm: state.haunt.as_ref().map(|h| h.hnorm).unwrap_or(0.0),
+            safety_min: decision.band_profile.safety_min,
+            safety_max,
+            fear_level: f64::from(state.fear_level),
+            fear_rate_norm: f64::from(state.fear_rate_norm),
+            psych_load: f64::from(state.psych_load),
+        }
+    }
+
+    /// Attach sleep indices (iN1, iN2N3, unknown mass).
+    pub fn with_sleep_indices(mut self, in1_index: f64, in2n3_index: f64, unknown_mass: f64) -> Self {
+        self.in1_index = in1_index;
+        self.in2n3_index = in2n3_index;
+        self.unknown_mass = unknown_mass;
+        self
+    }
+
+    /// Clamped, u16-quantized channels in serialization order.
+    pub fn quantize(&self) -> [u16; FEATURE_CHANNELS] {
+        [
+            quantize_u16(self.in1_index),
+            quantize_u16(self.in2n3_index),
+            quantize_u16(self.unknown_mass),
+            quantize_u16(self.hnorm),
+            quantize_u16(self.safety_min),
+            quantize_u16(self.safety_max),
+            quantize_u16(self.fear_level),
+            quantize_u16(self.fear_rate_norm),
+            quantize_u16(self.psych_load),
+        ]
+    }
+
+    /// Big-endian payload: gov_bits followed by the quantized channels.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(2 * (FEATURE_CHANNELS + 1));
+        out.extend_from_slice(&self.gov_bits.to_be_bytes());
+        for q in self.quantize() {
+            out.extend_from_slice(&q.to_be_bytes());
+        }
+        out
+    }
+}
+
+/// Compute HEXSTAMP_SPECTRAL_VISION_V1 for a feature vector.
+///
+/// Mixer input = name || location_bucket (u64 BE) || time_bucket (u64 BE) || payload.
+pub fn hexstamp_spectral_vision_v1(
+    features: &SpectralVisionFeatureVector,
+    location_bucket: u64,
+    time_bucket: u64,
+) -> HexStampSpectralVisionV1 {
+    let mut input = Vec::with_capacity(HEXSTAMP_SPECTRAL_VISION_V1.len() + 16 + 20);
+    input.extend_from_slice(HEXSTAMP_SPECTRAL_VISION_V1.as_bytes());
+    input.extend_from_slice(&location_bucket.to_be_bytes());
+    input.extend_from_slice(&time_bucket.to_be_bytes());
+    input.extend_from_slice(&features.to_be_bytes());
+
+    HexStampSpectralVisionV1 {
+        location_bucket,
+        time_bucket,
+        stamp: format!("{:016x}", stable_mix64(&input)),
+    }
+}
+
+/// Convenience: stamp a decision keyed by the state's region-session buckets.
+pub fn stamp_spectral_vision(
+    decision: &SpectralVisionDecision,
+    state: &XRGridState,
+) -> HexStampSpectralVisionV1 {
+    let features = SpectralVisionFeatureVector::from_decision(decision, state);
+    hexstamp_spectral_vision_v1(
+        &features,
+        state.region_session.location_bucket(),
+        state.region_session.time_bucket(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Published test vectors (SpectralVision.md §6).
+    const MIX_EMPTY: u64 = 0xf52a_15e9_a9b5_e89b;
+    const MIX_NAME: u64 = 0x5a76_e9bf_11e9_ccab;
+    const VECTOR_ZERO: &str = "9f7728eaafdd041c";
+    const VECTOR_SAMPLE: &str = "5ba93c9fbfc80a24";
+
+    fn vector() -> SpectralVisionFeatureVector {
+        SpectralVisionFeatureVector {
+            gov_bits: 0x010d,
+            in1_index: 0.25,
+            in2n3_index: 0.5,
+            unknown_mass: 0.1,
+            hnorm: 0.3,
+            safety_min: 0.45,
+            safety_max: 0.9,
+            fear_level: 0.2,
+            fear_rate_norm: 0.05,
+            psych_load: 0.35,
+        }
+    }
+
+    #[test]
+    fn test_mixer_vectors() {
+        assert_eq!(stable_mix64(b""), MIX_EMPTY);
+        assert_eq!(stable_mix64(HEXSTAMP_SPECTRAL_VISION_V1.as_bytes()), MIX_NAME);
+    }
+
+    #[test]
+    fn test_quantization_clamps() {
+        let mut v = vector();
+        v.hnorm = f64::NAN;
+        v.fear_level = 2.0;
+        v.psych_load = -1.0;
+        let q = v.quantize();
+        assert_eq!(q[3], 0);
+        assert_eq!(q[6], u16::MAX);
+        assert_eq!(q[8], 0);
+        assert_eq!(q[0], 16384);
+        assert_eq!(vector().quantize(), [16384, 32768, 6554, 19661, 29491, 58982, 13107, 3277, 22937]);
+    }
+
+    #[test]
+    fn test_published_vectors() {
+        let zero = SpectralVisionFeatureVector {
+            gov_bits: 0,
+            in1_index: 0.0,
+            in2n3_index: 0.0,
+            unknown_mass: 0.0,
+            hnorm: 0.0,
+            safety_min: 0.0,
+            safety_max: 0.0,
+            fear_level: 0.0,
+            fear_rate_norm: 0.0,
+            psych_load: 0.0,
+        };
+        assert_eq!(hexstamp_spectral_vision_v1(&zero, 0, 0).stamp, VECTOR_ZERO);
+        assert_eq!(hexstamp_spectral_vision_v1(&vector(), 42, 5_000_000).stamp, VECTOR_SAMPLE);
+    }
+
+    #[test]
+    fn test_keyed_by_buckets() {
+        let v = vector();
+        let a = hexstamp_spectral_vision_v1(&v, 1, 1);
+        let b = hexstamp_spectral_vision_v1(&v, 1, 2);
+        let c = hexstamp_spectral_vision_v1(&v, 2, 1);
+        assert_eq!(a.stamp.len(), 16);
+        assert_ne!(a.stamp, b.stamp);
+        assert_ne!(a.stamp, c.stamp);
+        assert_eq!(a, hexstamp_spectral_vision_v1(&v, 1, 1));
+    }
+}
@@ -0,0 +1,330 @@
+//! Sleep-state channel (SpectralVision.md §4.2) and the G_safe gate.
+//!
+//! Indices are momentary, epoch-level safety inputs; they must never be
+//! aggregated into person-level profiles or used for ranking.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::spectral_vision::clamp01;
+
+pub mod calibration;
+pub mod edf;
+pub mod hypnogram;
+
+/// Stage order for posterior vectors.
+pub const SLEEP_STAGES: [&str; 5] = ["W", "N1", "N2", "N3", "REM"];
+
+/// Errors importing hypnograms/EDF files or loading calibrations.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum SleepDataError {
+    #[error("cannot read {path}: {message}")]
+    Io { path: String, message: String },
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+    #[error("invalid JSON: {0}")]
+    Json(String),
+    #[error("invalid EDF: {0}")]
+    Edf(String),
+    #[error("calibration format version {found} is not supported (expected {expected})")]
+    UnsupportedVersion { found: u32, expected: u32 },
+    #[error("no computed epoch overlaps a scored hypnogram epoch")]
+    NoAlignedEpochs,
+}
+
+impl SleepDataError {
+    pub(crate) fn io(path: &std::path::Path, e: std::io::Error) -> Self {
+        SleepDataError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// AASM sleep stage as scored by an expert or predicted from posteriors.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SleepStage {
+    Wake,
+    N1,
+    N2,
+    N3,
+    Rem,
+    /// Movement, artifact or not scored; excluded from calibration.
+    Unscored,
+}
+
+impl SleepStage {
+    /// Scored stages in [`SLEEP_STAGES`] order.
+    pub const SCORED: [SleepStage; 5] = [
+        SleepStage::Wake,
+        SleepStage::N1,
+        SleepStage::N2,
+        SleepStage::N3,
+        SleepStage::Rem,
+    ];
+
+    /// Parse a hypnogram label: AASM (`W`, `N1`..`N3`, `R`/`REM`), R&K
+    /// (`S1`..`S4`, with S4 folded into N3) or Sleep-EDF annotations
+    /// (`Sleep stage 2`, `Movement time`). Unknown labels are None.
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim();
+        let label = label
+            .strip_prefix("Sleep stage ")
+            .or_else(|| label.strip_prefix("Sleep_stage_"))
+            .unwrap_or(label);
+        let stage = match label.to_ascii_uppercase().as_str() {
+            "W" | "WAKE" | "0" => Self::Wake,
+            "N1" | "S1" | "1" => Self::N1,
+            "N2" | "S2" | "2" => Self::N2,
+            "N3" | "N4" | "S3" | "S4" | "3" | "4" => Self::N3,
+            "R" | "REM" | "5" => Self::Rem,
+            "?" | "U" | "UNSCORED" | "MOVEMENT TIME" | "MT" | "ARTIFACT" => Self::Unscored,
+            _ => return None,
+        };
+        Some(stage)
+    }
+
+    /// Most probable stage of a posterior vector in [`SLEEP_STAGES`] order.
+    pub fn from_posteriors(posteriors: &[f64; 5]) -> Self {
+        let mut best = 0;
+        for (i, p) in posteriors.iter().enumerate() {
+            if *p > posteriors[best] {
+                best = i;
+            }
+        }
+        Self::SCORED[best]
+    }
+
+    /// Position in [`SLEEP_STAGES`]; None for Unscored.
+    pub fn index(self) -> Option<usize> {
+        Self::SCORED.iter().position(|s| *s == self)
+    }
+
+    /// Label as in [`SLEEP_STAGES`].
+    pub fn as_str(self) -> &'static str {
+        self.index().map_or("?", |i| SLEEP_STAGES[i])
+    }
+}
+
+/// One epoch of the sleep channel: `in1index`, `in2n3index`, `unknownmass`, `ucomb`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SleepStateSnapshot {
+    /// N1 (transitional) index ∈ [0,1].
+    #[serde(rename = "in1index", alias = "in1_index")]
+    pub in1_index: f64,
+    /// N2–N3 depth index ∈ [0,1]; 1.0 is physiologically deep N3.
+    #[serde(rename = "in2n3index", alias = "in2n3_index")]
+    pub in2n3_index: f64,
+    /// Posterior mass not assigned to any stage (artifact, off-head) ∈ [0,1].
+    #[serde(rename = "unknownmass", alias = "unknown_mass")]
+    pub unknown_mass: f64,
+    /// Combined uncertainty ucomb = (U_gap + U_ent) / 2 ∈ [0,1].
+    #[serde(rename = "ucomb", alias = "u_comb")]
+    pub u_comb: f64,
+    /// Micro-arousal detected in this epoch; vetoes the gate.
+    #[serde(default)]
+    pub microarousal: bool,
+}
+
+impl SleepStateSnapshot {
+    /// Snapshot from precomputed indices.
+    pub fn new(in1_index: f64, in2n3_index: f64, unknown_mass: f64, u_comb: f64) -> Self {
+        Self {
+            in1_index: clamp01(in1_index),
+            in2n3_index: clamp01(in2n3_index),
+            unknown_mass: clamp01(unknown_mass),
+            u_comb: clamp01(u_comb),
+            microarousal: false,
+        }
+    }
+
+    /// Derive indices from stage posteriors in [`SLEEP_STAGES`] order.
+    ///
+    /// Posteriors are renormalized; an all-zero vector is treated as uniform.
+    ///   iN1    = p_N1
+    ///   iN2N3  = 0.5 · p_N2 + p_N3
+    ///   U_gap  = 1 − max_k p_k
+    ///   U_ent  = −Σ p_k ln p_k / ln 5
+    pub fn from_posteriors(posteriors: [f64; 5], unknown_mass: f64) -> Self {
+        let mut p = posteriors.map(|x| if x.is_finite() { x.max(0.0) } else { 0.0 });
+        let sum: f64 = p.iter().sum();
+        if sum > 0.0 {
+            p.iter_mut().for_each(|x| *x /= sum);
+        } else {
+            p = [0.2; 5];
+        }
+
+        let u_gap = 1.0 - p.iter().cloned().fold(0.0, f64::max);
+        let u_ent = -p
+            .iter()
+            .filter(|x| **x > 0.0)
+            .map(|x| x * x.ln())
+            .sum::<f64>()
+            / (p.len() as f64).ln();
+
+        Self::new(p[1], 0.5 * p[2] + p[3], unknown_mass, (u_gap + u_ent) / 2.0)
+    }
+
+    /// Mark a micro-arousal in this epoch.
+    pub fn with_microarousal(mut self, microarousal: bool) -> Self {
+        self.microarousal = microarousal;
+        self
+    }
+
+    /// G_safe = min(1, iN2N3 · (1 − ucomb)).
+    pub fn g_safe(&self) -> f64 {
+        clamp01(self.in2n3_index * (1.0 - clamp01(self.u_comb)))
+    }
+
+    /// Evaluate the G_safe gate.
+    pub fn gate(&self, params: &SleepGateParams) -> SleepGate {
+        let g_safe = self.g_safe();
+        let mut reasons = Vec::new();
+        if g_safe < params.gsafe_min {
+            reasons.push(format!(
+                "G_safe {:.3} below {:.3}",
+                g_safe, params.gsafe_min
+            ));
+        }
+        if self.u_comb > params.ucomb_max {
+            reasons.push(format!(
+                "ucomb {:.3} above {:.3}",
+                self.u_comb, params.ucomb_max
+            ));
+        }
+        if self.unknown_mass > params.unknown_mass_max {
+            reasons.push(format!(
+                "unknownmass {:.3} above {:.3}",
+                self.unknown_mass, params.unknown_mass_max
+            ));
+        }
+        if self.in1_index > params.in1_max {
+            reasons.push(format!(
+                "iN1 {:.3} above {:.3}",
+                self.in1_index, params.in1_max
+            ));
+        }
+        if self.microarousal {
+            reasons.push("micro-arousal veto".to_string());
+        }
+
+        SleepGate {
+            g_safe,
+            u_comb: self.u_comb,
+            open: reasons.is_empty(),
+            reasons,
+        }
+    }
+}
+
+/// G_safe gate thresholds; omitted fields take their defaults.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SleepGateParams {
+    /// Minimum G_safe for deep excavation / full XR interaction.
+    pub gsafe_min: f64,
+    /// Maximum combined uncertainty.
+    pub ucomb_max: f64,
+    /// Maximum unassigned posterior mass.
+    pub unknown_mass_max: f64,
+    /// Maximum N1 index (transition towards waking).
+    pub in1_max: f64,
+}
+
+impl Default for SleepGateParams {
+    fn default() -> Self {
+        Self {
+            gsafe_min: 0.5,
+            ucomb_max: 0.5,
+            unknown_mass_max: 0.3,
+            in1_max: 0.5,
+        }
+    }
+}
+
+impl SleepGateParams {
+    /// Gate thresholds from a calibration file written by [`calibration::calibrate`].
+    pub fn load_calibrated(path: impl AsRef<std::path::Path>) -> Result<Self, SleepDataError> {
+        Ok(calibration::SleepCalibration::load(path)?.gate)
+    }
+}
+
+/// Result of the G_safe gate for one epoch.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SleepGate {
+    pub g_safe: f64,
+    pub u_comb: f64,
+    /// True if deep excavation and full XR interaction may proceed.
+    pub open: bool,
+    /// Why the gate is closed; empty when open.
+    pub reasons: Vec<String>,
+}
+
+impl SleepGate {
+    /// Reasons joined for violation details.
+    pub fn describe(&self) -> String {
+        self.reasons.join("; ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_posteriors_to_indices() {
+        let deep = SleepStateSnapshot::from_posteriors([0.0, 0.0, 0.0, 1.0, 0.0], 0.0);
+        assert_eq!(deep.in2n3_index, 1.0);
+        assert_eq!(deep.u_comb, 0.0);
+        assert_eq!(deep.g_safe(), 1.0);
+
+        let uniform = SleepStateSnapshot::from_posteriors([0.0; 5], 0.0);
+        assert!((uniform.u_comb - 0.9).abs() < 1e-12);
+        assert!((uniform.in1_index - 0.2).abs() < 1e-12);
+
+        // Unnormalized input is rescaled.
+        let n2 = SleepStateSnapshot::from_posteriors([0.0, 0.0, 4.0, 4.0, 0.0], 0.0);
+        assert!((n2.in2n3_index - 0.75).abs() < 1e-12);
+        assert!((n2.g_safe() - 0.75 * (1.0 - n2.u_comb)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gate_closes_on_each_condition() {
+        let params = SleepGateParams::default();
+        let deep = SleepStateSnapshot::new(0.05, 0.9, 0.0, 0.1);
+        assert!(deep.gate(&params).open);
+
+        let uncertain = SleepStateSnapshot::new(0.05, 1.0, 0.0, 0.6);
+        let gate = uncertain.gate(&params);
+        assert!(!gate.open);
+        assert_eq!(gate.reasons.len(), 2);
+        assert!(gate.describe().contains("ucomb 0.600"));
+
+        let artifact = SleepStateSnapshot::new(0.05, 0.9, 0.5, 0.1);
+        assert!(!artifact.gate(&params).open);
+        let waking = SleepStateSnapshot::new(0.6, 0.9, 0.0, 0.1);
+        assert!(!waking.gate(&params).open);
+        let gate = deep.with_microarousal(true).gate(&params);
+        assert_eq!(gate.reasons, ["micro-arousal veto"]);
+    }
+    #[test]
+    fn test_snapshot_wire_names() {
+        let snap = SleepStateSnapshot::new(0.25, 0.5, 0.0, 0.125);
+        let json = serde_json::to_value(&snap).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "in1index": 0.25,
+                "in2n3index": 0.5,
+                "unknownmass": 0.0,
+                "ucomb": 0.125,
+                "microarousal": false,
+            })
+        );
+        let legacy: SleepStateSnapshot = serde_json::from_str(
+            r#"{"in1_index": 0.25, "in2n3_index": 0.5, "unknown_mass": 0.0, "u_comb": 0.125}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy, snap);
+    }
+}
@@ -0,0 +1,401 @@
+//! PSG-anchored calibration of the sleep indices and the G_safe gate.
+//!
+//! Expert hypnogram epochs are aligned with computed stage posteriors to
+//! produce a per-stage confusion matrix, Cohen's kappa and a G_safe
+//! threshold curve, written as a versioned calibration file.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::sleep_state::hypnogram::Hypnogram;
+use crate::sleep_state::{
+    SleepDataError, SleepGateParams, SleepStage, SleepStateSnapshot, SLEEP_STAGES,
+};
+
+/// Calibration file format version.
+pub const CALIBRATION_FORMAT_VERSION: u32 = 1;
+
+/// Stage posteriors computed for one epoch.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ComputedEpoch {
+    /// Seconds from recording start.
+    pub onset_secs: f64,
+    /// Posteriors in [`SLEEP_STAGES`] order.
+    pub posteriors: [f64; 5],
+    #[serde(default)]
+    pub unknown_mass: f64,
+    #[serde(default)]
+    pub microarousal: bool,
+}
+
+impl ComputedEpoch {
+    pub fn snapshot(&self) -> SleepStateSnapshot {
+        SleepStateSnapshot::from_posteriors(self.posteriors, self.unknown_mass)
+            .with_microarousal(self.microarousal)
+    }
+}
+
+/// A computed epoch paired with its expert label.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AlignedEpoch {
+    pub onset_secs: f64,
+    pub expert: SleepStage,
+    pub predicted: SleepStage,
+    pub snapshot: SleepStateSnapshot,
+}
+
+/// Pair each computed epoch with the expert epoch containing its midpoint.
+///
+/// Computed epochs outside the hypnogram or on Unscored expert epochs are dropped.
+pub fn align_epochs(hypnogram: &Hypnogram, computed: &[ComputedEpoch]) -> Vec<AlignedEpoch> {
+    computed
+        .iter()
+        .filter_map(|c| {
+            let expert = hypnogram.stage_at(c.onset_secs + hypnogram.epoch_secs / 2.0)?;
+            expert.index()?;
+            Some(AlignedEpoch {
+                onset_secs: c.onset_secs,
+                expert,
+                predicted: SleepStage::from_posteriors(&c.posteriors),
+                snapshot: c.snapshot(),
+            })
+        })
+        .collect()
+}
+
+/// Expert (rows) × predicted (columns) counts in [`SLEEP_STAGES`] order.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    pub counts: [[u64; 5]; 5],
+}
+
+impl ConfusionMatrix {
+    pub fn from_aligned(epochs: &[AlignedEpoch]) -> Self {
+        let mut m = Self::default();
+        for e in epochs {
+            if let (Some(r), Some(c)) = (e.expert.index(), e.predicted.index()) {
+                m.counts[r][c] += 1;
+            }
+        }
+        m
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Fraction of epochs on the diagonal.
+    pub fn accuracy(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        (0..5).map(|i| self.counts[i][i]).sum::<u64>() as f64 / total as f64
+    }
+
+    /// Cohen's kappa κ = (p_o − p_e) / (1 − p_e).
+    pub fn cohen_kappa(&self) -> f64 {
+        let total = self.total() as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        let p_o = self.accuracy();
+        let p_e = (0..5)
+            .map(|i| {
+                let row: u64 = self.counts[i].iter().sum();
+                let col: u64 = self.counts.iter().map(|r| r[i]).sum();
+                row as f64 * col as f64
+            })
+            .sum::<f64>()
+            / (total * total);
+        if p_e >= 1.0 {
+            return if p_o >= 1.0 { 1.0 } else { 0.0 };
+        }
+        (p_o - p_e) / (1.0 - p_e)
+    }
+
+    /// Per-stage precision, recall and expert support.
+    pub fn stage_metrics(&self) -> Vec<StageMetrics> {
+        (0..5)
+            .map(|i| {
+                let hits = self.counts[i][i] as f64;
+                let support: u64 = self.counts[i].iter().sum();
+                let predicted: u64 = self.counts.iter().map(|r| r[i]).sum();
+                StageMetrics {
+                    stage: SLEEP_STAGES[i].to_string(),
+                    precision: if predicted > 0 {
+                        hits / predicted as f64
+                    } else {
+                        0.0
+                    },
+                    recall: if support > 0 {
+                        hits / support as f64
+                    } else {
+                        0.0
+                    },
+                    support,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StageMetrics {
+    pub stage: String,
+    pub precision: f64,
+    pub recall: f64,
+    pub support: u64,
+}
+
+/// Gate behaviour at one `gsafe_min` threshold.
+///
+/// Expert N2/N3 epochs count as safe; W, N1 and REM as unsafe.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ThresholdPoint {
+    pub gsafe_min: f64,
+    /// Fraction of all epochs with the gate open.
+    pub open_fraction: f64,
+    /// Gate open on an unsafe epoch, over unsafe epochs.
+    pub false_safe_rate: f64,
+    /// Gate closed on a safe epoch, over safe epochs.
+    pub false_unsafe_rate: f64,
+}
+
+/// Settings for [`calibrate`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CalibrationParams {
+    /// Curve resolution: thresholds i / steps for i in 0..=steps.
+    pub threshold_steps: usize,
+    /// Highest acceptable false-safe rate for the recommended threshold.
+    pub target_false_safe_rate: f64,
+    /// Non-G_safe thresholds kept while sweeping `gsafe_min`.
+    pub base_gate: SleepGateParams,
+}
+
+impl Default for CalibrationParams {
+    fn default() -> Self {
+        Self {
+            threshold_steps: 20,
+            target_false_safe_rate: 0.05,
+            base_gate: SleepGateParams::default(),
+        }
+    }
+}
+
+/// Versioned calibration file.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SleepCalibration {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Free-form source description, e.g. the PSG study or file names.
+    pub source: String,
+    pub epochs: usize,
+    pub confusion: ConfusionMatrix,
+    pub cohen_kappa: f64,
+    pub stages: Vec<StageMetrics>,
+    pub gsafe_curve: Vec<ThresholdPoint>,
+    /// Gate thresholds to load, with `gsafe_min` from the curve.
+    pub gate: SleepGateParams,
+}
+
+impl SleepCalibration {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Parse a calibration file, rejecting other format versions.
+    pub fn from_json(src: &str) -> Result<Self, SleepDataError> {
+        #[derive(Deserialize)]
+        struct Version {
+            format_version: u32,
+        }
+        let v: Version =
+            serde_json::from_str(src).map_err(|e| SleepDataError::Json(e.to_string()))?;
+        if v.format_version != CALIBRATION_FORMAT_VERSION {
+            return Err(SleepDataError::UnsupportedVersion {
+                found: v.format_version,
+                expected: CALIBRATION_FORMAT_VERSION,
+            });
+        }
+        serde_json::from_str(src).map_err(|e| SleepDataError::Json(e.to_string()))
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), SleepDataError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json()).map_err(|e| SleepDataError::io(path, e))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SleepDataError> {
+        let path = path.as_ref();
+        let src = std::fs::read_to_string(path).map_err(|e| SleepDataError::io(path, e))?;
+        Self::from_json(&src)
+    }
+}
+
+/// Sweep `gsafe_min` over aligned epochs.
+pub fn gsafe_curve(epochs: &[AlignedEpoch], params: &CalibrationParams) -> Vec<ThresholdPoint> {
+    let steps = params.threshold_steps.max(1);
+    let is_safe = |e: &AlignedEpoch| matches!(e.expert, SleepStage::N2 | SleepStage::N3);
+    let safe = epochs.iter().filter(|e| is_safe(e)).count();
+    let unsafe_ = epochs.len() - safe;
+    let rate = |n: usize, d: usize| if d > 0 { n as f64 / d as f64 } else { 0.0 };
+
+    (0..=steps)
+        .map(|i| {
+            let gate = SleepGateParams {
+                gsafe_min: i as f64 / steps as f64,
+                ..params.base_gate.clone()
+            };
+            let open: Vec<bool> = epochs.iter().map(|e| e.snapshot.gate(&gate).open).collect();
+            let false_safe = epochs
+                .iter()
+                .zip(&open)
+                .filter(|(e, o)| **o && !is_safe(e))
+                .count();
+            let false_unsafe = epochs
+                .iter()
+                .zip(&open)
+                .filter(|(e, o)| !**o && is_safe(e))
+                .count();
+            ThresholdPoint {
+                gsafe_min: gate.gsafe_min,
+                open_fraction: rate(open.iter().filter(|o| **o).count(), epochs.len()),
+                false_safe_rate: rate(false_safe, unsafe_),
+                false_unsafe_rate: rate(false_unsafe, safe),
+            }
+        })
+        .collect()
+}
+
+/// Calibrate computed epochs against an expert hypnogram.
+///
+/// The recommended `gsafe_min` is the lowest threshold whose false-safe rate
+/// meets the target, or 1.0 if none does.
+pub fn calibrate(
+    hypnogram: &Hypnogram,
+    computed: &[ComputedEpoch],
+    source: &str,
+    params: &CalibrationParams,
+) -> Result<SleepCalibration, SleepDataError> {
+    let aligned = align_epochs(hypnogram, computed);
+    if aligned.is_empty() {
+        return Err(SleepDataError::NoAlignedEpochs);
+    }
+    let confusion = ConfusionMatrix::from_aligned(&aligned);
+    let curve = gsafe_curve(&aligned, params);
+    let gsafe_min = curve
+        .iter()
+        .find(|p| p.false_safe_rate <= params.target_false_safe_rate)
+        .map_or(1.0, |p| p.gsafe_min);
+
+    Ok(SleepCalibration {
+        format_version: CALIBRATION_FORMAT_VERSION,
+        created_at: Utc::now(),
+        source: source.to_string(),
+        epochs: aligned.len(),
+        cohen_kappa: confusion.cohen_kappa(),
+        stages: confusion.stage_metrics(),
+        confusion,
+        gsafe_curve: curve,
+        gate: SleepGateParams {
+            gsafe_min,
+            ..params.base_gate.clone()
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn one_hot(stage: usize, p: f64) -> [f64; 5] {
+        let mut out = [(1.0 - p) / 4.0; 5];
+        out[stage] = p;
+        out
+    }
+
+    #[test]
+    fn test_kappa_matches_hand_computation() {
+        let mut m = ConfusionMatrix::default();
+        m.counts[0][0] = 20;
+        m.counts[0][1] = 5;
+        m.counts[1][0] = 10;
+        m.counts[1][1] = 15;
+        // p_o = 0.7, p_e = (25·30 + 25·20) / 50² = 0.5
+        assert!((m.accuracy() - 0.7).abs() < 1e-12);
+        assert!((m.cohen_kappa() - 0.4).abs() < 1e-12);
+        let wake = &m.stage_metrics()[0];
+        assert_eq!(wake.support, 25);
+        assert!((wake.recall - 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calibrate_aligns_and_writes_versioned_file() {
+        let hypnogram = Hypnogram::from_csv("W\nN1\nN2\nN3\nN3\n?\nREM\n", 30.0).unwrap();
+        // Computed epochs shifted by 5 s; the N1 epoch is predicted as N2.
+        let computed: Vec<ComputedEpoch> = [
+            (0, 0.9),
+            (2, 0.85),
+            (2, 0.9),
+            (3, 0.95),
+            (3, 0.9),
+            (0, 0.5),
+            (4, 0.8),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, (stage, p))| ComputedEpoch {
+            onset_secs: 5.0 + 30.0 * i as f64,
+            posteriors: one_hot(*stage, *p),
+            unknown_mass: 0.0,
+            microarousal: false,
+        })
+        .collect();
+
+        let params = CalibrationParams::default();
+        let cal = calibrate(&hypnogram, &computed, "unit-test", &params).unwrap();
+        assert_eq!(cal.epochs, 6);
+        assert_eq!(cal.confusion.counts[1][2], 1);
+        assert!(cal.cohen_kappa > 0.5 && cal.cohen_kappa < 1.0);
+        assert_eq!(cal.gsafe_curve.len(), 21);
+        assert_eq!(cal.gsafe_curve[0].false_unsafe_rate, 0.0);
+        // The misclassified N1 epoch (G_safe ≈ 0.34) must fall below the threshold.
+        let n1_gsafe = computed[1].snapshot().g_safe();
+        assert!(cal.gate.gsafe_min > n1_gsafe);
+        assert!(cal.gate.gsafe_min <= computed[2].snapshot().g_safe());
+
+        let path = std::env::temp_dir().join(format!("sleep-cal-{}.json", uuid::Uuid::new_v4()));
+        cal.write(&path).unwrap();
+        assert_eq!(SleepGateParams::load_calibrated(&path).unwrap(), cal.gate);
+        let bumped = cal
+            .to_json()
+            .replacen("\"format_version\": 1", "\"format_version\": 2", 1);
+        std::fs::write(&path, bumped).unwrap();
+        assert_eq!(
+            SleepCalibration::load(&path),
+            Err(SleepDataError::UnsupportedVersion {
+                found: 2,
+                expected: CALIBRATION_FORMAT_VERSION
+            })
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_no_overlap_is_an_error() {
+        let hypnogram = Hypnogram::from_csv("W\n", 30.0).unwrap();
+        let computed = [ComputedEpoch {
+            onset_secs: 600.0,
+            posteriors: one_hot(0, 1.0),
+            unknown_mass: 0.0,
+            microarousal: false,
+        }];
+        assert_eq!(
+            calibrate(&hypnogram, &computed, "", &CalibrationParams::default()),
+            Err(SleepDataError::NoAlignedEpochs)
+        );
+    }
+}
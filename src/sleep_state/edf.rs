@@ -0,0 +1,311 @@
+//! EDF/EDF+ header and annotation reader (no sample decoding).
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sleep_state::SleepDataError;
+
+/// Label of the EDF+ annotation signal.
+pub const EDF_ANNOTATIONS_LABEL: &str = "EDF Annotations";
+
+/// One signal header.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EdfSignalHeader {
+    pub label: String,
+    pub transducer: String,
+    pub physical_dimension: String,
+    pub physical_min: f64,
+    pub physical_max: f64,
+    pub digital_min: i32,
+    pub digital_max: i32,
+    pub prefiltering: String,
+    pub samples_per_record: usize,
+}
+
+impl EdfSignalHeader {
+    /// True for the EDF+ annotation channel.
+    pub fn is_annotations(&self) -> bool {
+        self.label == EDF_ANNOTATIONS_LABEL
+    }
+
+    /// Sampling rate given the record duration.
+    pub fn sample_rate_hz(&self, record_duration_secs: f64) -> f64 {
+        if record_duration_secs > 0.0 {
+            self.samples_per_record as f64 / record_duration_secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Fixed EDF header plus signal headers.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EdfHeader {
+    pub version: String,
+    pub patient: String,
+    pub recording: String,
+    /// dd.mm.yy as stored.
+    pub start_date: String,
+    /// hh.mm.ss as stored.
+    pub start_time: String,
+    pub header_bytes: usize,
+    /// "EDF+C" / "EDF+D" for EDF+, empty for plain EDF.
+    pub reserved: String,
+    /// Number of data records; -1 if unknown.
+    pub records: i64,
+    pub record_duration_secs: f64,
+    pub signals: Vec<EdfSignalHeader>,
+}
+
+impl EdfHeader {
+    /// Bytes per data record (2 bytes per sample).
+    pub fn record_bytes(&self) -> usize {
+        self.signals.iter().map(|s| 2 * s.samples_per_record).sum()
+    }
+}
+
+/// One EDF+ annotation from a time-stamped annotation list.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EdfAnnotation {
+    /// Seconds from recording start.
+    pub onset_secs: f64,
+    pub duration_secs: Option<f64>,
+    pub text: String,
+}
+
+struct Fields<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Fields<'a> {
+    fn take(&mut self, len: usize, field: &str) -> Result<String, SleepDataError> {
+        let end = self.offset + len;
+        let raw = self
+            .bytes
+            .get(self.offset..end)
+            .ok_or_else(|| SleepDataError::Edf(format!("header truncated at {}", field)))?;
+        self.offset = end;
+        Ok(String::from_utf8_lossy(raw).trim().to_string())
+    }
+
+    fn number<T: std::str::FromStr>(
+        &mut self,
+        len: usize,
+        field: &str,
+    ) -> Result<T, SleepDataError> {
+        let text = self.take(len, field)?;
+        text.parse()
+            .map_err(|_| SleepDataError::Edf(format!("{} is not a number: {:?}", field, text)))
+    }
+
+    fn per_signal<T>(
+        &mut self,
+        ns: usize,
+        len: usize,
+        field: &str,
+        parse: impl Fn(&mut Self, usize, &str) -> Result<T, SleepDataError>,
+    ) -> Result<Vec<T>, SleepDataError> {
+        (0..ns).map(|_| parse(self, len, field)).collect()
+    }
+}
+
+/// Parse the EDF header from the start of a file.
+pub fn parse_edf_header(bytes: &[u8]) -> Result<EdfHeader, SleepDataError> {
+    let mut f = Fields { bytes, offset: 0 };
+    let version = f.take(8, "version")?;
+    let patient = f.take(80, "patient")?;
+    let recording = f.take(80, "recording")?;
+    let start_date = f.take(8, "start date")?;
+    let start_time = f.take(8, "start time")?;
+    let header_bytes: usize = f.number(8, "header bytes")?;
+    let reserved = f.take(44, "reserved")?;
+    let records: i64 = f.number(8, "record count")?;
+    let record_duration_secs: f64 = f.number(8, "record duration")?;
+    let ns: usize = f.number(4, "signal count")?;
+
+    if header_bytes != 256 * (ns + 1) {
+        return Err(SleepDataError::Edf(format!(
+            "header size {} does not match {} signals",
+            header_bytes, ns
+        )));
+    }
+
+    let labels = f.per_signal(ns, 16, "label", |f, n, name| f.take(n, name))?;
+    let transducers = f.per_signal(ns, 80, "transducer", |f, n, name| f.take(n, name))?;
+    let dimensions = f.per_signal(ns, 8, "physical dimension", |f, n, name| f.take(n, name))?;
+    let physical_min = f.per_signal(ns, 8, "physical minimum", |f, n, name| f.number(n, name))?;
+    let physical_max = f.per_signal(ns, 8, "physical maximum", |f, n, name| f.number(n, name))?;
+    let digital_min = f.per_signal(ns, 8, "digital minimum", |f, n, name| f.number(n, name))?;
+    let digital_max = f.per_signal(ns, 8, "digital maximum", |f, n, name| f.number(n, name))?;
+    let prefiltering = f.per_signal(ns, 80, "prefiltering", |f, n, name| f.take(n, name))?;
+    let samples = f.per_signal(ns, 8, "samples per record", |f, n, name| f.number(n, name))?;
+    f.per_signal(ns, 32, "signal reserved", |f, n, name| f.take(n, name))?;
+
+    let signals = (0..ns)
+        .map(|i| EdfSignalHeader {
+            label: labels[i].clone(),
+            transducer: transducers[i].clone(),
+            physical_dimension: dimensions[i].clone(),
+            physical_min: physical_min[i],
+            physical_max: physical_max[i],
+            digital_min: digital_min[i],
+            digital_max: digital_max[i],
+            prefiltering: prefiltering[i].clone(),
+            samples_per_record: samples[i],
+        })
+        .collect();
+
+    Ok(EdfHeader {
+        version,
+        patient,
+        recording,
+        start_date,
+        start_time,
+        header_bytes,
+        reserved,
+        records,
+        record_duration_secs,
+        signals,
+    })
+}
+
+/// Read every annotation from the EDF+ annotation signal(s).
+///
+/// Record-timekeeping TALs (empty text) are skipped. Plain EDF files yield
+/// an empty list.
+pub fn read_edf_annotations(bytes: &[u8]) -> Result<Vec<EdfAnnotation>, SleepDataError> {
+    let header = parse_edf_header(bytes)?;
+    let record_bytes = header.record_bytes();
+    let mut annotations = Vec::new();
+    if record_bytes == 0 || !header.signals.iter().any(|s| s.is_annotations()) {
+        return Ok(annotations);
+    }
+
+    let data = &bytes[header.header_bytes.min(bytes.len())..];
+    for record in data.chunks_exact(record_bytes) {
+        let mut offset = 0;
+        for signal in &header.signals {
+            let len = 2 * signal.samples_per_record;
+            if signal.is_annotations() {
+                parse_tals(&record[offset..offset + len], &mut annotations)?;
+            }
+            offset += len;
+        }
+    }
+    Ok(annotations)
+}
+
+/// Read an EDF file's header and annotations from disk.
+pub fn load_edf(path: impl AsRef<Path>) -> Result<(EdfHeader, Vec<EdfAnnotation>), SleepDataError> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(|e| SleepDataError::io(path, e))?;
+    Ok((parse_edf_header(&bytes)?, read_edf_annotations(&bytes)?))
+}
+
+/// Time-stamped annotation lists: `+onset[\x15duration]\x14text\x14...\x14\x00`.
+fn parse_tals(raw: &[u8], out: &mut Vec<EdfAnnotation>) -> Result<(), SleepDataError> {
+    for tal in raw.split(|b| *b == 0).filter(|t| !t.is_empty()) {
+        let mut parts = tal.split(|b| *b == 0x14);
+        let stamp = String::from_utf8_lossy(parts.next().unwrap_or_default()).to_string();
+        let (onset, duration) = match stamp.split_once('\u{15}') {
+            Some((onset, duration)) => (onset, Some(duration)),
+            None => (stamp.as_str(), None),
+        };
+        let onset_secs: f64 = onset
+            .parse()
+            .map_err(|_| SleepDataError::Edf(format!("bad annotation onset {:?}", onset)))?;
+        let duration_secs = duration
+            .map(|d| {
+                d.parse()
+                    .map_err(|_| SleepDataError::Edf(format!("bad annotation duration {:?}", d)))
+            })
+            .transpose()?;
+
+        for text in parts.filter(|t| !t.is_empty()) {
+            out.push(EdfAnnotation {
+                onset_secs,
+                duration_secs,
+                text: String::from_utf8_lossy(text).to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    fn field(out: &mut Vec<u8>, value: &str, len: usize) {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.resize(len, b' ');
+        out.extend_from_slice(&bytes);
+    }
+
+    /// EDF+ file with one EEG signal and one annotation signal, 30 s records.
+    pub(crate) fn sample_edf(tals: &[&str]) -> Vec<u8> {
+        let ann_samples = 60;
+        let mut out = Vec::new();
+        field(&mut out, "0", 8);
+        field(&mut out, "X X X X", 80);
+        field(&mut out, "Startdate 01-JAN-2026 X X X", 80);
+        field(&mut out, "01.01.26", 8);
+        field(&mut out, "22.00.00", 8);
+        field(&mut out, "768", 8);
+        field(&mut out, "EDF+C", 44);
+        field(&mut out, &tals.len().to_string(), 8);
+        field(&mut out, "30", 8);
+        field(&mut out, "2", 4);
+        for (a, b, len) in [
+            ("EEG Fpz-Cz", EDF_ANNOTATIONS_LABEL, 16),
+            ("AgAgCl electrode", "", 80),
+            ("uV", "", 8),
+            ("-200", "-1", 8),
+            ("200", "1", 8),
+            ("-32768", "-32768", 8),
+            ("32767", "32767", 8),
+            ("HP:0.5Hz", "", 80),
+            ("4", &ann_samples.to_string(), 8),
+            ("", "", 32),
+        ] {
+            field(&mut out, a, len);
+            field(&mut out, b, len);
+        }
+        for tal in tals {
+            out.extend_from_slice(&[0u8; 8]);
+            let mut ann = tal.as_bytes().to_vec();
+            ann.resize(2 * ann_samples, 0);
+            out.extend_from_slice(&ann);
+        }
+        out
+    }
+
+    #[test]
+    fn test_parses_header_and_annotations() {
+        let bytes = sample_edf(&[
+            "+0\x14\x14\x00+0\x1530\x14Sleep stage W\x14\x00",
+            "+30\x14\x14\x00+30\x1560\x14Sleep stage 2\x14\x00",
+        ]);
+        let header = parse_edf_header(&bytes).unwrap();
+        assert_eq!(header.reserved, "EDF+C");
+        assert_eq!(header.records, 2);
+        assert_eq!(header.signals[0].label, "EEG Fpz-Cz");
+        assert!((header.signals[0].sample_rate_hz(30.0) - 4.0 / 30.0).abs() < 1e-12);
+        assert!(header.signals[1].is_annotations());
+
+        let annotations = read_edf_annotations(&bytes).unwrap();
+        assert_eq!(annotations.len(), 2);
+        assert_eq!(annotations[1].onset_secs, 30.0);
+        assert_eq!(annotations[1].duration_secs, Some(60.0));
+        assert_eq!(annotations[1].text, "Sleep stage 2");
+    }
+
+    #[test]
+    fn test_rejects_truncated_header() {
+        let bytes = sample_edf(&[]);
+        let err = parse_edf_header(&bytes[..270]).unwrap_err();
+        assert!(matches!(err, SleepDataError::Edf(msg) if msg.contains("label")));
+    }
+}
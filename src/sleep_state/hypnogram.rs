@@ -0,0 +1,224 @@
+//! Expert-scored hypnograms from CSV, JSON or EDF+ annotations.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sleep_state::edf::EdfAnnotation;
+use crate::sleep_state::{SleepDataError, SleepStage};
+
+/// AASM scoring epoch length.
+pub const DEFAULT_EPOCH_SECS: f64 = 30.0;
+
+/// Fixed-length epoch labels starting at `start_secs` from recording start.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Hypnogram {
+    pub epoch_secs: f64,
+    #[serde(default)]
+    pub start_secs: f64,
+    pub stages: Vec<SleepStage>,
+}
+
+#[derive(Deserialize)]
+struct HypnogramJson {
+    #[serde(default = "default_epoch_secs")]
+    epoch_secs: f64,
+    #[serde(default)]
+    start_secs: f64,
+    stages: Vec<String>,
+}
+
+fn default_epoch_secs() -> f64 {
+    DEFAULT_EPOCH_SECS
+}
+
+impl Hypnogram {
+    /// Stage of the epoch containing `t_secs`; None outside the hypnogram.
+    pub fn stage_at(&self, t_secs: f64) -> Option<SleepStage> {
+        if self.epoch_secs <= 0.0 || t_secs < self.start_secs {
+            return None;
+        }
+        let idx = ((t_secs - self.start_secs) / self.epoch_secs).floor() as usize;
+        self.stages.get(idx).copied()
+    }
+
+    /// Parse `epoch,stage` rows (or a single `stage` column) with an optional
+    /// header row. Epoch numbers must be consecutive from the first row.
+    pub fn from_csv(src: &str, epoch_secs: f64) -> Result<Self, SleepDataError> {
+        let mut stages = Vec::new();
+        let mut first_epoch = None;
+        let mut first_row = true;
+        for (i, raw) in src.lines().enumerate() {
+            let line = i + 1;
+            let row = raw.trim();
+            if row.is_empty() || row.starts_with('#') {
+                continue;
+            }
+            let cols: Vec<&str> = row.split([',', ';', '\t']).map(str::trim).collect();
+            let label = *cols.last().unwrap_or(&"");
+            let is_header = std::mem::replace(&mut first_row, false);
+            let Some(stage) = SleepStage::from_label(label) else {
+                if is_header {
+                    continue;
+                }
+                return Err(SleepDataError::Parse {
+                    line,
+                    message: format!("unknown sleep stage {:?}", label),
+                });
+            };
+
+            if cols.len() > 1 {
+                let epoch: usize = cols[0].parse().map_err(|_| SleepDataError::Parse {
+                    line,
+                    message: format!("bad epoch number {:?}", cols[0]),
+                })?;
+                let base = *first_epoch.get_or_insert(epoch);
+                if epoch != base + stages.len() {
+                    return Err(SleepDataError::Parse {
+                        line,
+                        message: format!(
+                            "epoch {} out of sequence, expected {}",
+                            epoch,
+                            base + stages.len()
+                        ),
+                    });
+                }
+            }
+            stages.push(stage);
+        }
+
+        Ok(Self {
+            epoch_secs,
+            start_secs: 0.0,
+            stages,
+        })
+    }
+
+    /// Parse `{"epoch_secs": 30, "start_secs": 0, "stages": ["W", "N1", ...]}`.
+    pub fn from_json(src: &str) -> Result<Self, SleepDataError> {
+        let raw: HypnogramJson =
+            serde_json::from_str(src).map_err(|e| SleepDataError::Json(e.to_string()))?;
+        let stages = raw
+            .stages
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                SleepStage::from_label(label).ok_or_else(|| {
+                    SleepDataError::Json(format!("stage {}: unknown sleep stage {:?}", i, label))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            epoch_secs: raw.epoch_secs,
+            start_secs: raw.start_secs,
+            stages,
+        })
+    }
+
+    /// Build from EDF+ stage annotations (e.g. `Sleep stage W`, duration 60).
+    ///
+    /// Non-stage annotations are ignored; gaps between stage annotations are
+    /// filled with Unscored.
+    pub fn from_edf_annotations(annotations: &[EdfAnnotation], epoch_secs: f64) -> Self {
+        let mut scored: Vec<(&EdfAnnotation, SleepStage)> = annotations
+            .iter()
+            .filter_map(|a| SleepStage::from_label(&a.text).map(|s| (a, s)))
+            .collect();
+        scored.sort_by(|a, b| a.0.onset_secs.total_cmp(&b.0.onset_secs));
+
+        let start_secs = scored.first().map_or(0.0, |(a, _)| a.onset_secs);
+        let mut stages = Vec::new();
+        for (a, stage) in scored {
+            let first = ((a.onset_secs - start_secs) / epoch_secs).round() as usize;
+            let count = (a.duration_secs.unwrap_or(epoch_secs) / epoch_secs)
+                .round()
+                .max(1.0) as usize;
+            if stages.len() < first {
+                stages.resize(first, SleepStage::Unscored);
+            }
+            stages.truncate(first);
+            stages.extend(std::iter::repeat_n(stage, count));
+        }
+
+        Self {
+            epoch_secs,
+            start_secs,
+            stages,
+        }
+    }
+
+    /// Load by extension: `.csv`/`.txt`, `.json`, or `.edf`.
+    pub fn load(path: impl AsRef<Path>, epoch_secs: f64) -> Result<Self, SleepDataError> {
+        let path = path.as_ref();
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if ext == "edf" {
+            let (_, annotations) = crate::sleep_state::edf::load_edf(path)?;
+            return Ok(Self::from_edf_annotations(&annotations, epoch_secs));
+        }
+        let src = std::fs::read_to_string(path).map_err(|e| SleepDataError::io(path, e))?;
+        if ext == "json" {
+            Self::from_json(&src)
+        } else {
+            Self::from_csv(&src, epoch_secs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sleep_state::edf::{read_edf_annotations, tests::sample_edf};
+
+    #[test]
+    fn test_csv_with_header_and_epoch_numbers() {
+        let h = Hypnogram::from_csv("epoch,stage\n1,W\n2,N1\n3,S4\n4,REM\n", 30.0).unwrap();
+        assert_eq!(
+            h.stages,
+            [
+                SleepStage::Wake,
+                SleepStage::N1,
+                SleepStage::N3,
+                SleepStage::Rem
+            ]
+        );
+        assert_eq!(h.stage_at(65.0), Some(SleepStage::N3));
+        assert_eq!(h.stage_at(120.0), None);
+
+        let err = Hypnogram::from_csv("1,W\n3,N2\n", 30.0).unwrap_err();
+        assert!(matches!(err, SleepDataError::Parse { line: 2, .. }));
+        let err = Hypnogram::from_csv("W\nN2\nX9\n", 30.0).unwrap_err();
+        assert!(matches!(err, SleepDataError::Parse { line: 3, .. }));
+    }
+
+    #[test]
+    fn test_json_hypnogram() {
+        let h = Hypnogram::from_json(r#"{"stages": ["W", "N2", "?"], "start_secs": 15}"#).unwrap();
+        assert_eq!(h.epoch_secs, DEFAULT_EPOCH_SECS);
+        assert_eq!(h.stage_at(50.0), Some(SleepStage::N2));
+        assert_eq!(h.stages[2], SleepStage::Unscored);
+        assert!(Hypnogram::from_json(r#"{"stages": ["deep"]}"#).is_err());
+    }
+
+    #[test]
+    fn test_edf_stage_annotations_expand_to_epochs() {
+        let bytes = sample_edf(&[
+            "+0\x14\x14\x00+0\x1560\x14Sleep stage W\x14\x00",
+            "+30\x14\x14\x00+90\x1530\x14Sleep stage 3\x14\x00+95\x14Arousal\x14\x00",
+        ]);
+        let annotations = read_edf_annotations(&bytes).unwrap();
+        let h = Hypnogram::from_edf_annotations(&annotations, 30.0);
+        assert_eq!(
+            h.stages,
+            [
+                SleepStage::Wake,
+                SleepStage::Wake,
+                SleepStage::Unscored,
+                SleepStage::N3
+            ]
+        );
+    }
+}
@@ -2,7 +2,16 @@ use std::cmp::Ordering;
 
 use serde::{Deserialize, Serialize};
 
-use crate::governance::{GovernanceMode, GovernanceStateV1};
+use crate::governance::{EnforcementMode, GovernanceMode, GovernanceStateV1, PolicyViolation};
+use crate::sleep_state::{SleepGate, SleepGateParams, SleepStateSnapshot};
+
+pub mod batch;
+pub mod calibration;
+pub mod profile;
+pub mod rolling;
+pub mod trace;
+
+use trace::{DecisionTrace, StepLog, StepOutcome};
 
 /// Band-level safety metrics for one spectral-object.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -11,31 +20,177 @@ pub struct BandSafetyProfile {
     pub bands: Vec<BandSafetyEntry>,
     /// Minimum safety across all bands.
     pub safety_min: f64,
-    /// Mean safety across all bands.
+    /// Weighted mean safety across all bands (registry weights).
     pub safety_mean: f64,
+    /// Name of the band that set safety_min; None for an empty profile.
+    pub safety_min_band: Option<String>,
+    /// Variance of safety_min, propagated from that band's inputs.
+    #[serde(default)]
+    pub safety_min_variance: f64,
+}
+
+impl BandSafetyProfile {
+    /// safety_min as read by the depth gate: the minimum of per-band bounds.
+    pub fn safety_min_bound(&self, bound: DecisionBound) -> f64 {
+        self.bands
+            .iter()
+            .map(|b| bound.apply(b.safety_score, b.safety_variance))
+            .fold(None, |acc: Option<f64>, s| Some(acc.map_or(s, |m| m.min(s))))
+            .unwrap_or(self.safety_min)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BandSafetyEntry {
-    /// Band index or label (e.g., 0=delta,1=theta,...).
+    /// Band index, resolved through `SpectralVisionParams::bands`.
     pub band_index: i32,
+    /// Registry name, or `band<index>` for unregistered bands.
+    pub band_name: String,
     /// Normalized mean power μ_B ∈ [0,1].
     pub mean_power: f64,
     /// Normalized stddev σ_B ∈ [0,1].
     pub stddev_power: f64,
     /// Safety score s_B ∈ [0,1].
     pub safety_score: f64,
+    /// Var(s_B) = Var(μ_B) + Var(σ_B).
+    #[serde(default)]
+    pub safety_variance: f64,
+    /// Registry weight used in safety_mean.
+    pub weight: f64,
     /// Discrete hazard class for routing/policies.
     pub hazard_class: BandHazardClass,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum BandHazardClass {
     Safe,
     Elevated,
     High,
 }
 
+/// Artifact tracks for one spectral-object (SpectralVision.md §4.2, §5.2).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ArtifactProfile {
+    /// artifactepochfraction ∈ [0,1]: share of epochs flagged as artifact.
+    pub epoch_fraction: f64,
+    /// artifactcorr ∈ [0,1]: correlation of band power with artifact channels.
+    pub correlation: f64,
+    /// Per-band artifact masks (band_index, contamination ∈ [0,1]).
+    pub band_masks: Vec<(i32, f64)>,
+}
+
+impl ArtifactProfile {
+    /// Profile with only the scalar epoch fraction (no correlation or masks).
+    pub fn from_epoch_fraction(epoch_fraction: f64) -> Self {
+        Self {
+            epoch_fraction,
+            ..Self::default()
+        }
+    }
+
+    /// Mask value for a band; 0 if unmasked.
+    pub fn band_mask(&self, band_index: i32) -> f64 {
+        self.band_masks
+            .iter()
+            .find(|(b, _)| *b == band_index)
+            .map_or(0.0, |(_, m)| clamp01(*m))
+    }
+}
+
+/// Measurement variances for the spectral-vision inputs; zero means exact.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct InputUncertainty {
+    /// (band_index, Var(μ_B), Var(σ_B)); unlisted bands are exact.
+    pub bands: Vec<(i32, f64, f64)>,
+    pub stability_variance: f64,
+    pub confidence_variance: f64,
+}
+
+impl InputUncertainty {
+    /// (Var(μ_B), Var(σ_B)) for a band.
+    pub fn band(&self, band_index: i32) -> (f64, f64) {
+        self.bands
+            .iter()
+            .find(|(b, _, _)| *b == band_index)
+            .map_or((0.0, 0.0), |(_, vm, vs)| (clean_variance(*vm), clean_variance(*vs)))
+    }
+}
+
+/// Variance of an interval [lo, hi] read as value ± z·sd.
+pub fn variance_from_interval(lo: f64, hi: f64, z: f64) -> f64 {
+    if z > 0.0 {
+        clean_variance(((hi - lo) / (2.0 * z)).powi(2))
+    } else {
+        0.0
+    }
+}
+
+/// Which value the depth and promotion gates compare against thresholds.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum DecisionBound {
+    /// Point estimates.
+    #[default]
+    Point,
+    /// Lower confidence bound value − z·sd.
+    Pessimistic { z: f64 },
+}
+
+impl DecisionBound {
+    /// Value to gate on, given its variance.
+    pub fn apply(self, value: f64, variance: f64) -> f64 {
+        match self {
+            DecisionBound::Point => value,
+            DecisionBound::Pessimistic { z } => {
+                let z = if z.is_finite() { z.max(0.0) } else { 0.0 };
+                clamp01(value - z * clean_variance(variance).sqrt())
+            }
+        }
+    }
+}
+
+/// How much one spectral-object's signature relies on each band.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BandDependency {
+    /// (band_index, weight ≥ 0); normalized over the bands in the profile.
+    pub weights: Vec<(i32, f64)>,
+}
+
+/// Band-dependency summary used to scale and gate promotion.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BandDependencyAssessment {
+    /// Dependency share on HIGH hazard bands ∈ [0,1].
+    pub high_share: f64,
+    /// Dependency share on ELEVATED hazard bands ∈ [0,1].
+    pub elevated_share: f64,
+    /// Multiplier applied to promotionscore ∈ [0,1].
+    pub promotion_factor: f64,
+}
+
+impl Default for BandDependencyAssessment {
+    fn default() -> Self {
+        Self {
+            high_share: 0.0,
+            elevated_share: 0.0,
+            promotion_factor: 1.0,
+        }
+    }
+}
+
+/// One band's share of the hygiene verdict.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BandHygieneContribution {
+    pub band_index: i32,
+    pub band_name: String,
+    /// s_B from the band-safety profile.
+    pub safety_score: f64,
+    /// a_B = clamp01(mask_B + artifactcorr · μ_B).
+    pub artifact_load: f64,
+    /// q_B = s_B · (1 − a_B).
+    pub quality: f64,
+    /// Share of the weighted quality loss (s_B − q_B) carried by this band.
+    pub loss_share: f64,
+}
+
 /// Hygiene metrics computed from band safety and artifact metrics.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SpectralHygiene {
@@ -45,6 +200,11 @@ pub struct SpectralHygiene {
     pub artifact_level: f64,
     /// Fraction of bands with safety_score ≥ safety_threshold.
     pub safe_band_fraction: f64,
+    /// Per-band contributions, in profile order.
+    pub bands: Vec<BandHygieneContribution>,
+    /// First-order variance of band_quality from the band safety variances.
+    #[serde(default)]
+    pub band_quality_variance: f64,
 }
 
 /// Excavation depth mode for this spectral-object.
@@ -55,8 +215,115 @@ pub enum ExcavationDepth {
     DigFull,
 }
 
+/// One named EEG/RF band and its per-band overrides; omitted fields take
+/// their defaults (no overrides, weight 1).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BandDefinition {
+    pub index: i32,
+    pub name: String,
+    /// Lower edge in Hz (inclusive).
+    pub freq_low_hz: f64,
+    /// Upper edge in Hz (exclusive).
+    pub freq_high_hz: f64,
+    /// Overrides `SpectralVisionParams::hazard_elevated_max` for this band.
+    pub hazard_elevated_max: Option<f64>,
+    /// Overrides `SpectralVisionParams::hazard_safe_min` for this band.
+    pub hazard_safe_min: Option<f64>,
+    /// Weight in safety_mean (≥ 0, default 1).
+    pub weight: f64,
+}
+
+impl Default for BandDefinition {
+    /// Unnamed, empty band; `SpectralVisionParams::validate` rejects it
+    /// until a name and frequency range are set.
+    fn default() -> Self {
+        Self::new(0, "", 0.0, 0.0)
+    }
+}
+
+impl BandDefinition {
+    pub fn new(index: i32, name: &str, freq_low_hz: f64, freq_high_hz: f64) -> Self {
+        Self {
+            index,
+            name: name.to_string(),
+            freq_low_hz,
+            freq_high_hz,
+            hazard_elevated_max: None,
+            hazard_safe_min: None,
+            weight: 1.0,
+        }
+    }
+
+    /// Set per-band hazard cut-offs.
+    pub fn with_hazard(mut self, elevated_max: f64, safe_min: f64) -> Self {
+        self.hazard_elevated_max = Some(elevated_max);
+        self.hazard_safe_min = Some(safe_min);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// True if `hz` falls inside this band.
+    pub fn contains_hz(&self, hz: f64) -> bool {
+        hz >= self.freq_low_hz && hz < self.freq_high_hz
+    }
+}
+
+/// Band registry keyed by band index.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct BandRegistry {
+    pub bands: Vec<BandDefinition>,
+}
+
+impl BandRegistry {
+    /// Standard EEG bands: 0=delta, 1=theta, 2=alpha, 3=beta, 4=gamma.
+    pub fn eeg_standard() -> Self {
+        Self {
+            bands: vec![
+                BandDefinition::new(0, "delta", 0.5, 4.0),
+                BandDefinition::new(1, "theta", 4.0, 8.0),
+                BandDefinition::new(2, "alpha", 8.0, 13.0),
+                BandDefinition::new(3, "beta", 13.0, 30.0),
+                BandDefinition::new(4, "gamma", 30.0, 100.0),
+            ],
+        }
+    }
+
+    pub fn get(&self, index: i32) -> Option<&BandDefinition> {
+        self.bands.iter().find(|b| b.index == index)
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&BandDefinition> {
+        self.bands.iter().find(|b| b.name == name)
+    }
+
+    /// Mutable access for per-band overrides.
+    pub fn by_name_mut(&mut self, name: &str) -> Option<&mut BandDefinition> {
+        self.bands.iter_mut().find(|b| b.name == name)
+    }
+
+    /// Band containing `hz`, if any.
+    pub fn band_for_hz(&self, hz: f64) -> Option<&BandDefinition> {
+        self.bands.iter().find(|b| b.contains_hz(hz))
+    }
+
+    /// Add or replace the band with the same index.
+    pub fn insert(&mut self, band: BandDefinition) {
+        self.bands.retain(|b| b.index != band.index);
+        self.bands.push(band);
+        self.bands.sort_by_key(|b| b.index);
+    }
+}
+
 /// Parameters for spectral-vision math.
-#[derive(Clone, Debug)]
+///
+/// Loadable from JSON/TOML, with omitted fields defaulted; see [`profile`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SpectralVisionParams {
     /// Threshold separating low vs medium safety.
     pub safety_slow: f64,
@@ -71,6 +338,22 @@ pub struct SpectralVisionParams {
     pub w_safety_min: f64,
     /// Promotion gate threshold.
     pub promotion_threshold: f64,
+    /// Promotion multipliers for dependency on ELEVATED / HIGH bands (SAFE = 1).
+    pub dependency_weight_elevated: f64,
+    pub dependency_weight_high: f64,
+    /// HIGH-band dependency share above which promotion is blocked in every
+    /// enforcement mode.
+    pub high_dependency_max: f64,
+    /// Band names, frequency ranges and per-band overrides.
+    pub bands: BandRegistry,
+    /// G_safe thresholds applied when a sleep channel is bound.
+    pub sleep_gate: SleepGateParams,
+    /// Point or pessimistic (lower-bound) gating of uncertain inputs.
+    pub decision_bound: DecisionBound,
+    /// How depth gates are applied (ALN `enforcement_mode`).
+    pub enforcement_mode: EnforcementMode,
+    /// Attach a [`DecisionTrace`] to every decision.
+    pub trace: bool,
 }
 
 impl Default for SpectralVisionParams {
@@ -84,11 +367,28 @@ impl Default for SpectralVisionParams {
             w_confidence: 0.4,
             w_safety_min: 0.2,
             promotion_threshold: 0.8,
+            dependency_weight_elevated: 0.75,
+            dependency_weight_high: 0.0,
+            high_dependency_max: 0.5,
+            bands: BandRegistry::eeg_standard(),
+            sleep_gate: SleepGateParams::default(),
+            decision_bound: DecisionBound::Point,
+            enforcement_mode: EnforcementMode::Strict,
+            trace: false,
         }
     }
 }
 
-fn clamp01(x: f64) -> f64 {
+fn clean_variance(v: f64) -> f64 {
+    if v.is_finite() {
+        v.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+/// Clamp to 0.0..=1.0; non-finite input maps to 0.0.
+pub(crate) fn clamp01(x: f64) -> f64 {
     if !x.is_finite() {
         0.0
     } else if x < 0.0 {
@@ -104,65 +404,146 @@ fn clamp01(x: f64) -> f64 {
 ///
 /// For each band B:
 ///   s_B = clamp01(1 - μ_B - σ_B)
-/// hazard_class derived from s_B and params.hazard_*.[file:8]
+/// hazard_class derived from s_B and the band's hazard cut-offs, falling
+/// back to params.hazard_*; safety_mean is weighted by band weight.[file:8]
 pub fn compute_band_safety(
     raw_bands: &[(i32, f64, f64)],
     params: &SpectralVisionParams,
+) -> BandSafetyProfile {
+    compute_band_safety_uncertain(raw_bands, &InputUncertainty::default(), params)
+}
+
+/// As [`compute_band_safety`], propagating input variances:
+///   Var(s_B) = Var(μ_B) + Var(σ_B)
+/// and safety_min_variance = Var(s_B) of the band that set safety_min.
+pub fn compute_band_safety_uncertain(
+    raw_bands: &[(i32, f64, f64)],
+    uncertainty: &InputUncertainty,
+    params: &SpectralVisionParams,
 ) -> BandSafetyProfile {
     let mut entries = Vec::with_capacity(raw_bands.len());
-    let mut sum = 0.0;
+    let mut weighted_sum = 0.0;
+    let mut weight_total = 0.0;
     let mut safety_min = 1.0;
+    let mut safety_min_band = None;
+    let mut safety_min_variance = 0.0;
 
     for (band_index, mean_power_raw, stddev_power_raw) in raw_bands.iter().copied() {
         let mu = clamp01(mean_power_raw);
         let sigma = clamp01(stddev_power_raw);
         let s_b = clamp01(1.0 - mu - sigma);
+        let (var_mu, var_sigma) = uncertainty.band(band_index);
+        let safety_variance = var_mu + var_sigma;
+
+        let def = params.bands.get(band_index);
+        let band_name = def.map_or_else(|| format!("band{}", band_index), |d| d.name.clone());
+        let elevated_max = def
+            .and_then(|d| d.hazard_elevated_max)
+            .unwrap_or(params.hazard_elevated_max);
+        let safe_min = def
+            .and_then(|d| d.hazard_safe_min)
+            .unwrap_or(params.hazard_safe_min);
+        let weight = def.map_or(1.0, |d| d.weight.max(0.0));
 
-        let hazard = if s_b < params.hazard_elevated_max {
+        let hazard = if s_b < elevated_max {
             BandHazardClass::High
-        } else if s_b < params.hazard_safe_min {
+        } else if s_b < safe_min {
             BandHazardClass::Elevated
         } else {
             BandHazardClass::Safe
         };
 
-        sum += s_b;
-        if s_b < safety_min {
+        weighted_sum += weight * s_b;
+        weight_total += weight;
+        if safety_min_band.is_none() || s_b < safety_min {
             safety_min = s_b;
+            safety_min_band = Some(band_name.clone());
+            safety_min_variance = safety_variance;
         }
 
         entries.push(BandSafetyEntry {
             band_index,
+            band_name,
             mean_power: mu,
             stddev_power: sigma,
             safety_score: s_b,
+            safety_variance,
+            weight,
             hazard_class: hazard,
         });
     }
 
-    let safety_mean = if entries.is_empty() {
-        0.0
+    let safety_mean = if weight_total > 0.0 {
+        weighted_sum / weight_total
     } else {
-        sum / (entries.len() as f64)
+        0.0
     };
 
     BandSafetyProfile {
         bands: entries,
         safety_min: clamp01(safety_min),
         safety_mean: clamp01(safety_mean),
+        safety_min_band,
+        safety_min_variance,
     }
 }
 
 /// Compute spectral hygiene and safe_band_fraction from band-safety and artifact metrics.[file:8]
+///
+/// For each band B:
+///   a_B = clamp01(mask_B + artifactcorr · μ_B)
+///   q_B = s_B · (1 − a_B)
+/// band_quality = clamp01(weighted_mean(q_B) · (1 − artifactepochfraction)).
+/// With no masks and zero correlation this reduces to safety_mean · (1 − artifactepochfraction).
+/// band_quality_variance = (1 − e)² · Σ_B (w_B / W)² (1 − a_B)² Var(s_B).
 pub fn compute_spectral_hygiene(
     band_profile: &BandSafetyProfile,
-    artifact_epoch_fraction_raw: f64,
+    artifacts: &ArtifactProfile,
     safety_threshold: f64,
 ) -> SpectralHygiene {
-    let artifact_epoch_fraction = clamp01(artifact_epoch_fraction_raw);
+    let artifact_epoch_fraction = clamp01(artifacts.epoch_fraction);
+    let correlation = clamp01(artifacts.correlation);
+
+    let mut bands = Vec::with_capacity(band_profile.bands.len());
+    let (mut weighted_quality, mut weight_total, mut loss_total) = (0.0, 0.0, 0.0);
+    let mut weighted_variance = 0.0;
+    for entry in &band_profile.bands {
+        let artifact_load =
+            clamp01(artifacts.band_mask(entry.band_index) + correlation * entry.mean_power);
+        let quality = clamp01(entry.safety_score * (1.0 - artifact_load));
+        weighted_quality += entry.weight * quality;
+        weight_total += entry.weight;
+        loss_total += entry.weight * (entry.safety_score - quality);
+        weighted_variance +=
+            (entry.weight * (1.0 - artifact_load)).powi(2) * clean_variance(entry.safety_variance);
+        bands.push(BandHygieneContribution {
+            band_index: entry.band_index,
+            band_name: entry.band_name.clone(),
+            safety_score: entry.safety_score,
+            artifact_load,
+            quality,
+            loss_share: entry.weight * (entry.safety_score - quality),
+        });
+    }
+    for band in &mut bands {
+        band.loss_share = if loss_total > 0.0 {
+            band.loss_share / loss_total
+        } else {
+            0.0
+        };
+    }
+    let mean_quality = if weight_total > 0.0 {
+        weighted_quality / weight_total
+    } else {
+        0.0
+    };
 
-    // band_quality = clamp01(safety_mean * (1 - artifact_epoch_fraction))
-    let band_quality = clamp01(band_profile.safety_mean * (1.0 - artifact_epoch_fraction));
+    let band_quality = clamp01(mean_quality * (1.0 - artifact_epoch_fraction));
+    let band_quality_variance = if weight_total > 0.0 {
+        weighted_variance * ((1.0 - artifact_epoch_fraction) / weight_total).powi(2)
+    } else {
+        0.0
+    };
     let artifact_level = clamp01(1.0 - band_quality);
 
     let mut safe_count = 0usize;
@@ -181,6 +562,8 @@ pub fn compute_spectral_hygiene(
         band_quality,
         artifact_level,
         safe_band_fraction: clamp01(safe_band_fraction),
+        bands,
+        band_quality_variance,
     }
 }
 
@@ -198,16 +581,37 @@ pub fn compute_promotion_score(
     let s = clamp01(stability_score_raw);
     let c = clamp01(confidence_score_raw);
     let safety_min = clamp01(safety_min_raw);
+    let [w1, w2, w3] = promotion_weights(params);
+
+    clamp01(w1 * s + w2 * c + w3 * safety_min)
+}
 
+/// Promotion weights (w1, w2, w3) renormalized to sum to 1; all zero if
+/// they sum to zero or less.
+pub fn promotion_weights(params: &SpectralVisionParams) -> [f64; 3] {
     let w_sum = params.w_stability + params.w_confidence + params.w_safety_min;
     if w_sum <= 0.0 {
-        return 0.0;
+        return [0.0; 3];
     }
-    let w1 = params.w_stability / w_sum;
-    let w2 = params.w_confidence / w_sum;
-    let w3 = params.w_safety_min / w_sum;
+    [
+        params.w_stability / w_sum,
+        params.w_confidence / w_sum,
+        params.w_safety_min / w_sum,
+    ]
+}
 
-    clamp01(w1 * s + w2 * c + w3 * safety_min)
+/// First-order variance of promotionscore:
+///   Σ (w_i / Σw)² · Var(input_i).
+pub fn compute_promotion_variance(
+    stability_variance: f64,
+    confidence_variance: f64,
+    safety_min_variance: f64,
+    params: &SpectralVisionParams,
+) -> f64 {
+    let [w1, w2, w3] = promotion_weights(params);
+    w1.powi(2) * clean_variance(stability_variance)
+        + w2.powi(2) * clean_variance(confidence_variance)
+        + w3.powi(2) * clean_variance(safety_min_variance)
 }
 
 /// Decide whether a spectral-object is eligible for catalog promotion.
@@ -218,6 +622,51 @@ pub fn passes_promotion_gate(
     promotion_score >= params.promotion_threshold
 }
 
+/// Summarize how much an object depends on ELEVATED and HIGH hazard bands.
+///
+/// Dependency weights are normalized over bands present in the profile;
+/// weights on unmeasured bands are ignored. Then
+///   promotion_factor = Σ_B d̂_B · w(class_B),
+/// with w(Safe) = 1 and w(Elevated), w(High) from params. Without usable
+/// dependency weights the factor is 1 and both shares are 0.
+pub fn assess_band_dependency(
+    band_profile: &BandSafetyProfile,
+    dependency: &BandDependency,
+    params: &SpectralVisionParams,
+) -> BandDependencyAssessment {
+    let mut total = 0.0;
+    let mut high = 0.0;
+    let mut elevated = 0.0;
+    for (band_index, weight) in &dependency.weights {
+        let weight = if weight.is_finite() { weight.max(0.0) } else { 0.0 };
+        let Some(entry) = band_profile.bands.iter().find(|b| b.band_index == *band_index) else {
+            continue;
+        };
+        total += weight;
+        match entry.hazard_class {
+            BandHazardClass::High => high += weight,
+            BandHazardClass::Elevated => elevated += weight,
+            BandHazardClass::Safe => {}
+        }
+    }
+    if total <= 0.0 {
+        return BandDependencyAssessment::default();
+    }
+
+    let high_share = high / total;
+    let elevated_share = elevated / total;
+    let safe_share = 1.0 - high_share - elevated_share;
+    BandDependencyAssessment {
+        high_share,
+        elevated_share,
+        promotion_factor: clamp01(
+            safe_share
+                + elevated_share * clamp01(params.dependency_weight_elevated)
+                + high_share * clamp01(params.dependency_weight_high),
+        ),
+    }
+}
+
 /// Compute excavation depth as a function of safety and governance.[file:8][file:5]
 ///
 /// Rules:
@@ -227,34 +676,142 @@ pub fn passes_promotion_gate(
 ///     if safety_min < safety_slow  → Sniff
 ///     else if safety_min < safety_shigh → DigLight
 ///     else → DigFull
+/// - If a sleep channel is bound and its G_safe gate is closed → at most DigLight.
 pub fn compute_excavation_depth(
     band_profile: &BandSafetyProfile,
     gov: &GovernanceStateV1,
+    sleep: Option<&SleepGate>,
     params: &SpectralVisionParams,
 ) -> ExcavationDepth {
-    if !gov.spectral_quantification_active || !gov.soul_modeling_forbidden {
+    compute_excavation_depth_gated(band_profile, gov, sleep, params, &mut Vec::new())
+}
+
+/// As [`compute_excavation_depth`], honouring `params.enforcement_mode` and
+/// recording each depth gate that fired.
+///
+/// The constitutional bits are non-waivable and force Sniff in every mode,
+/// as are the sleep and observe-only gates; only the band-safety gate
+/// follows the enforcement mode.
+pub fn compute_excavation_depth_gated(
+    band_profile: &BandSafetyProfile,
+    gov: &GovernanceStateV1,
+    sleep: Option<&SleepGate>,
+    params: &SpectralVisionParams,
+    violations: &mut Vec<PolicyViolation>,
+) -> ExcavationDepth {
+    excavation_depth_logged(band_profile, gov, sleep, params, violations, &mut StepLog::new(false))
+}
+
+/// Depth gates, recording each rule's trace step as it is decided.
+fn excavation_depth_logged(
+    band_profile: &BandSafetyProfile,
+    gov: &GovernanceStateV1,
+    sleep: Option<&SleepGate>,
+    params: &SpectralVisionParams,
+    violations: &mut Vec<PolicyViolation>,
+    steps: &mut StepLog,
+) -> ExcavationDepth {
+    let constitutional = !gov.spectral_quantification_active || !gov.soul_modeling_forbidden;
+    steps.gate("governance.constitutional", constitutional, EnforcementMode::Strict, || {
+        format!(
+            "spectral_quantification_active={}, soul_modeling_forbidden={}",
+            gov.spectral_quantification_active, gov.soul_modeling_forbidden
+        )
+    });
+    if constitutional {
+        for rule in [
+            "spectral_vision.band_safety",
+            "spectral_vision.sleep_gate",
+            "spectral_vision.observe_only",
+        ] {
+            steps.record(rule, StepOutcome::Skipped, || "constitutional bits force Sniff".into());
+        }
         return ExcavationDepth::Sniff;
     }
 
-    match gov.mode {
-        GovernanceMode::ActiveFree => {
-            // Roaming + non-interference ⇒ observe-only, no deep digging.
+    let mode = params.enforcement_mode;
+    let s_min = band_profile.safety_min_bound(params.decision_bound);
+    let mut depth = ExcavationDepth::DigFull;
+
+    steps.gate("spectral_vision.band_safety", s_min < params.safety_shigh, mode, || {
+        format!(
+            "safety_min {:.3} ({}) vs slow {:.3} / shigh {:.3}",
+            s_min,
+            band_profile.safety_min_band.as_deref().unwrap_or("-"),
+            params.safety_slow,
+            params.safety_shigh
+        )
+    });
+    if s_min < params.safety_shigh {
+        let limited = if s_min < params.safety_slow {
             ExcavationDepth::Sniff
-        }
-        GovernanceMode::Dormant
-        | GovernanceMode::ActiveGoverned
-        | GovernanceMode::TechnicalOnly => {
-            let s_min = band_profile.safety_min;
-
-            if s_min < params.safety_slow {
-                ExcavationDepth::Sniff
-            } else if s_min < params.safety_shigh {
-                ExcavationDepth::DigLight
-            } else {
-                ExcavationDepth::DigFull
+        } else {
+            ExcavationDepth::DigLight
+        };
+        depth = mode.resolve(
+            "spectral_vision.band_safety",
+            format!("safety_min {:.3} limits depth to {:?}", s_min, limited),
+            limited,
+            depth,
+            violations,
+        );
+    }
+
+    // A closed G_safe gate is non-waivable: it caps depth in every mode,
+    // advisory and disabled included.
+    match sleep {
+        None => steps.record("spectral_vision.sleep_gate", StepOutcome::Skipped, || {
+            "no sleep channel bound".into()
+        }),
+        Some(gate) if gate.open => steps.record("spectral_vision.sleep_gate", StepOutcome::Passed, || {
+            format!("G_safe {:.3}, gate open", gate.g_safe)
+        }),
+        Some(gate) => {
+            // Closed, but an earlier rule may already hold depth below DigFull.
+            let fired = depth == ExcavationDepth::DigFull;
+            steps.gate("spectral_vision.sleep_gate", fired, EnforcementMode::Strict, || {
+                format!("gate closed: {}", gate.describe())
+            });
+            if fired {
+                depth = PolicyViolation::enforce(
+                    "spectral_vision.sleep_gate",
+                    format!("G_safe gate closed ({}) limits depth to DigLight", gate.describe()),
+                    ExcavationDepth::DigLight,
+                    violations,
+                );
             }
         }
     }
+
+    let observe_only = gov.mode == GovernanceMode::ActiveFree;
+    steps.gate("spectral_vision.observe_only", observe_only, EnforcementMode::Strict, || {
+        format!("governance mode {:?}", gov.mode)
+    });
+    if observe_only {
+        // Roaming + non-interference ⇒ observe-only, no deep digging, in every mode.
+        depth = PolicyViolation::enforce(
+            "spectral_vision.observe_only",
+            "ActiveFree governance limits depth to Sniff",
+            ExcavationDepth::Sniff,
+            violations,
+        );
+    }
+
+    depth
+}
+
+/// Object-level inputs to [`evaluate_spectral_vision`] besides the band powers.
+///
+/// The default has zero scores, no artifact load, no band dependency, no
+/// sleep channel and exact (zero-variance) measurements.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpectralVisionInputs<'a> {
+    pub stability_score: f64,
+    pub confidence_score: f64,
+    pub artifacts: Option<&'a ArtifactProfile>,
+    pub dependency: Option<&'a BandDependency>,
+    pub sleep: Option<&'a SleepStateSnapshot>,
+    pub uncertainty: Option<&'a InputUncertainty>,
 }
 
 /// Helper that encapsulates the full spectral-vision decision for one object.
@@ -266,38 +823,147 @@ pub struct SpectralVisionDecision {
     pub band_profile: BandSafetyProfile,
     pub hygiene: SpectralHygiene,
     pub excavation_depth: ExcavationDepth,
+    /// promotionscore after band-dependency scaling.
     pub promotion_score: f64,
+    /// Variance of promotion_score; 0 for exact inputs.
+    #[serde(default)]
+    pub promotion_variance: f64,
     pub promotion_passed: bool,
+    #[serde(default)]
+    pub band_dependency: BandDependencyAssessment,
+    /// Why promotion was blocked regardless of score, if it was.
+    #[serde(default)]
+    pub promotion_block_reason: Option<String>,
+    /// G_safe gate for the bound sleep channel, if any.
+    #[serde(default)]
+    pub sleep_gate: Option<SleepGate>,
+    /// Depth and promotion gates that fired; unenforced entries mean advisory mode.
+    #[serde(default)]
+    pub violations: Vec<PolicyViolation>,
+    /// [`SpectralVisionParams::fingerprint`] of the params in force.
+    #[serde(default)]
+    pub params_fingerprint: String,
+    /// Rule path and threshold margins, when `params.trace` is set.
+    #[serde(default)]
+    pub trace: Option<DecisionTrace>,
 }
 
 pub fn evaluate_spectral_vision(
     raw_bands: &[(i32, f64, f64)],
-    stability_score: f64,
-    confidence_score: f64,
-    artifact_epoch_fraction: f64,
+    inputs: SpectralVisionInputs<'_>,
     params: &SpectralVisionParams,
     gov: &GovernanceStateV1,
 ) -> SpectralVisionDecision {
-    let band_profile = compute_band_safety(raw_bands, params);
-    let hygiene = compute_spectral_hygiene(
-        &band_profile,
-        artifact_epoch_fraction,
-        params.safety_shigh,
+    evaluate_spectral_vision_fingerprinted(raw_bands, inputs, params, &params.fingerprint(), gov)
+}
+
+/// As [`evaluate_spectral_vision`], with `params_fingerprint` precomputed
+/// by callers that evaluate many objects under the same params.
+pub fn evaluate_spectral_vision_fingerprinted(
+    raw_bands: &[(i32, f64, f64)],
+    inputs: SpectralVisionInputs<'_>,
+    params: &SpectralVisionParams,
+    params_fingerprint: &str,
+    gov: &GovernanceStateV1,
+) -> SpectralVisionDecision {
+    let no_artifacts = ArtifactProfile::default();
+    let no_dependency = BandDependency::default();
+    let exact = InputUncertainty::default();
+    let artifacts = inputs.artifacts.unwrap_or(&no_artifacts);
+    let dependency = inputs.dependency.unwrap_or(&no_dependency);
+    let uncertainty = inputs.uncertainty.unwrap_or(&exact);
+    let (stability_score, confidence_score) = (inputs.stability_score, inputs.confidence_score);
+    let band_profile = compute_band_safety_uncertain(raw_bands, uncertainty, params);
+    let sleep_gate = inputs.sleep.map(|s| s.gate(&params.sleep_gate));
+    let hygiene = compute_spectral_hygiene(&band_profile, artifacts, params.safety_shigh);
+    let band_dependency = assess_band_dependency(&band_profile, dependency, params);
+
+    let factor = band_dependency.promotion_factor;
+    let promotion_score = factor
+        * compute_promotion_score(stability_score, confidence_score, band_profile.safety_min, params);
+    let promotion_variance = factor.powi(2)
+        * compute_promotion_variance(
+            uncertainty.stability_variance,
+            uncertainty.confidence_variance,
+            band_profile.safety_min_variance,
+            params,
+        );
+    // Promotion is decided first but traced after the depth rules.
+    let mut promotion_steps = StepLog::new(params.trace);
+    let gated_score = params.decision_bound.apply(promotion_score, promotion_variance);
+    let mut promotion_passed = passes_promotion_gate(gated_score, params);
+    promotion_steps.gate(
+        "spectral_vision.promotion_threshold",
+        !promotion_passed,
+        EnforcementMode::Strict,
+        || format!("promotionscore {:.3} vs threshold {:.3}", gated_score, params.promotion_threshold),
     );
 
-    let promotion_score =
-        compute_promotion_score(stability_score, confidence_score, band_profile.safety_min, params);
-    let promotion_passed = passes_promotion_gate(promotion_score, params);
+    let mut violations = Vec::new();
+    let mut promotion_block_reason = None;
+    let dependent = band_dependency.high_share > params.high_dependency_max;
+    // Non-waivable: a HIGH-band dependency blocks promotion in every mode.
+    promotion_steps.gate(
+        "spectral_vision.high_band_dependency",
+        dependent,
+        EnforcementMode::Strict,
+        || {
+            format!(
+                "HIGH-band share {:.3} vs max {:.3}",
+                band_dependency.high_share, params.high_dependency_max
+            )
+        },
+    );
+    if dependent {
+        let reason = format!(
+            "HIGH-band dependency share {:.3} exceeds {:.3}",
+            band_dependency.high_share, params.high_dependency_max
+        );
+        promotion_passed = PolicyViolation::enforce(
+            "spectral_vision.high_band_dependency",
+            reason.clone(),
+            false,
+            &mut violations,
+        );
+        promotion_block_reason = Some(reason);
+    }
 
-    let excavation_depth = compute_excavation_depth(&band_profile, gov, params);
+    let mut steps = StepLog::new(params.trace);
+    let excavation_depth = excavation_depth_logged(
+        &band_profile,
+        gov,
+        sleep_gate.as_ref(),
+        params,
+        &mut violations,
+        &mut steps,
+    );
+    steps.append(promotion_steps);
 
-    SpectralVisionDecision {
+    let mut decision = SpectralVisionDecision {
         band_profile,
         hygiene,
         excavation_depth,
         promotion_score,
+        promotion_variance,
         promotion_passed,
+        band_dependency,
+        promotion_block_reason,
+        sleep_gate,
+        violations,
+        params_fingerprint: params_fingerprint.to_string(),
+        trace: None,
+    };
+    if params.trace {
+        decision.trace = Some(trace::trace_decision(
+            &decision,
+            steps.into_steps(),
+            stability_score,
+            confidence_score,
+            artifacts,
+            params,
+        ));
     }
+    decision
 }
 
 // Optional: convenience ordering so you can prioritize higher-depth objects.
@@ -318,3 +984,204 @@ impl PartialOrd for ExcavationDepth {
         Some(self.cmp(other))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_names_and_safety_min_band() {
+        let params = SpectralVisionParams::default();
+        let profile = compute_band_safety(&[(0, 0.2, 0.1), (4, 0.5, 0.2), (9, 0.1, 0.1)], &params);
+
+        let names: Vec<_> = profile.bands.iter().map(|b| b.band_name.as_str()).collect();
+        assert_eq!(names, ["delta", "gamma", "band9"]);
+        assert_eq!(profile.safety_min_band.as_deref(), Some("gamma"));
+        assert!((profile.safety_min - 0.3).abs() < 1e-12);
+        assert_eq!(params.bands.band_for_hz(10.0).unwrap().name, "alpha");
+    }
+
+    #[test]
+    fn test_per_band_hazard_and_weight_overrides() {
+        let mut params = SpectralVisionParams::default();
+        // s_B = 0.5 for both bands: Elevated under the global cut-offs.
+        let raw = [(0, 0.3, 0.2), (4, 0.3, 0.2)];
+        let profile = compute_band_safety(&raw, &params);
+        assert!(profile.bands.iter().all(|b| b.hazard_class == BandHazardClass::Elevated));
+
+        let delta = params.bands.by_name_mut("delta").unwrap();
+        delta.hazard_elevated_max = Some(0.3);
+        delta.hazard_safe_min = Some(0.45);
+        params.bands.insert(BandDefinition::new(4, "gamma", 30.0, 100.0).with_hazard(0.6, 0.8));
+        let profile = compute_band_safety(&raw, &params);
+        assert_eq!(profile.bands[0].hazard_class, BandHazardClass::Safe);
+        assert_eq!(profile.bands[1].hazard_class, BandHazardClass::High);
+
+        params.bands.by_name_mut("gamma").unwrap().weight = 3.0;
+        let profile = compute_band_safety(&[(0, 0.0, 0.0), (4, 0.5, 0.5)], &params);
+        assert!((profile.safety_mean - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hygiene_reduces_to_scalar_formula_without_masks() {
+        let params = SpectralVisionParams::default();
+        let profile = compute_band_safety(&[(0, 0.2, 0.1), (2, 0.1, 0.1)], &params);
+        let hygiene =
+            compute_spectral_hygiene(&profile, &ArtifactProfile::from_epoch_fraction(0.25), 0.7);
+        assert!((hygiene.band_quality - profile.safety_mean * 0.75).abs() < 1e-12);
+        assert!(hygiene.bands.iter().all(|b| b.loss_share == 0.0));
+    }
+
+    #[test]
+    fn test_correlated_high_power_band_lowers_quality() {
+        let params = SpectralVisionParams::default();
+        let profile = compute_band_safety(&[(0, 0.1, 0.0), (4, 0.6, 0.0)], &params);
+        let clean = compute_spectral_hygiene(&profile, &ArtifactProfile::default(), 0.7);
+
+        let artifacts = ArtifactProfile {
+            epoch_fraction: 0.0,
+            correlation: 0.5,
+            band_masks: vec![(0, 0.0)],
+        };
+        let dirty = compute_spectral_hygiene(&profile, &artifacts, 0.7);
+        assert!(dirty.band_quality < clean.band_quality);
+        let gamma = &dirty.bands[1];
+        assert_eq!(gamma.band_name, "gamma");
+        assert!((gamma.artifact_load - 0.3).abs() < 1e-12);
+        assert!(gamma.loss_share > dirty.bands[0].loss_share);
+    }
+
+    #[test]
+    fn test_band_dependency_scales_promotion() {
+        let params = SpectralVisionParams::default();
+        let gov = GovernanceStateV1::active_governed();
+        // delta Safe (0.9), theta Elevated (0.5), gamma High (0.2).
+        let raw = [(0, 0.05, 0.05), (1, 0.3, 0.2), (4, 0.6, 0.2)];
+        let inputs = SpectralVisionInputs {
+            stability_score: 1.0,
+            confidence_score: 1.0,
+            ..Default::default()
+        };
+        let base = evaluate_spectral_vision(&raw, inputs, &params, &gov);
+        assert_eq!(base.band_dependency.promotion_factor, 1.0);
+
+        let dependency = BandDependency {
+            weights: vec![(0, 2.0), (1, 1.0), (4, 1.0), (7, 5.0)],
+        };
+        let inputs = SpectralVisionInputs {
+            dependency: Some(&dependency),
+            ..inputs
+        };
+        let d = evaluate_spectral_vision(&raw, inputs, &params, &gov);
+        assert!((d.band_dependency.high_share - 0.25).abs() < 1e-12);
+        assert!((d.band_dependency.elevated_share - 0.25).abs() < 1e-12);
+        assert!((d.band_dependency.promotion_factor - 0.6875).abs() < 1e-12);
+        assert!((d.promotion_score - base.promotion_score * 0.6875).abs() < 1e-12);
+        assert!(d.promotion_block_reason.is_none());
+    }
+
+    #[test]
+    fn test_high_band_dependency_blocks_promotion() {
+        let mut params = SpectralVisionParams::default();
+        let gov = GovernanceStateV1::active_governed();
+        params.dependency_weight_high = 1.0;
+        let raw = [(0, 0.0, 0.0), (4, 0.7, 0.1)];
+        let dependency = BandDependency {
+            weights: vec![(0, 1.0), (4, 3.0)],
+        };
+        let inputs = SpectralVisionInputs {
+            stability_score: 1.0,
+            confidence_score: 1.0,
+            dependency: Some(&dependency),
+            ..Default::default()
+        };
+
+        let d = evaluate_spectral_vision(&raw, inputs, &params, &gov);
+        assert!(d.promotion_score >= params.promotion_threshold);
+        assert!(!d.promotion_passed);
+        assert!(d.promotion_block_reason.unwrap().contains("0.750"));
+        assert_eq!(d.violations[0].gate, "spectral_vision.high_band_dependency");
+
+        for mode in [EnforcementMode::Advisory, EnforcementMode::Disabled] {
+            params.enforcement_mode = mode;
+            let d = evaluate_spectral_vision(&raw, inputs, &params, &gov);
+            assert!(!d.promotion_passed);
+            assert!(d.promotion_block_reason.is_some());
+            assert!(d.violations[0].enforced);
+        }
+    }
+
+    #[test]
+    fn test_closed_sleep_gate_limits_depth() {
+        let params = SpectralVisionParams::default();
+        let gov = GovernanceStateV1::active_governed();
+        let raw = [(0, 0.1, 0.1), (2, 0.1, 0.1)];
+        let deep = SleepStateSnapshot::new(0.0, 0.9, 0.0, 0.1);
+        let uncertain = SleepStateSnapshot::new(0.0, 0.9, 0.0, 0.7);
+        let inputs = SpectralVisionInputs {
+            stability_score: 1.0,
+            confidence_score: 1.0,
+            ..Default::default()
+        };
+        let with_sleep = |sleep| SpectralVisionInputs {
+            sleep: Some(sleep),
+            ..inputs
+        };
+
+        let d = evaluate_spectral_vision(&raw, inputs, &params, &gov);
+        assert_eq!(d.excavation_depth, ExcavationDepth::DigFull);
+        let d = evaluate_spectral_vision(&raw, with_sleep(&deep), &params, &gov);
+        assert_eq!(d.excavation_depth, ExcavationDepth::DigFull);
+        assert!(d.sleep_gate.unwrap().open);
+
+        let d = evaluate_spectral_vision(&raw, with_sleep(&uncertain), &params, &gov);
+        assert_eq!(d.excavation_depth, ExcavationDepth::DigLight);
+        assert_eq!(d.violations[0].gate, "spectral_vision.sleep_gate");
+        assert!(!d.sleep_gate.unwrap().open);
+
+        // Non-waivable: advisory and disabled modes still cap depth.
+        for mode in [EnforcementMode::Advisory, EnforcementMode::Disabled] {
+            let params = SpectralVisionParams {
+                enforcement_mode: mode,
+                ..params.clone()
+            };
+            let d = evaluate_spectral_vision(&raw, with_sleep(&uncertain), &params, &gov);
+            assert_eq!(d.excavation_depth, ExcavationDepth::DigLight);
+            assert!(d.violations[0].enforced);
+        }
+    }
+
+    #[test]
+    fn test_pessimistic_bound_keeps_noisy_object_out_of_dig_full() {
+        let mut params = SpectralVisionParams::default();
+        let gov = GovernanceStateV1::active_governed();
+        // s_B = 0.72, just above safety_shigh.
+        let raw = [(0, 0.14, 0.14), (2, 0.1, 0.1)];
+        let uncertainty = InputUncertainty {
+            bands: vec![(0, 0.0004, 0.0005)],
+            stability_variance: 0.01,
+            confidence_variance: 0.0,
+        };
+        let inputs = SpectralVisionInputs {
+            stability_score: 0.9,
+            confidence_score: 0.9,
+            uncertainty: Some(&uncertainty),
+            ..Default::default()
+        };
+        let eval = |params: &SpectralVisionParams| evaluate_spectral_vision(&raw, inputs, params, &gov);
+
+        let d = eval(&params);
+        assert_eq!(d.excavation_depth, ExcavationDepth::DigFull);
+        assert!((d.band_profile.safety_min_variance - 0.0009).abs() < 1e-12);
+        assert!(d.hygiene.band_quality_variance > 0.0);
+        assert!((d.promotion_variance - (0.16 * 0.01 + 0.04 * 0.0009)).abs() < 1e-12);
+        assert!(d.promotion_passed);
+
+        params.decision_bound = DecisionBound::Pessimistic { z: 1.645 };
+        let d = eval(&params);
+        // 0.72 − 1.645 · 0.03 ≈ 0.67 < 0.7.
+        assert_eq!(d.excavation_depth, ExcavationDepth::DigLight);
+        assert!(!d.promotion_passed);
+        assert!((variance_from_interval(0.6, 0.8, 2.0) - 0.0025).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,462 @@
+//! Batch, multi-threaded spectral-vision over a `SpectralRealityModel` catalog.
+//!
+//! Results are written into each object's `signature.extra` under
+//! `bandsafetyprofile`, `spectralhygiene`, `excavationdepth` and
+//! `catalogpromotiongate`, as described on [`SpectralVisionDecision`].
+//! Catalog runs need the [`QueryIntent::Annotate`] intent; runs against a
+//! [`CatalogStore`] journal every annotated object.
+
+use std::collections::HashMap;
+use std::thread;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::governance::{GovernanceMode, GovernanceStateV1};
+use crate::sleep_state::SleepStateSnapshot;
+use crate::spectral_reality_model::store::{CatalogStore, StoreError};
+use crate::spectral_reality_model::{
+    QueryContext, QueryError, QueryIntent, SpectralObject, SpectralRealityModel,
+};
+use crate::spectral_vision::{
+    evaluate_spectral_vision_fingerprinted, ArtifactProfile, BandDependency, BandHazardClass,
+    ExcavationDepth, InputUncertainty, SpectralVisionDecision, SpectralVisionInputs,
+    SpectralVisionParams,
+};
+
+/// Errors running a catalog batch.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum BatchError {
+    #[error("query context mode {context:?} does not match governance mode {governance:?}")]
+    ModeMismatch {
+        context: GovernanceMode,
+        governance: GovernanceMode,
+    },
+    #[error(transparent)]
+    Query(#[from] QueryError),
+    #[error(transparent)]
+    Store(#[from] StoreError),
+}
+
+/// Per-object inputs; stability and confidence come from the object itself.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ObjectBandInputs {
+    /// (band_index, μ_B, σ_B).
+    pub raw_bands: Vec<(i32, f64, f64)>,
+    #[serde(default)]
+    pub artifacts: ArtifactProfile,
+    #[serde(default)]
+    pub dependency: BandDependency,
+    #[serde(default)]
+    pub sleep: Option<SleepStateSnapshot>,
+    #[serde(default)]
+    pub uncertainty: Option<InputUncertainty>,
+}
+
+/// Batch settings.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct BatchOptions {
+    /// Worker threads; 0 means the available parallelism.
+    pub threads: usize,
+}
+
+/// Objects per excavation depth.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DepthCounts {
+    pub sniff: usize,
+    pub dig_light: usize,
+    pub dig_full: usize,
+}
+
+/// Band entries per hazard class, summed over all evaluated objects.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HazardCounts {
+    pub safe: usize,
+    pub elevated: usize,
+    pub high: usize,
+}
+
+/// Outcome of a batch run.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub evaluated: usize,
+    /// Objects without band inputs; left untouched.
+    pub skipped: usize,
+    pub promoted: usize,
+    pub depth: DepthCounts,
+    pub hazard: HazardCounts,
+    pub params_fingerprint: String,
+}
+
+impl BatchSummary {
+    fn record(&mut self, decision: &SpectralVisionDecision) {
+        self.evaluated += 1;
+        self.promoted += usize::from(decision.promotion_passed);
+        match decision.excavation_depth {
+            ExcavationDepth::Sniff => self.depth.sniff += 1,
+            ExcavationDepth::DigLight => self.depth.dig_light += 1,
+            ExcavationDepth::DigFull => self.depth.dig_full += 1,
+        }
+        for band in &decision.band_profile.bands {
+            match band.hazard_class {
+                BandHazardClass::Safe => self.hazard.safe += 1,
+                BandHazardClass::Elevated => self.hazard.elevated += 1,
+                BandHazardClass::High => self.hazard.high += 1,
+            }
+        }
+    }
+
+    fn merge(&mut self, other: BatchSummary) {
+        self.evaluated += other.evaluated;
+        self.skipped += other.skipped;
+        self.promoted += other.promoted;
+        self.depth.sniff += other.depth.sniff;
+        self.depth.dig_light += other.depth.dig_light;
+        self.depth.dig_full += other.depth.dig_full;
+        self.hazard.safe += other.hazard.safe;
+        self.hazard.elevated += other.hazard.elevated;
+        self.hazard.high += other.hazard.high;
+    }
+}
+
+/// Write a decision into `signature.extra`, replacing a non-object `extra`.
+pub fn write_signature_extra(object: &mut SpectralObject, decision: &SpectralVisionDecision) {
+    let extra = object
+        .signature
+        .entry("extra".to_string())
+        .or_insert_with(|| json!({}));
+    if !extra.is_object() {
+        *extra = json!({});
+    }
+    let Value::Object(extra) = extra else {
+        return;
+    };
+    extra.insert("bandsafetyprofile".into(), to_value(&decision.band_profile));
+    extra.insert("spectralhygiene".into(), to_value(&decision.hygiene));
+    extra.insert(
+        "excavationdepth".into(),
+        to_value(&decision.excavation_depth),
+    );
+    extra.insert(
+        "catalogpromotiongate".into(),
+        json!({
+            "promotion_score": decision.promotion_score,
+            "promotion_variance": decision.promotion_variance,
+            "passed": decision.promotion_passed,
+            "block_reason": decision.promotion_block_reason,
+            "params_fingerprint": decision.params_fingerprint,
+        }),
+    );
+    object.updated_at = Utc::now();
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+/// Evaluate and annotate objects across `options.threads` workers.
+pub fn evaluate_objects(
+    objects: &mut [&mut SpectralObject],
+    inputs: &HashMap<String, ObjectBandInputs>,
+    params: &SpectralVisionParams,
+    gov: &GovernanceStateV1,
+    options: &BatchOptions,
+) -> BatchSummary {
+    let threads = match options.threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let chunk = objects.len().div_ceil(threads).max(1);
+    let fingerprint = params.fingerprint();
+    let fingerprint = fingerprint.as_str();
+
+    let mut summary = thread::scope(|scope| {
+        let workers: Vec<_> = objects
+            .chunks_mut(chunk)
+            .map(|part| {
+                scope.spawn(move || {
+                    let mut summary = BatchSummary::default();
+                    for object in part.iter_mut() {
+                        let Some(input) = inputs.get(&object.id) else {
+                            summary.skipped += 1;
+                            continue;
+                        };
+                        let decision = evaluate_spectral_vision_fingerprinted(
+                            &input.raw_bands,
+                            SpectralVisionInputs {
+                                stability_score: object.stability,
+                                confidence_score: object.confidence,
+                                artifacts: Some(&input.artifacts),
+                                dependency: Some(&input.dependency),
+                                sleep: input.sleep.as_ref(),
+                                uncertainty: input.uncertainty.as_ref(),
+                            },
+                            params,
+                            fingerprint,
+                            gov,
+                        );
+                        write_signature_extra(object, &decision);
+                        summary.record(&decision);
+                    }
+                    summary
+                })
+            })
+            .collect();
+
+        let mut total = BatchSummary::default();
+        for worker in workers {
+            total.merge(
+                worker
+                    .join()
+                    .expect("spectral-vision batch worker panicked"),
+            );
+        }
+        total
+    });
+    summary.params_fingerprint = fingerprint.to_string();
+    summary
+}
+
+/// Evaluate every catalog object that has band inputs.
+///
+/// `ctx` must carry the [`QueryIntent::Annotate`] intent and the same mode
+/// as `gov`; the write is authorized before anything is evaluated, so a
+/// Dormant catalog, a read-only intent or a guarded intent is refused.
+pub fn evaluate_catalog(
+    model: &mut SpectralRealityModel,
+    inputs: &HashMap<String, ObjectBandInputs>,
+    params: &SpectralVisionParams,
+    gov: &GovernanceStateV1,
+    ctx: &QueryContext,
+    options: &BatchOptions,
+) -> Result<BatchSummary, BatchError> {
+    check_mode(gov, ctx)?;
+    let mut objects = model.objects_mut(ctx)?;
+    Ok(evaluate_objects(&mut objects, inputs, params, gov, options))
+}
+
+/// As [`evaluate_catalog`], journaling each annotated object through the
+/// store so the results survive a reopen.
+pub fn evaluate_store(
+    store: &mut CatalogStore,
+    inputs: &HashMap<String, ObjectBandInputs>,
+    params: &SpectralVisionParams,
+    gov: &GovernanceStateV1,
+    ctx: &QueryContext,
+    options: &BatchOptions,
+) -> Result<BatchSummary, BatchError> {
+    check_mode(gov, ctx)?;
+    let mut objects = store.checkout(ctx)?;
+    let total = objects.len();
+    objects.retain(|o| inputs.contains_key(&o.id));
+    let mut summary = evaluate_objects(
+        &mut objects.iter_mut().collect::<Vec<_>>(),
+        inputs,
+        params,
+        gov,
+        options,
+    );
+    summary.skipped += total - objects.len();
+    for object in objects {
+        store.upsert(object)?;
+    }
+    Ok(summary)
+}
+
+fn check_mode(gov: &GovernanceStateV1, ctx: &QueryContext) -> Result<(), BatchError> {
+    if gov.mode == ctx.mode {
+        Ok(())
+    } else {
+        Err(BatchError::ModeMismatch {
+            context: ctx.mode,
+            governance: gov.mode,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spectral_reality_model::store::StoreOptions;
+    use crate::spectral_reality_model::{Origin, SpectralKind};
+
+    fn object(stability: f64) -> SpectralObject {
+        let mut o = SpectralObject::new(
+            SpectralKind::TracePattern,
+            Origin {
+                domain: "shop.example.com".to_string(),
+                system: "checkout".to_string(),
+                run_id: "r1".to_string(),
+                modality: "eeg".to_string(),
+            },
+            HashMap::new(),
+        );
+        o.touch(Some(stability), None, Some(stability), None, None);
+        o
+    }
+
+    #[test]
+    fn test_catalog_batch_annotates_and_summarizes() {
+        let mut model = SpectralRealityModel::default();
+        let mut inputs = HashMap::new();
+        // safety_min 0.9 (DigFull), 0.5 (DigLight), 0.2 (Sniff), plus one without inputs.
+        for (i, mu) in [0.1, 0.5, 0.8].iter().enumerate() {
+            let id = model.upsert(object(0.8)).id.clone();
+            let raw_bands = if i == 0 {
+                vec![(0, *mu, 0.0), (2, 0.1, 0.0)]
+            } else {
+                vec![(0, *mu, 0.0)]
+            };
+            inputs.insert(
+                id,
+                ObjectBandInputs {
+                    raw_bands,
+                    ..ObjectBandInputs::default()
+                },
+            );
+        }
+        model.upsert(object(0.8));
+
+        let ctx = QueryContext::new(QueryIntent::Annotate, GovernanceMode::ActiveGoverned);
+        let params = SpectralVisionParams::default();
+        let options = BatchOptions { threads: 3 };
+        let summary = evaluate_catalog(
+            &mut model,
+            &inputs,
+            &params,
+            &GovernanceStateV1::active_governed(),
+            &ctx,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!((summary.evaluated, summary.skipped), (3, 1));
+        assert_eq!(
+            summary.depth,
+            DepthCounts {
+                sniff: 1,
+                dig_light: 1,
+                dig_full: 1
+            }
+        );
+        assert_eq!(
+            summary.hazard,
+            HazardCounts {
+                safe: 2,
+                elevated: 1,
+                high: 1
+            }
+        );
+        assert_eq!(summary.promoted, 1);
+        assert_eq!(summary.params_fingerprint, params.fingerprint());
+
+        let snapshot = model.snapshot(&ctx).unwrap();
+        let annotated: Vec<&Value> = snapshot
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|o| o["signature"].get("extra"))
+            .collect();
+        assert_eq!(annotated.len(), 3);
+        for extra in annotated {
+            assert!(extra["bandsafetyprofile"]["safety_min"].is_number());
+            assert!(extra["spectralhygiene"]["band_quality"].is_number());
+            assert!(extra["excavationdepth"].is_string());
+            assert_eq!(
+                extra["catalogpromotiongate"]["params_fingerprint"],
+                json!(params.fingerprint())
+            );
+        }
+    }
+
+    #[test]
+    fn test_dormant_read_only_and_mismatched_runs_are_refused() {
+        let mut model = SpectralRealityModel::default();
+        model.upsert(object(0.9));
+        let dormant = GovernanceStateV1 {
+            mode: GovernanceMode::Dormant,
+            ..GovernanceStateV1::active_governed()
+        };
+        let run = |model: &mut SpectralRealityModel, gov: &GovernanceStateV1, ctx| {
+            evaluate_catalog(
+                model,
+                &HashMap::new(),
+                &SpectralVisionParams::default(),
+                gov,
+                &ctx,
+                &BatchOptions::default(),
+            )
+        };
+
+        for intent in [
+            QueryIntent::Annotate,
+            QueryIntent::IntegrityCheck,
+            QueryIntent::ZoningAudit,
+        ] {
+            let ctx = QueryContext::new(intent, GovernanceMode::Dormant);
+            assert!(matches!(
+                run(&mut model, &dormant, ctx),
+                Err(BatchError::Query(QueryError::Denied { .. }))
+            ));
+        }
+        let audit = QueryContext::new(QueryIntent::IntegrityCheck, GovernanceMode::ActiveGoverned);
+        assert!(matches!(
+            run(&mut model, &GovernanceStateV1::active_governed(), audit),
+            Err(BatchError::Query(QueryError::Denied { .. }))
+        ));
+        assert_eq!(model.denials().len(), 4);
+
+        let annotate = QueryContext::new(QueryIntent::Annotate, GovernanceMode::ActiveGoverned);
+        assert_eq!(
+            run(&mut model, &dormant, annotate),
+            Err(BatchError::ModeMismatch {
+                context: GovernanceMode::ActiveGoverned,
+                governance: GovernanceMode::Dormant
+            })
+        );
+    }
+
+    #[test]
+    fn test_store_batch_is_journaled() {
+        let dir = std::env::temp_dir().join(format!("batch-store-{}", uuid::Uuid::new_v4()));
+        let options = StoreOptions {
+            snapshot_every: 0,
+            fsync: false,
+        };
+        let (mut store, _) = CatalogStore::open(&dir, options.clone()).unwrap();
+        let id = store.upsert(object(0.8)).unwrap().id.clone();
+        store.upsert(object(0.8)).unwrap();
+        let inputs = HashMap::from([(
+            id.clone(),
+            ObjectBandInputs {
+                raw_bands: vec![(0, 0.1, 0.0)],
+                ..ObjectBandInputs::default()
+            },
+        )]);
+        let ctx = QueryContext::new(QueryIntent::Annotate, GovernanceMode::ActiveGoverned);
+        let summary = evaluate_store(
+            &mut store,
+            &inputs,
+            &SpectralVisionParams::default(),
+            &GovernanceStateV1::active_governed(),
+            &ctx,
+            &BatchOptions::default(),
+        )
+        .unwrap();
+        assert_eq!((summary.evaluated, summary.skipped), (1, 1));
+        assert_eq!(store.seq(), 3);
+        drop(store);
+
+        let (store, report) = CatalogStore::open(&dir, options).unwrap();
+        assert_eq!(report.replayed, 3);
+        let lookup = QueryContext::new(QueryIntent::CatalogLookup, GovernanceMode::ActiveGoverned);
+        let restored = store.model().get_by_id(&id, &lookup).unwrap().unwrap();
+        assert_eq!(
+            restored.signature["extra"]["excavationdepth"],
+            json!(ExcavationDepth::DigFull)
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
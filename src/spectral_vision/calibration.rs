@@ -0,0 +1,477 @@
+//! Calibration of the promotion weights and threshold from reviewer outcomes.
+//!
+//! A constrained logistic regression
+//!   P(accept) = σ(β₀ + β₁·stability + β₂·confidence + β₃·safety_min), β₁..₃ ≥ 0
+//! is fitted to labelled NDJSON records. Since P depends on the inputs only
+//! through Σβ·promotionscore, the weights are w_i = β_i / Σβ and the
+//! threshold at P = 0.5 is −β₀ / Σβ.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::spectral_vision::profile::ParamsError;
+use crate::spectral_vision::{clamp01, SpectralVisionParams};
+
+/// Calibration file format version.
+pub const PROMOTION_CALIBRATION_FORMAT_VERSION: u32 = 1;
+
+/// Errors reading outcomes or fitting/loading a promotion calibration.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum PromotionCalibrationError {
+    #[error("cannot read {path}: {message}")]
+    Io { path: String, message: String },
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+    #[error("invalid JSON: {0}")]
+    Json(String),
+    #[error("calibration format version {found} is not supported (expected {expected})")]
+    UnsupportedVersion { found: u32, expected: u32 },
+    #[error("need both accepted and rejected outcomes (accepted {accepted}, rejected {rejected})")]
+    SingleClass { accepted: usize, rejected: usize },
+    #[error("fitted weights are all zero; the inputs do not separate the outcomes")]
+    NoSignal,
+    #[error("calibrated params are invalid: {0}")]
+    Params(#[from] ParamsError),
+}
+
+impl PromotionCalibrationError {
+    fn io(path: &Path, e: std::io::Error) -> Self {
+        PromotionCalibrationError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// One reviewed spectral-object.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LabelledOutcome {
+    #[serde(default)]
+    pub spectral_id: Option<String>,
+    pub stability: f64,
+    pub confidence: f64,
+    pub safety_min: f64,
+    /// True if the reviewer accepted the object into the catalog.
+    pub accepted: bool,
+}
+
+impl LabelledOutcome {
+    fn features(&self) -> [f64; 3] {
+        [
+            clamp01(self.stability),
+            clamp01(self.confidence),
+            clamp01(self.safety_min),
+        ]
+    }
+}
+
+/// Parse one [`LabelledOutcome`] per line; blank lines are skipped.
+pub fn parse_outcomes(src: &str) -> Result<Vec<LabelledOutcome>, PromotionCalibrationError> {
+    src.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            serde_json::from_str(l).map_err(|e| PromotionCalibrationError::Parse {
+                line: i + 1,
+                message: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Read an NDJSON outcome file.
+pub fn load_outcomes(
+    path: impl AsRef<Path>,
+) -> Result<Vec<LabelledOutcome>, PromotionCalibrationError> {
+    let path = path.as_ref();
+    let src = std::fs::read_to_string(path).map_err(|e| PromotionCalibrationError::io(path, e))?;
+    parse_outcomes(&src)
+}
+
+/// Settings for [`calibrate_promotion`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PromotionCalibrationParams {
+    /// Gradient-descent iterations.
+    pub iterations: usize,
+    pub learning_rate: f64,
+    /// L2 penalty on β₁..₃; keeps separable data finite.
+    pub l2: f64,
+    /// If set, pick the lowest threshold reaching this precision instead of P = 0.5.
+    pub target_precision: Option<f64>,
+    /// Precision/recall curve resolution: thresholds i / steps.
+    pub threshold_steps: usize,
+    /// Number of equal-width reliability bins.
+    pub calibration_bins: usize,
+}
+
+impl Default for PromotionCalibrationParams {
+    fn default() -> Self {
+        Self {
+            iterations: 5000,
+            learning_rate: 1.0,
+            l2: 1e-4,
+            target_precision: None,
+            threshold_steps: 20,
+            calibration_bins: 10,
+        }
+    }
+}
+
+/// Gate behaviour at one promotion threshold.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PrecisionRecallPoint {
+    pub threshold: f64,
+    pub precision: f64,
+    pub recall: f64,
+    /// Fraction of all objects promoted.
+    pub promoted_fraction: f64,
+}
+
+/// One reliability bin: predicted acceptance vs observed acceptance.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReliabilityBin {
+    pub p_low: f64,
+    pub p_high: f64,
+    pub count: usize,
+    pub mean_predicted: f64,
+    pub observed_rate: f64,
+}
+
+/// Versioned promotion calibration file.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PromotionCalibration {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Free-form source description, e.g. the review export.
+    pub source: String,
+    pub samples: usize,
+    pub w_stability: f64,
+    pub w_confidence: f64,
+    pub w_safety_min: f64,
+    pub promotion_threshold: f64,
+    /// Logistic coefficients [β₀, β₁, β₂, β₃].
+    pub coefficients: [f64; 4],
+    pub log_loss: f64,
+    /// Precision and recall at `promotion_threshold`.
+    pub precision: f64,
+    pub recall: f64,
+    pub pr_curve: Vec<PrecisionRecallPoint>,
+    pub calibration_curve: Vec<ReliabilityBin>,
+}
+
+impl PromotionCalibration {
+    /// Overwrite the promotion weights and threshold in `params`.
+    pub fn apply(&self, params: &mut SpectralVisionParams) {
+        params.w_stability = self.w_stability;
+        params.w_confidence = self.w_confidence;
+        params.w_safety_min = self.w_safety_min;
+        params.promotion_threshold = self.promotion_threshold;
+    }
+
+    /// Fitted acceptance probability for a (weighted) promotionscore.
+    pub fn acceptance_probability(&self, promotion_score: f64) -> f64 {
+        let slope: f64 = self.coefficients[1..].iter().sum();
+        sigmoid(self.coefficients[0] + slope * promotion_score)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Parse a calibration file, rejecting other format versions.
+    pub fn from_json(src: &str) -> Result<Self, PromotionCalibrationError> {
+        #[derive(Deserialize)]
+        struct Version {
+            format_version: u32,
+        }
+        let v: Version = serde_json::from_str(src)
+            .map_err(|e| PromotionCalibrationError::Json(e.to_string()))?;
+        if v.format_version != PROMOTION_CALIBRATION_FORMAT_VERSION {
+            return Err(PromotionCalibrationError::UnsupportedVersion {
+                found: v.format_version,
+                expected: PROMOTION_CALIBRATION_FORMAT_VERSION,
+            });
+        }
+        serde_json::from_str(src).map_err(|e| PromotionCalibrationError::Json(e.to_string()))
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), PromotionCalibrationError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json()).map_err(|e| PromotionCalibrationError::io(path, e))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PromotionCalibrationError> {
+        let path = path.as_ref();
+        let src =
+            std::fs::read_to_string(path).map_err(|e| PromotionCalibrationError::io(path, e))?;
+        Self::from_json(&src)
+    }
+}
+
+impl SpectralVisionParams {
+    /// These params with the promotion weights and threshold replaced from a
+    /// calibration file written by [`calibrate_promotion`]; every other
+    /// setting is kept, and the result is validated like any loaded params.
+    pub fn with_promotion_calibration(
+        mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, PromotionCalibrationError> {
+        PromotionCalibration::load(path)?.apply(&mut self);
+        self.validate()?;
+        Ok(self)
+    }
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Projected gradient descent on mean log-loss + l2·|β₁..₃|², with β₁..₃ ≥ 0.
+fn fit_logistic(xs: &[[f64; 3]], ys: &[f64], params: &PromotionCalibrationParams) -> [f64; 4] {
+    let n = xs.len() as f64;
+    let mut beta = [0.0; 4];
+    for _ in 0..params.iterations {
+        let mut grad = [0.0; 4];
+        for (x, y) in xs.iter().zip(ys) {
+            let z = beta[0] + beta[1] * x[0] + beta[2] * x[1] + beta[3] * x[2];
+            let err = sigmoid(z) - y;
+            grad[0] += err;
+            for k in 0..3 {
+                grad[k + 1] += err * x[k];
+            }
+        }
+        beta[0] -= params.learning_rate * grad[0] / n;
+        for k in 1..4 {
+            let g = grad[k] / n + 2.0 * params.l2 * beta[k];
+            beta[k] = (beta[k] - params.learning_rate * g).max(0.0);
+        }
+    }
+    beta
+}
+
+fn precision_recall(scores: &[f64], ys: &[f64], threshold: f64) -> PrecisionRecallPoint {
+    let (mut tp, mut fp, mut positives) = (0usize, 0usize, 0usize);
+    for (s, y) in scores.iter().zip(ys) {
+        let accepted = *y > 0.5;
+        positives += accepted as usize;
+        if *s >= threshold {
+            if accepted {
+                tp += 1;
+            } else {
+                fp += 1;
+            }
+        }
+    }
+    let rate = |n: usize, d: usize| if d > 0 { n as f64 / d as f64 } else { 0.0 };
+    PrecisionRecallPoint {
+        threshold,
+        precision: if tp + fp > 0 { rate(tp, tp + fp) } else { 1.0 },
+        recall: rate(tp, positives),
+        promoted_fraction: rate(tp + fp, scores.len()),
+    }
+}
+
+/// Fit promotion weights and threshold to reviewer outcomes.
+///
+/// The threshold is −β₀ / Σβ (P = 0.5), or with `target_precision` the
+/// lowest curve threshold reaching that precision (1.0 if none does).
+pub fn calibrate_promotion(
+    outcomes: &[LabelledOutcome],
+    source: &str,
+    params: &PromotionCalibrationParams,
+) -> Result<PromotionCalibration, PromotionCalibrationError> {
+    let accepted = outcomes.iter().filter(|o| o.accepted).count();
+    let rejected = outcomes.len() - accepted;
+    if accepted == 0 || rejected == 0 {
+        return Err(PromotionCalibrationError::SingleClass { accepted, rejected });
+    }
+
+    let xs: Vec<[f64; 3]> = outcomes.iter().map(LabelledOutcome::features).collect();
+    let ys: Vec<f64> = outcomes
+        .iter()
+        .map(|o| f64::from(u8::from(o.accepted)))
+        .collect();
+    let beta = fit_logistic(&xs, &ys, params);
+    let slope: f64 = beta[1..].iter().sum();
+    if slope <= 0.0 {
+        return Err(PromotionCalibrationError::NoSignal);
+    }
+    let weights = [beta[1] / slope, beta[2] / slope, beta[3] / slope];
+    let scores: Vec<f64> = xs
+        .iter()
+        .map(|x| weights[0] * x[0] + weights[1] * x[1] + weights[2] * x[2])
+        .collect();
+    let probs: Vec<f64> = scores
+        .iter()
+        .map(|s| sigmoid(beta[0] + slope * s))
+        .collect();
+
+    let log_loss = probs
+        .iter()
+        .zip(&ys)
+        .map(|(p, y)| {
+            let p = p.clamp(1e-12, 1.0 - 1e-12);
+            -(y * p.ln() + (1.0 - y) * (1.0 - p).ln())
+        })
+        .sum::<f64>()
+        / ys.len() as f64;
+
+    let steps = params.threshold_steps.max(1);
+    let pr_curve: Vec<PrecisionRecallPoint> = (0..=steps)
+        .map(|i| precision_recall(&scores, &ys, i as f64 / steps as f64))
+        .collect();
+    let promotion_threshold = match params.target_precision {
+        Some(target) => pr_curve
+            .iter()
+            .find(|p| p.precision >= target && p.promoted_fraction > 0.0)
+            .map_or(1.0, |p| p.threshold),
+        None => clamp01(-beta[0] / slope),
+    };
+    let at_threshold = precision_recall(&scores, &ys, promotion_threshold);
+
+    let bins = params.calibration_bins.max(1);
+    let calibration_curve = (0..bins)
+        .map(|b| {
+            let (lo, hi) = (b as f64 / bins as f64, (b + 1) as f64 / bins as f64);
+            let members: Vec<(f64, f64)> = probs
+                .iter()
+                .zip(&ys)
+                .filter(|(p, _)| **p >= lo && (**p < hi || b + 1 == bins))
+                .map(|(p, y)| (*p, *y))
+                .collect();
+            let count = members.len();
+            let mean = |f: fn(&(f64, f64)) -> f64| {
+                if count > 0 {
+                    members.iter().map(f).sum::<f64>() / count as f64
+                } else {
+                    0.0
+                }
+            };
+            ReliabilityBin {
+                p_low: lo,
+                p_high: hi,
+                count,
+                mean_predicted: mean(|m| m.0),
+                observed_rate: mean(|m| m.1),
+            }
+        })
+        .collect();
+
+    Ok(PromotionCalibration {
+        format_version: PROMOTION_CALIBRATION_FORMAT_VERSION,
+        created_at: Utc::now(),
+        source: source.to_string(),
+        samples: outcomes.len(),
+        w_stability: weights[0],
+        w_confidence: weights[1],
+        w_safety_min: weights[2],
+        promotion_threshold,
+        coefficients: beta,
+        log_loss,
+        precision: at_threshold.precision,
+        recall: at_threshold.recall,
+        pr_curve,
+        calibration_curve,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Grid of objects accepted iff 0.1·s + 0.1·c + 0.8·m ≥ 0.6.
+    fn outcomes() -> Vec<LabelledOutcome> {
+        let mut out = Vec::new();
+        for i in 0..8 {
+            for j in 0..8 {
+                for k in 0..8 {
+                    let (s, c, m) = (i as f64 / 7.0, j as f64 / 7.0, k as f64 / 7.0);
+                    out.push(LabelledOutcome {
+                        spectral_id: None,
+                        stability: s,
+                        confidence: c,
+                        safety_min: m,
+                        accepted: 0.1 * s + 0.1 * c + 0.8 * m >= 0.6,
+                    });
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_fit_recovers_dominant_weight() {
+        let cal = calibrate_promotion(&outcomes(), "grid", &PromotionCalibrationParams::default())
+            .unwrap();
+        let sum = cal.w_stability + cal.w_confidence + cal.w_safety_min;
+        assert!((sum - 1.0).abs() < 1e-9);
+        assert!(cal.w_safety_min > 0.6, "{:?}", cal);
+        assert!((cal.promotion_threshold - 0.6).abs() < 0.1);
+        assert!(cal.precision > 0.9 && cal.recall > 0.9);
+        assert_eq!(cal.pr_curve.len(), 21);
+        let binned: usize = cal.calibration_curve.iter().map(|b| b.count).sum();
+        assert_eq!(binned, cal.samples);
+
+        let strict = PromotionCalibrationParams {
+            target_precision: Some(1.0),
+            ..PromotionCalibrationParams::default()
+        };
+        let strict = calibrate_promotion(&outcomes(), "grid", &strict).unwrap();
+        assert_eq!(strict.precision, 1.0);
+        assert!(strict.promotion_threshold >= cal.promotion_threshold);
+    }
+
+    #[test]
+    fn test_calibration_file_loads_into_params() {
+        let cal = calibrate_promotion(&outcomes(), "grid", &PromotionCalibrationParams::default())
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("promo-cal-{}.json", uuid::Uuid::new_v4()));
+        cal.write(&path).unwrap();
+        let field = SpectralVisionParams::profile("field").unwrap();
+        let params = field.clone().with_promotion_calibration(&path).unwrap();
+        assert_eq!(params.w_safety_min, cal.w_safety_min);
+        assert_eq!(params.promotion_threshold, cal.promotion_threshold);
+        assert_eq!(params.safety_shigh, field.safety_shigh);
+        assert_eq!(params.decision_bound, field.decision_bound);
+
+        let out_of_range = PromotionCalibration {
+            promotion_threshold: 1.5,
+            ..cal.clone()
+        };
+        out_of_range.write(&path).unwrap();
+        assert!(matches!(
+            field.with_promotion_calibration(&path),
+            Err(PromotionCalibrationError::Params(ParamsError::Invalid(_)))
+        ));
+
+        let bumped = cal
+            .to_json()
+            .replacen("\"format_version\": 1", "\"format_version\": 3", 1);
+        std::fs::write(&path, bumped).unwrap();
+        assert!(matches!(
+            PromotionCalibration::load(&path),
+            Err(PromotionCalibrationError::UnsupportedVersion { found: 3, .. })
+        ));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_outcome_parse_errors() {
+        let src = "{\"stability\":1,\"confidence\":1,\"safety_min\":1,\"accepted\":true}\n\n{\"stability\":1}\n";
+        assert!(matches!(
+            parse_outcomes(src),
+            Err(PromotionCalibrationError::Parse { line: 3, .. })
+        ));
+        let all_accepted = parse_outcomes(src.lines().next().unwrap()).unwrap();
+        assert_eq!(
+            calibrate_promotion(&all_accepted, "", &PromotionCalibrationParams::default()),
+            Err(PromotionCalibrationError::SingleClass {
+                accepted: 1,
+                rejected: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Named, file-loadable and validated `SpectralVisionParams` profiles.
+//!
+//! Every loader validates before returning, and each decision carries the
+//! params fingerprint so audits can tell which thresholds were in force.
+
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::governance::EnforcementMode;
+use crate::spectral_vision::{DecisionBound, SpectralVisionParams};
+
+/// Built-in profile names accepted by [`SpectralVisionParams::profile`].
+pub const PROFILE_NAMES: [&str; 4] = ["default", "lab", "field", "sleep-xr"];
+
+/// Errors loading or validating spectral-vision params.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ParamsError {
+    #[error("cannot read {path}: {message}")]
+    Io { path: String, message: String },
+    #[error("invalid {format} params: {message}")]
+    Parse {
+        format: &'static str,
+        message: String,
+    },
+    #[error("unsupported params file extension {0:?} (expected .json or .toml)")]
+    UnsupportedFormat(String),
+    #[error("unknown params profile {0:?}")]
+    UnknownProfile(String),
+    #[error("invalid spectral-vision params: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+}
+
+fn unit(name: &str, value: f64, errors: &mut Vec<String>) {
+    if !(value.is_finite() && (0.0..=1.0).contains(&value)) {
+        errors.push(format!("{} = {} is outside [0, 1]", name, value));
+    }
+}
+
+fn ordered(low: (&str, f64), high: (&str, f64), strict: bool, errors: &mut Vec<String>) {
+    let ok = if strict {
+        low.1 < high.1
+    } else {
+        low.1 <= high.1
+    };
+    if !ok {
+        errors.push(format!(
+            "{} ({}) must be {} {} ({})",
+            low.0,
+            low.1,
+            if strict { "<" } else { "≤" },
+            high.0,
+            high.1
+        ));
+    }
+}
+
+impl SpectralVisionParams {
+    /// Built-in profile by name (see [`PROFILE_NAMES`]).
+    ///
+    /// - `lab`: controlled recordings; point estimates, decision traces on.
+    /// - `field`: noisy recordings; raised thresholds, pessimistic z = 1.64.
+    /// - `sleep-xr`: XR during sleep; stricter G_safe gate and band dependency.
+    pub fn profile(name: &str) -> Result<Self, ParamsError> {
+        let mut p = Self::default();
+        match name {
+            "default" => {}
+            "lab" => p.trace = true,
+            "field" => {
+                p.safety_slow = 0.45;
+                p.safety_shigh = 0.75;
+                p.promotion_threshold = 0.85;
+                p.decision_bound = DecisionBound::Pessimistic { z: 1.64 };
+            }
+            "sleep-xr" => {
+                p.sleep_gate.gsafe_min = 0.6;
+                p.sleep_gate.ucomb_max = 0.4;
+                p.sleep_gate.in1_max = 0.4;
+                p.dependency_weight_elevated = 0.5;
+                p.high_dependency_max = 0.3;
+                p.decision_bound = DecisionBound::Pessimistic { z: 1.0 };
+                p.enforcement_mode = EnforcementMode::Strict;
+            }
+            other => return Err(ParamsError::UnknownProfile(other.to_string())),
+        }
+        Ok(p)
+    }
+
+    /// Check every invariant, reporting all violations at once.
+    pub fn validate(&self) -> Result<(), ParamsError> {
+        let mut errors = Vec::new();
+        let e = &mut errors;
+
+        unit("safety_slow", self.safety_slow, e);
+        unit("safety_shigh", self.safety_shigh, e);
+        ordered(
+            ("safety_slow", self.safety_slow),
+            ("safety_shigh", self.safety_shigh),
+            true,
+            e,
+        );
+        unit("hazard_elevated_max", self.hazard_elevated_max, e);
+        unit("hazard_safe_min", self.hazard_safe_min, e);
+        ordered(
+            ("hazard_elevated_max", self.hazard_elevated_max),
+            ("hazard_safe_min", self.hazard_safe_min),
+            false,
+            e,
+        );
+
+        let weights = [
+            ("w_stability", self.w_stability),
+            ("w_confidence", self.w_confidence),
+            ("w_safety_min", self.w_safety_min),
+        ];
+        for (name, w) in weights {
+            if !(w.is_finite() && w >= 0.0) {
+                e.push(format!("{} = {} must be a non-negative number", name, w));
+            }
+        }
+        if weights.iter().map(|(_, w)| w.max(0.0)).sum::<f64>() <= 0.0 {
+            e.push("promotion weights sum to zero".to_string());
+        }
+        unit("promotion_threshold", self.promotion_threshold, e);
+        unit(
+            "dependency_weight_elevated",
+            self.dependency_weight_elevated,
+            e,
+        );
+        unit("dependency_weight_high", self.dependency_weight_high, e);
+        unit("high_dependency_max", self.high_dependency_max, e);
+
+        let mut seen = Vec::new();
+        for band in &self.bands.bands {
+            let label = format!("band {} ({})", band.index, band.name);
+            if seen.contains(&band.index) {
+                e.push(format!("{}: duplicate band index", label));
+            }
+            seen.push(band.index);
+            if band.name.is_empty() {
+                e.push(format!("{}: name must not be empty", label));
+            }
+            if !(band.freq_low_hz.is_finite() && band.freq_low_hz < band.freq_high_hz) {
+                e.push(format!(
+                    "{}: freq_low_hz {} must be < freq_high_hz {}",
+                    label, band.freq_low_hz, band.freq_high_hz
+                ));
+            }
+            if !(band.weight.is_finite() && band.weight >= 0.0) {
+                e.push(format!(
+                    "{}: weight {} must be non-negative",
+                    label, band.weight
+                ));
+            }
+            if band.hazard_elevated_max.is_none() && band.hazard_safe_min.is_none() {
+                continue;
+            }
+            let elevated = band.hazard_elevated_max.unwrap_or(self.hazard_elevated_max);
+            let safe = band.hazard_safe_min.unwrap_or(self.hazard_safe_min);
+            if !(0.0..=1.0).contains(&elevated) || !(0.0..=1.0).contains(&safe) || elevated > safe {
+                e.push(format!(
+                    "{}: hazard cut-offs {} / {} must satisfy 0 ≤ elevated_max ≤ safe_min ≤ 1",
+                    label, elevated, safe
+                ));
+            }
+        }
+
+        unit("sleep_gate.gsafe_min", self.sleep_gate.gsafe_min, e);
+        unit("sleep_gate.ucomb_max", self.sleep_gate.ucomb_max, e);
+        unit(
+            "sleep_gate.unknown_mass_max",
+            self.sleep_gate.unknown_mass_max,
+            e,
+        );
+        unit("sleep_gate.in1_max", self.sleep_gate.in1_max, e);
+        if let DecisionBound::Pessimistic { z } = self.decision_bound {
+            if !(z.is_finite() && z >= 0.0) {
+                e.push(format!("decision_bound z = {} must be non-negative", z));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ParamsError::Invalid(errors))
+        }
+    }
+
+    /// Parse and validate JSON params; omitted fields take their defaults.
+    pub fn from_json(src: &str) -> Result<Self, ParamsError> {
+        let params: Self = serde_json::from_str(src).map_err(|e| ParamsError::Parse {
+            format: "JSON",
+            message: e.to_string(),
+        })?;
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Parse and validate TOML params; omitted fields take their defaults.
+    pub fn from_toml(src: &str) -> Result<Self, ParamsError> {
+        let params: Self = toml::from_str(src).map_err(|e| ParamsError::Parse {
+            format: "TOML",
+            message: e.to_string(),
+        })?;
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Load `.json` or `.toml` params.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ParamsError> {
+        let path = path.as_ref();
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if ext != "json" && ext != "toml" {
+            return Err(ParamsError::UnsupportedFormat(ext));
+        }
+        let src = std::fs::read_to_string(path).map_err(|e| ParamsError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        if ext == "json" {
+            Self::from_json(&src)
+        } else {
+            Self::from_toml(&src)
+        }
+    }
+
+    /// 16 lowercase hex chars of SHA-256 over the canonical (key-sorted) JSON
+    /// of every decision-affecting field; `trace` is excluded.
+    pub fn fingerprint(&self) -> String {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(map) = value.as_object_mut() {
+            map.remove("trace");
+        }
+        let digest = Sha256::digest(value.to_string().as_bytes());
+        digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sleep_state::SleepGateParams;
+
+    #[test]
+    fn test_profiles_are_valid_and_distinct() {
+        let mut fingerprints = Vec::new();
+        for name in PROFILE_NAMES {
+            let p = SpectralVisionParams::profile(name).unwrap();
+            p.validate().unwrap();
+            fingerprints.push(p.fingerprint());
+        }
+        // lab differs from default only in `trace`, which is not fingerprinted.
+        assert_eq!(fingerprints[0], fingerprints[1]);
+        assert_ne!(fingerprints[0], fingerprints[2]);
+        assert_ne!(fingerprints[2], fingerprints[3]);
+        assert_eq!(fingerprints[0].len(), 16);
+        assert!(matches!(
+            SpectralVisionParams::profile("moon"),
+            Err(ParamsError::UnknownProfile(name)) if name == "moon"
+        ));
+    }
+
+    #[test]
+    fn test_validation_reports_every_violation() {
+        let mut p = SpectralVisionParams {
+            safety_slow: 0.8,
+            hazard_elevated_max: 0.9,
+            w_confidence: -1.0,
+            ..SpectralVisionParams::default()
+        };
+        p.bands.by_name_mut("gamma").unwrap().freq_low_hz = 200.0;
+        let Err(ParamsError::Invalid(errors)) = p.validate() else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors[0].contains("safety_slow (0.8) must be < safety_shigh (0.7)"));
+        assert!(errors[3].contains("band 4 (gamma)"));
+    }
+
+    #[test]
+    fn test_load_json_and_toml_with_defaults() {
+        let json = SpectralVisionParams::from_json(r#"{"safety_slow": 0.3}"#).unwrap();
+        assert_eq!(json.safety_slow, 0.3);
+        assert_eq!(json.safety_shigh, 0.7);
+
+        let toml = SpectralVisionParams::from_toml(
+            "safety_shigh = 0.8\nenforcement_mode = \"advisory\"\n\n[sleep_gate]\ngsafe_min = 0.6\nucomb_max = 0.5\nunknown_mass_max = 0.3\nin1_max = 0.5\n",
+        )
+        .unwrap();
+        assert_eq!(toml.enforcement_mode, EnforcementMode::Advisory);
+        assert_eq!(toml.sleep_gate.gsafe_min, 0.6);
+
+        assert!(matches!(
+            SpectralVisionParams::from_json(r#"{"safety_slo": 0.3}"#),
+            Err(ParamsError::Parse { format: "JSON", .. })
+        ));
+        assert!(matches!(
+            SpectralVisionParams::from_toml("safety_slow = 0.9"),
+            Err(ParamsError::Invalid(_))
+        ));
+
+        let path = std::env::temp_dir().join(format!("sv-params-{}.json", uuid::Uuid::new_v4()));
+        let field = SpectralVisionParams::profile("field").unwrap();
+        std::fs::write(&path, serde_json::to_string(&field).unwrap()).unwrap();
+        let loaded = SpectralVisionParams::load(&path).unwrap();
+        assert_eq!(loaded.fingerprint(), field.fingerprint());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_partial_toml_profile_defaults_nested_fields() {
+        let p = SpectralVisionParams::from_toml(
+            r#"
+safety_slow = 0.35
+
+[sleep_gate]
+gsafe_min = 0.6
+
+[[bands.bands]]
+index = 0
+name = "delta"
+freq_low_hz = 0.5
+freq_high_hz = 4.0
+
+[[bands.bands]]
+index = 4
+name = "gamma"
+freq_low_hz = 30.0
+freq_high_hz = 100.0
+hazard_safe_min = 0.4
+"#,
+        )
+        .unwrap();
+        assert_eq!(p.safety_slow, 0.35);
+        assert_eq!(
+            p.sleep_gate,
+            SleepGateParams {
+                gsafe_min: 0.6,
+                ..SleepGateParams::default()
+            }
+        );
+        assert_eq!(p.bands.bands.len(), 2);
+        assert_eq!(p.bands.bands[0].weight, 1.0);
+        assert_eq!(p.bands.bands[0].hazard_safe_min, None);
+        assert_eq!(p.bands.bands[1].hazard_safe_min, Some(0.4));
+
+        // A band without a name or range still fails validation.
+        assert!(matches!(
+            SpectralVisionParams::from_toml("[[bands.bands]]\nindex = 2\n"),
+            Err(ParamsError::Invalid(errors)) if errors.len() == 2
+        ));
+    }
+}
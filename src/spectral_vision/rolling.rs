@@ -0,0 +1,557 @@
+//! Streaming band safety over sliding or exponentially weighted windows.
+//!
+//! Each ingested epoch carries one normalized power per band (as produced
+//! per epoch by `band_power`). μ_B and σ_B (population stddev, matching
+//! `compute_band_power_inputs`) are updated incrementally: sliding windows
+//! add and retire samples with Welford's update, EWMA windows decay with
+//! α = 1 − 2^(−Δt / half-life). Every ingest emits one decision per window.
+//! A window with no epochs, or missing any registry band, is held at Sniff
+//! without promotion rather than scored on the bands it happens to hold.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::governance::{GovernanceStateV1, PolicyViolation};
+use crate::sleep_state::SleepStateSnapshot;
+use crate::spectral_vision::{
+    clamp01, evaluate_spectral_vision_fingerprinted, ArtifactProfile, BandDependency,
+    ExcavationDepth, InputUncertainty, SpectralVisionDecision, SpectralVisionInputs,
+    SpectralVisionParams,
+};
+
+/// Errors feeding the streaming evaluator.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum RollingError {
+    #[error("epoch at {t_secs}s is not after the previous epoch at {last_secs}s")]
+    OutOfOrder { t_secs: f64, last_secs: f64 },
+    #[error("epoch time {0} is not finite")]
+    InvalidTime(f64),
+    #[error("window {name}: {detail}")]
+    InvalidWindow { name: String, detail: String },
+}
+
+/// How a window aggregates epochs.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowKind {
+    /// Epochs within the last `secs`, equally weighted.
+    Sliding { secs: f64 },
+    /// Exponential decay; an epoch's weight halves every `half_life_secs`.
+    Ewma { half_life_secs: f64 },
+}
+
+/// One configured window.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WindowSpec {
+    pub name: String,
+    pub kind: WindowKind,
+    /// If set, each band's (μ_B, σ_B) is the worst (lowest s_B) this window
+    /// produced over the last `worst_of_secs`, so safety_min means "worst
+    /// window in the last N seconds".
+    #[serde(default)]
+    pub worst_of_secs: Option<f64>,
+}
+
+impl WindowSpec {
+    pub fn sliding(name: &str, secs: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: WindowKind::Sliding { secs },
+            worst_of_secs: None,
+        }
+    }
+
+    pub fn ewma(name: &str, half_life_secs: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: WindowKind::Ewma { half_life_secs },
+            worst_of_secs: None,
+        }
+    }
+
+    pub fn with_worst_of(mut self, secs: f64) -> Self {
+        self.worst_of_secs = Some(secs);
+        self
+    }
+
+    /// Window length and half-life must be finite and positive.
+    pub fn validate(&self) -> Result<(), RollingError> {
+        let (field, secs) = match self.kind {
+            WindowKind::Sliding { secs } => ("secs", secs),
+            WindowKind::Ewma { half_life_secs } => ("half_life_secs", half_life_secs),
+        };
+        if secs.is_finite() && secs > 0.0 {
+            Ok(())
+        } else {
+            Err(RollingError::InvalidWindow {
+                name: self.name.clone(),
+                detail: format!("{} must be finite and positive, got {}", field, secs),
+            })
+        }
+    }
+
+    /// Sliding 30 s, 5 min and 30 min windows.
+    pub fn field_defaults() -> Vec<Self> {
+        vec![
+            Self::sliding("30s", 30.0),
+            Self::sliding("5m", 300.0),
+            Self::sliding("30m", 1800.0),
+        ]
+    }
+}
+
+/// One epoch of per-band normalized power.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct BandEpoch {
+    /// Seconds from stream start; strictly increasing.
+    pub t_secs: f64,
+    /// (band_index, normalized power ∈ [0,1]).
+    pub bands: Vec<(i32, f64)>,
+    /// Sleep channel for this epoch, if bound.
+    #[serde(default)]
+    pub sleep: Option<SleepStateSnapshot>,
+}
+
+/// Object-level inputs held fixed across windows.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StreamContext {
+    pub stability_score: f64,
+    pub confidence_score: f64,
+    pub artifacts: ArtifactProfile,
+    pub dependency: BandDependency,
+}
+
+/// Decision for one window after an ingest.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WindowDecision {
+    pub window: String,
+    pub t_secs: f64,
+    /// Epochs currently in the window (EWMA: all epochs seen).
+    pub epochs: usize,
+    /// True once a sliding window spans its full length; always true for EWMA.
+    pub complete: bool,
+    pub decision: SpectralVisionDecision,
+}
+
+/// Running mean/variance of one band in one window.
+#[derive(Clone, Debug, Default)]
+struct BandStats {
+    n: usize,
+    mean: f64,
+    m2: f64,
+    /// Sliding windows only: samples still in the window.
+    samples: VecDeque<(f64, f64)>,
+    /// EWMA only: time of the last update.
+    last_t: Option<f64>,
+    /// Monotone deque of (t, μ, σ, s) for worst-of; front is the worst.
+    worst: VecDeque<(f64, f64, f64, f64)>,
+}
+
+impl BandStats {
+    fn add(&mut self, x: f64) {
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn remove(&mut self, x: f64) {
+        if self.n <= 1 {
+            *self = Self {
+                worst: std::mem::take(&mut self.worst),
+                ..Self::default()
+            };
+            return;
+        }
+        self.n -= 1;
+        let delta = x - self.mean;
+        self.mean -= delta / self.n as f64;
+        self.m2 = (self.m2 - delta * (x - self.mean)).max(0.0);
+    }
+
+    fn ewma(&mut self, t: f64, x: f64, half_life_secs: f64) {
+        self.n += 1;
+        match self.last_t {
+            None => {
+                self.mean = x;
+                self.m2 = 0.0;
+            }
+            Some(last) => {
+                let alpha = 1.0 - (-(t - last) / half_life_secs.max(f64::MIN_POSITIVE)).exp2();
+                let delta = x - self.mean;
+                self.mean += alpha * delta;
+                // m2 holds the EWMA variance directly.
+                self.m2 = (1.0 - alpha) * (self.m2 + alpha * delta * delta);
+            }
+        }
+        self.last_t = Some(t);
+    }
+
+    fn mean_std(&self, kind: WindowKind) -> (f64, f64) {
+        let var = match kind {
+            WindowKind::Sliding { .. } if self.n > 0 => self.m2 / self.n as f64,
+            WindowKind::Sliding { .. } => 0.0,
+            WindowKind::Ewma { .. } => self.m2,
+        };
+        (clamp01(self.mean), clamp01(var.max(0.0).sqrt()))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct WindowState {
+    spec: WindowSpec,
+    bands: BTreeMap<i32, BandStats>,
+    first_t: Option<f64>,
+}
+
+/// Incremental, multi-window spectral-vision evaluator for one object.
+#[derive(Clone, Debug)]
+pub struct RollingBandSafety {
+    params: SpectralVisionParams,
+    /// `params.fingerprint()`, computed once; params never change after `new`.
+    params_fingerprint: String,
+    windows: Vec<WindowState>,
+    last_t: Option<f64>,
+}
+
+impl RollingBandSafety {
+    /// Fails if any window has a non-positive length or half-life.
+    pub fn new(
+        windows: Vec<WindowSpec>,
+        params: SpectralVisionParams,
+    ) -> Result<Self, RollingError> {
+        for spec in &windows {
+            spec.validate()?;
+        }
+        Ok(Self {
+            params_fingerprint: params.fingerprint(),
+            params,
+            windows: windows
+                .into_iter()
+                .map(|spec| WindowState {
+                    spec,
+                    bands: BTreeMap::new(),
+                    first_t: None,
+                })
+                .collect(),
+            last_t: None,
+        })
+    }
+
+    /// Current (band_index, μ_B, σ_B) for a window, after worst-of selection.
+    pub fn band_inputs(&self, window: &str) -> Option<Vec<(i32, f64, f64)>> {
+        let w = self.windows.iter().find(|w| w.spec.name == window)?;
+        Some(window_inputs(w).0)
+    }
+
+    /// Fold one epoch into every window and emit a decision per window.
+    pub fn ingest(
+        &mut self,
+        epoch: &BandEpoch,
+        context: &StreamContext,
+        gov: &GovernanceStateV1,
+    ) -> Result<Vec<WindowDecision>, RollingError> {
+        let t = epoch.t_secs;
+        if !t.is_finite() {
+            return Err(RollingError::InvalidTime(t));
+        }
+        if let Some(last_secs) = self.last_t.filter(|last| t <= *last) {
+            return Err(RollingError::OutOfOrder {
+                t_secs: t,
+                last_secs,
+            });
+        }
+        self.last_t = Some(t);
+
+        let mut out = Vec::with_capacity(self.windows.len());
+        for w in &mut self.windows {
+            w.first_t.get_or_insert(t);
+            let kind = w.spec.kind;
+            for (band, power) in &epoch.bands {
+                let x = clamp01(*power);
+                let stats = w.bands.entry(*band).or_default();
+                match kind {
+                    WindowKind::Sliding { .. } => {
+                        stats.samples.push_back((t, x));
+                        stats.add(x);
+                    }
+                    WindowKind::Ewma { half_life_secs } => stats.ewma(t, x, half_life_secs),
+                }
+            }
+
+            if let WindowKind::Sliding { secs } = kind {
+                for stats in w.bands.values_mut() {
+                    while let Some(&(ts, x)) = stats.samples.front() {
+                        if ts > t - secs {
+                            break;
+                        }
+                        stats.samples.pop_front();
+                        stats.remove(x);
+                    }
+                }
+                w.bands.retain(|_, s| s.n > 0);
+            }
+
+            if let Some(lookback) = w.spec.worst_of_secs {
+                for stats in w.bands.values_mut() {
+                    let (mu, sigma) = stats.mean_std(kind);
+                    let s = clamp01(1.0 - mu - sigma);
+                    while stats.worst.back().is_some_and(|b| b.3 >= s) {
+                        stats.worst.pop_back();
+                    }
+                    stats.worst.push_back((t, mu, sigma, s));
+                    while stats.worst.front().is_some_and(|f| f.0 < t - lookback) {
+                        stats.worst.pop_front();
+                    }
+                }
+            }
+
+            let (raw, uncertainty, epochs) = window_inputs(w);
+            let mut decision = evaluate_spectral_vision_fingerprinted(
+                &raw,
+                SpectralVisionInputs {
+                    stability_score: context.stability_score,
+                    confidence_score: context.confidence_score,
+                    artifacts: Some(&context.artifacts),
+                    dependency: Some(&context.dependency),
+                    sleep: epoch.sleep.as_ref(),
+                    uncertainty: Some(&uncertainty),
+                },
+                &self.params,
+                &self.params_fingerprint,
+                gov,
+            );
+            let missing: Vec<&str> = self
+                .params
+                .bands
+                .bands
+                .iter()
+                .filter(|b| !raw.iter().any(|(i, _, _)| *i == b.index))
+                .map(|b| b.name.as_str())
+                .collect();
+            if epochs == 0 || !missing.is_empty() {
+                // Non-waivable: an absent band is not a safe band.
+                let reason = if epochs == 0 {
+                    "window holds no epochs".to_string()
+                } else {
+                    format!("window has no epochs for band(s) {}", missing.join(", "))
+                };
+                decision.excavation_depth = PolicyViolation::enforce(
+                    "spectral_vision.window_coverage",
+                    reason.clone(),
+                    ExcavationDepth::Sniff,
+                    &mut decision.violations,
+                );
+                decision.promotion_passed = false;
+                decision.promotion_block_reason = Some(reason);
+            }
+            let complete = match kind {
+                WindowKind::Sliding { secs } => w.first_t.is_some_and(|f| t - f >= secs),
+                WindowKind::Ewma { .. } => true,
+            };
+            out.push(WindowDecision {
+                window: w.spec.name.clone(),
+                t_secs: t,
+                epochs,
+                complete,
+                decision,
+            });
+        }
+        Ok(out)
+    }
+}
+
+/// Band inputs, their uncertainty (Var(μ_B) = σ_B² / n for sliding windows)
+/// and the epoch count for one window.
+fn window_inputs(w: &WindowState) -> (Vec<(i32, f64, f64)>, InputUncertainty, usize) {
+    let mut raw = Vec::with_capacity(w.bands.len());
+    let mut uncertainty = InputUncertainty::default();
+    let mut epochs = 0;
+    for (band, stats) in &w.bands {
+        let (mu, sigma) = match (w.spec.worst_of_secs, stats.worst.front()) {
+            (Some(_), Some(&(_, mu, sigma, _))) => (mu, sigma),
+            _ => stats.mean_std(w.spec.kind),
+        };
+        raw.push((*band, mu, sigma));
+        if matches!(w.spec.kind, WindowKind::Sliding { .. }) && stats.n > 0 {
+            uncertainty
+                .bands
+                .push((*band, sigma * sigma / stats.n as f64, 0.0));
+        }
+        epochs = epochs.max(stats.n);
+    }
+    (raw, uncertainty, epochs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch(t: f64, delta: f64) -> BandEpoch {
+        BandEpoch {
+            t_secs: t,
+            bands: vec![(0, delta), (1, 0.1), (2, 0.1), (3, 0.1), (4, 0.1)],
+            sleep: None,
+        }
+    }
+
+    #[test]
+    fn test_sliding_window_matches_batch_statistics() {
+        let mut rolling = RollingBandSafety::new(
+            vec![WindowSpec::sliding("30s", 30.0)],
+            SpectralVisionParams::default(),
+        )
+        .unwrap();
+        let powers: Vec<f64> = (0..40).map(|i| ((i * 7) % 11) as f64 / 20.0).collect();
+        let mut last = Vec::new();
+        for (i, p) in powers.iter().enumerate() {
+            last = rolling
+                .ingest(
+                    &epoch(4.0 * i as f64, *p),
+                    &StreamContext::default(),
+                    &GovernanceStateV1::active_governed(),
+                )
+                .unwrap();
+        }
+        // Window (126, 156] holds the last 8 epochs (t = 128..=156).
+        let tail = &powers[powers.len() - 8..];
+        let mean = tail.iter().sum::<f64>() / 8.0;
+        let std = (tail.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / 8.0).sqrt();
+        let inputs = rolling.band_inputs("30s").unwrap();
+        assert!((inputs[0].1 - mean).abs() < 1e-9);
+        assert!((inputs[0].2 - std).abs() < 1e-9);
+        assert_eq!(last[0].epochs, 8);
+        assert!(last[0].complete);
+        assert_eq!(
+            last[0].decision.params_fingerprint,
+            SpectralVisionParams::default().fingerprint()
+        );
+
+        assert_eq!(
+            rolling
+                .ingest(
+                    &epoch(10.0, 0.1),
+                    &StreamContext::default(),
+                    &GovernanceStateV1::active_governed()
+                )
+                .unwrap_err(),
+            RollingError::OutOfOrder {
+                t_secs: 10.0,
+                last_secs: 156.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_worst_of_keeps_a_recent_dip() {
+        let windows = vec![
+            WindowSpec::sliding("30s", 30.0),
+            WindowSpec::sliding("30s-worst-5m", 30.0).with_worst_of(300.0),
+        ];
+        let mut rolling = RollingBandSafety::new(windows, SpectralVisionParams::default()).unwrap();
+        let ctx = StreamContext::default();
+        let mut last = Vec::new();
+        for i in 0..60 {
+            let t = 4.0 * i as f64;
+            // Delta power spikes between 40 s and 60 s, then recovers.
+            let p = if (40.0..60.0).contains(&t) { 0.9 } else { 0.05 };
+            last = rolling
+                .ingest(&epoch(t, p), &ctx, &GovernanceStateV1::active_governed())
+                .unwrap();
+        }
+        assert_eq!(last[0].decision.excavation_depth, ExcavationDepth::DigFull);
+        assert_eq!(last[1].decision.excavation_depth, ExcavationDepth::Sniff);
+        assert!(last[1].decision.band_profile.safety_min < 0.4);
+    }
+
+    #[test]
+    fn test_ewma_tracks_level_shift() {
+        let mut rolling = RollingBandSafety::new(
+            vec![WindowSpec::ewma("ewma-60s", 60.0)],
+            SpectralVisionParams::default(),
+        )
+        .unwrap();
+        for i in 0..100 {
+            let p = if i < 50 { 0.2 } else { 0.6 };
+            rolling
+                .ingest(
+                    &epoch(4.0 * i as f64, p),
+                    &StreamContext::default(),
+                    &GovernanceStateV1::active_governed(),
+                )
+                .unwrap();
+        }
+        // 200 s after the shift ≈ 3.3 half-lives: within 10% of the new level.
+        let (_, mu, sigma) = rolling.band_inputs("ewma-60s").unwrap()[0];
+        assert!((mu - 0.6).abs() < 0.04, "{}", mu);
+        // The jump's variance decays at half the rate: σ ≈ 0.4 · 2^(−200/120) ≈ 0.13.
+        assert!((sigma - 0.126).abs() < 0.01, "{}", sigma);
+    }
+
+    #[test]
+    fn test_drained_or_partial_window_forces_sniff() {
+        let mut rolling = RollingBandSafety::new(
+            vec![WindowSpec::sliding("30s", 30.0)],
+            SpectralVisionParams::default(),
+        )
+        .unwrap();
+        let ctx = StreamContext {
+            stability_score: 1.0,
+            confidence_score: 1.0,
+            ..Default::default()
+        };
+        let gov = GovernanceStateV1::active_governed();
+        let first = rolling.ingest(&epoch(0.0, 0.1), &ctx, &gov).unwrap();
+        assert_eq!(first[0].decision.excavation_depth, ExcavationDepth::DigFull);
+        assert!(first[0].decision.promotion_passed);
+
+        // Nothing arrives until t=40, so the 30 s window has drained.
+        let empty = BandEpoch {
+            t_secs: 40.0,
+            ..Default::default()
+        };
+        let drained = &rolling.ingest(&empty, &ctx, &gov).unwrap()[0];
+        assert_eq!(drained.epochs, 0);
+        assert_eq!(drained.decision.excavation_depth, ExcavationDepth::Sniff);
+        assert!(!drained.decision.promotion_passed);
+        assert!(drained
+            .decision
+            .violations
+            .iter()
+            .any(|v| v.gate == "spectral_vision.window_coverage" && v.enforced));
+
+        // A silent gamma band is missing, not safe.
+        let partial = BandEpoch {
+            t_secs: 44.0,
+            bands: vec![(0, 0.1), (1, 0.1), (2, 0.1), (3, 0.1)],
+            sleep: None,
+        };
+        let partial = &rolling.ingest(&partial, &ctx, &gov).unwrap()[0];
+        assert_eq!(partial.decision.excavation_depth, ExcavationDepth::Sniff);
+        assert!(!partial.decision.promotion_passed);
+        assert!(partial
+            .decision
+            .promotion_block_reason
+            .as_deref()
+            .unwrap()
+            .contains("gamma"));
+    }
+
+    #[test]
+    fn test_rejects_non_positive_windows() {
+        for spec in [
+            WindowSpec::sliding("zero", 0.0),
+            WindowSpec::sliding("negative", -30.0),
+            WindowSpec::ewma("zero-half-life", 0.0),
+            WindowSpec::ewma("nan-half-life", f64::NAN),
+        ] {
+            let name = spec.name.clone();
+            match RollingBandSafety::new(vec![spec], SpectralVisionParams::default()) {
+                Err(RollingError::InvalidWindow { name: n, .. }) => assert_eq!(n, name),
+                other => panic!("{}: {:?}", name, other.map(|_| ())),
+            }
+        }
+    }
+}
@@ -0,0 +1,397 @@
+//! Explainable trace of one spectral-vision decision.
+//!
+//! The trace lists the rules in evaluation order, the inputs after clamping,
+//! the normalized promotion weights and the signed margin to each threshold.
+//! Its `Display` impl renders a plain-text report for audits and the CLI.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::governance::EnforcementMode;
+use crate::spectral_vision::{
+    clamp01, promotion_weights, ArtifactProfile, DecisionBound, ExcavationDepth,
+    SpectralVisionDecision, SpectralVisionParams,
+};
+
+/// How a rule affected the decision.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepOutcome {
+    /// Condition not met; the rule changed nothing.
+    Passed,
+    /// The rule limited depth or blocked promotion.
+    Fired,
+    /// Condition met but only recorded (advisory enforcement).
+    Advisory,
+    /// Condition met but the gate is disabled.
+    Waived,
+    /// Not evaluated (no input, or an earlier rule already decided).
+    Skipped,
+}
+
+/// One rule on the decision path.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TraceStep {
+    /// Gate identifier, matching `PolicyViolation::gate` where one exists.
+    pub rule: String,
+    pub outcome: StepOutcome,
+    pub detail: String,
+}
+
+/// Distance from a value to a threshold; positive margin means it cleared.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ThresholdMargin {
+    /// Comparison that must hold, e.g. `safety_min >= safety_shigh`.
+    pub check: String,
+    pub value: f64,
+    pub threshold: f64,
+    pub margin: f64,
+}
+
+/// One band's inputs after clamping.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TracedBand {
+    pub band_index: i32,
+    pub band_name: String,
+    pub mean_power: f64,
+    pub stddev_power: f64,
+    pub safety_score: f64,
+}
+
+/// Scalar inputs after clamping.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TracedInputs {
+    pub bands: Vec<TracedBand>,
+    pub stability: f64,
+    pub confidence: f64,
+    pub artifact_epoch_fraction: f64,
+    pub artifact_correlation: f64,
+}
+
+/// Promotion weights renormalized to sum to 1, and the dependency factor.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TracedWeights {
+    pub stability: f64,
+    pub confidence: f64,
+    pub safety_min: f64,
+    pub dependency_factor: f64,
+}
+
+/// Structured explanation of a [`SpectralVisionDecision`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DecisionTrace {
+    pub enforcement_mode: EnforcementMode,
+    pub decision_bound: DecisionBound,
+    pub inputs: TracedInputs,
+    pub weights: TracedWeights,
+    /// Depth rules, then promotion rules, in evaluation order.
+    pub steps: Vec<TraceStep>,
+    pub margins: Vec<ThresholdMargin>,
+    pub excavation_depth: ExcavationDepth,
+    pub promotion_passed: bool,
+}
+
+impl DecisionTrace {
+    /// Steps that limited depth or blocked promotion.
+    pub fn fired(&self) -> impl Iterator<Item = &TraceStep> {
+        self.steps
+            .iter()
+            .filter(|s| s.outcome == StepOutcome::Fired)
+    }
+}
+
+/// Trace steps recorded by the gate functions as they decide; records
+/// nothing unless tracing is enabled.
+#[derive(Debug, Default)]
+pub(crate) struct StepLog(Option<Vec<TraceStep>>);
+
+impl StepLog {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self(enabled.then(Vec::new))
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        rule: &str,
+        outcome: StepOutcome,
+        detail: impl FnOnce() -> String,
+    ) {
+        if let Some(steps) = &mut self.0 {
+            steps.push(TraceStep {
+                rule: rule.to_string(),
+                outcome,
+                detail: detail(),
+            });
+        }
+    }
+
+    /// Record a gate whose condition `fired`, resolved under `mode`;
+    /// non-waivable gates pass `EnforcementMode::Strict`.
+    pub(crate) fn gate(
+        &mut self,
+        rule: &str,
+        fired: bool,
+        mode: EnforcementMode,
+        detail: impl FnOnce() -> String,
+    ) {
+        let outcome = match (fired, mode) {
+            (false, _) => StepOutcome::Passed,
+            (true, EnforcementMode::Strict) => StepOutcome::Fired,
+            (true, EnforcementMode::Advisory) => StepOutcome::Advisory,
+            (true, EnforcementMode::Disabled) => StepOutcome::Waived,
+        };
+        self.record(rule, outcome, detail);
+    }
+
+    pub(crate) fn append(&mut self, other: StepLog) {
+        if let (Some(steps), Some(more)) = (&mut self.0, other.0) {
+            steps.extend(more);
+        }
+    }
+
+    pub(crate) fn into_steps(self) -> Vec<TraceStep> {
+        self.0.unwrap_or_default()
+    }
+}
+
+fn margin(check: &str, value: f64, threshold: f64, at_least: bool) -> ThresholdMargin {
+    ThresholdMargin {
+        check: check.to_string(),
+        value,
+        threshold,
+        margin: if at_least {
+            value - threshold
+        } else {
+            threshold - value
+        },
+    }
+}
+
+/// Build the trace for a decision from the inputs it was evaluated on.
+///
+/// `steps` are the rule outcomes recorded by the gate functions while the
+/// decision was made; this adds the clamped inputs, weights and margins.
+pub(crate) fn trace_decision(
+    decision: &SpectralVisionDecision,
+    steps: Vec<TraceStep>,
+    stability_score: f64,
+    confidence_score: f64,
+    artifacts: &ArtifactProfile,
+    params: &SpectralVisionParams,
+) -> DecisionTrace {
+    let s_min = decision
+        .band_profile
+        .safety_min_bound(params.decision_bound);
+    let gated_score = params
+        .decision_bound
+        .apply(decision.promotion_score, decision.promotion_variance);
+    let dependency = &decision.band_dependency;
+
+    let mut margins = vec![
+        margin("safety_min >= safety_slow", s_min, params.safety_slow, true),
+        margin(
+            "safety_min >= safety_shigh",
+            s_min,
+            params.safety_shigh,
+            true,
+        ),
+        margin(
+            "promotionscore >= promotion_threshold",
+            gated_score,
+            params.promotion_threshold,
+            true,
+        ),
+        margin(
+            "high_share <= high_dependency_max",
+            dependency.high_share,
+            params.high_dependency_max,
+            false,
+        ),
+    ];
+    if let Some(g) = &decision.sleep_gate {
+        margins.push(margin(
+            "gsafe >= gsafe_min",
+            g.g_safe,
+            params.sleep_gate.gsafe_min,
+            true,
+        ));
+        margins.push(margin(
+            "ucomb <= ucomb_max",
+            g.u_comb,
+            params.sleep_gate.ucomb_max,
+            false,
+        ));
+    }
+
+    let [w_stability, w_confidence, w_safety_min] = promotion_weights(params);
+    DecisionTrace {
+        enforcement_mode: params.enforcement_mode,
+        decision_bound: params.decision_bound,
+        inputs: TracedInputs {
+            bands: decision
+                .band_profile
+                .bands
+                .iter()
+                .map(|b| TracedBand {
+                    band_index: b.band_index,
+                    band_name: b.band_name.clone(),
+                    mean_power: b.mean_power,
+                    stddev_power: b.stddev_power,
+                    safety_score: b.safety_score,
+                })
+                .collect(),
+            stability: clamp01(stability_score),
+            confidence: clamp01(confidence_score),
+            artifact_epoch_fraction: clamp01(artifacts.epoch_fraction),
+            artifact_correlation: clamp01(artifacts.correlation),
+        },
+        weights: TracedWeights {
+            stability: w_stability,
+            confidence: w_confidence,
+            safety_min: w_safety_min,
+            dependency_factor: dependency.promotion_factor,
+        },
+        steps,
+        margins,
+        excavation_depth: decision.excavation_depth,
+        promotion_passed: decision.promotion_passed,
+    }
+}
+
+impl fmt::Display for StepOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            StepOutcome::Passed => "pass",
+            StepOutcome::Fired => "FIRED",
+            StepOutcome::Advisory => "advisory",
+            StepOutcome::Waived => "waived",
+            StepOutcome::Skipped => "skip",
+        })
+    }
+}
+
+impl fmt::Display for DecisionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "decision: depth={:?} promoted={} (enforcement {:?}, bound {:?})",
+            self.excavation_depth,
+            self.promotion_passed,
+            self.enforcement_mode,
+            self.decision_bound
+        )?;
+        writeln!(f, "rules:")?;
+        for s in &self.steps {
+            writeln!(f, "  [{:<8}] {:<38} {}", s.outcome, s.rule, s.detail)?;
+        }
+        writeln!(f, "margins (positive = clears):")?;
+        for m in &self.margins {
+            writeln!(
+                f,
+                "  {:<40} value {:.3} threshold {:.3} margin {:+.3}",
+                m.check, m.value, m.threshold, m.margin
+            )?;
+        }
+        let i = &self.inputs;
+        writeln!(
+            f,
+            "inputs: stability {:.3}, confidence {:.3}, artifact epochs {:.3}, artifact corr {:.3}",
+            i.stability, i.confidence, i.artifact_epoch_fraction, i.artifact_correlation
+        )?;
+        for b in &i.bands {
+            writeln!(
+                f,
+                "  {:<8} mu {:.3} sigma {:.3} s {:.3}",
+                b.band_name, b.mean_power, b.stddev_power, b.safety_score
+            )?;
+        }
+        let w = &self.weights;
+        write!(
+            f,
+            "weights: stability {:.3}, confidence {:.3}, safety_min {:.3}, dependency factor {:.3}",
+            w.stability, w.confidence, w.safety_min, w.dependency_factor
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::{GovernanceMode, GovernanceStateV1};
+    use crate::spectral_vision::{evaluate_spectral_vision, SpectralVisionInputs};
+
+    fn evaluate(
+        raw: &[(i32, f64, f64)],
+        params: &SpectralVisionParams,
+        gov: &GovernanceStateV1,
+    ) -> SpectralVisionDecision {
+        let inputs = SpectralVisionInputs {
+            stability_score: 1.4,
+            confidence_score: 0.9,
+            ..Default::default()
+        };
+        evaluate_spectral_vision(raw, inputs, params, gov)
+    }
+
+    #[test]
+    fn test_trace_names_the_rule_that_held_depth() {
+        let params = SpectralVisionParams {
+            trace: true,
+            ..SpectralVisionParams::default()
+        };
+        let decision = evaluate(
+            &[(0, 0.5, 0.2), (2, 0.1, 0.0)],
+            &params,
+            &GovernanceStateV1::active_governed(),
+        );
+        assert_eq!(decision.excavation_depth, ExcavationDepth::Sniff);
+        let trace = decision.trace.expect("trace requested");
+
+        let fired: Vec<_> = trace.fired().map(|s| s.rule.as_str()).collect();
+        assert_eq!(fired, ["spectral_vision.band_safety"]);
+        assert!(trace.promotion_passed);
+        assert_eq!(trace.inputs.stability, 1.0);
+        assert!((trace.weights.stability - 0.4).abs() < 1e-12);
+        let slow = &trace.margins[0];
+        assert!((slow.margin - (0.3 - 0.4)).abs() < 1e-12);
+
+        let report = trace.to_string();
+        assert!(report.contains("[FIRED   ] spectral_vision.band_safety"));
+        assert!(report.contains("safety_min 0.300 (delta)"));
+    }
+
+    #[test]
+    fn test_trace_reflects_enforcement_mode() {
+        let mut params = SpectralVisionParams {
+            trace: true,
+            enforcement_mode: EnforcementMode::Advisory,
+            ..SpectralVisionParams::default()
+        };
+        let governed = GovernanceStateV1::active_governed();
+        let raw = [(0, 0.5, 0.2), (2, 0.1, 0.0)];
+        let trace = evaluate(&raw, &params, &governed).trace.unwrap();
+        assert_eq!(trace.excavation_depth, ExcavationDepth::DigFull);
+        assert_eq!(trace.steps[1].rule, "spectral_vision.band_safety");
+        assert_eq!(trace.steps[1].outcome, StepOutcome::Advisory);
+
+        params.enforcement_mode = EnforcementMode::Disabled;
+        let trace = evaluate(&raw, &params, &governed).trace.unwrap();
+        assert_eq!(trace.steps[1].outcome, StepOutcome::Waived);
+
+        // Observe-only is non-waivable, even with gates disabled.
+        let free = GovernanceStateV1 {
+            spectral_roaming_active: true,
+            non_interference_required: true,
+            mode: GovernanceMode::ActiveFree,
+            ..governed
+        };
+        let trace = evaluate(&[(0, 0.0, 0.0)], &params, &free).trace.unwrap();
+        assert_eq!(trace.excavation_depth, ExcavationDepth::Sniff);
+        assert_eq!(trace.steps[3].rule, "spectral_vision.observe_only");
+        assert_eq!(trace.steps[3].outcome, StepOutcome::Fired);
+
+        params.trace = false;
+        assert!(evaluate(&[(0, 0.0, 0.0)], &params, &free).trace.is_none());
+    }
+}
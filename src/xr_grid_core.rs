@@ -1,13 +1,13 @@
 use serde::{Deserialize, Serialize};
 
 use crate::governance::spectral_governance_audit::SpectralGovernanceAudit;
+use crate::governance::{EnforcementMode, PolicyViolation};
 use crate::hauntdensity::HauntDensityScore;
 use crate::hauntdensity::HauntZone;
-use crate::hexstamp_quantum_roaming::{
-    HexStampQuantumRoamingV1, XRZone,
-};
-use crate::ghostnet::tokens::{PsychRiskView};
+use crate::hexstamp_quantum_roaming::{RoamingStamp, XRZone};
+use crate::ghostnet::tokens::{PsychRiskBand, PsychRiskView};
 use crate::ghostnet::spectral::RegionSessionKey;
+use crate::sleep_state::{SleepGate, SleepGateParams, SleepStateSnapshot};
 
 /// Nonsoul snapshot for XR routing, per region-session window.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +22,15 @@ pub struct XRGridState {
     pub psych_load: f32,
     /// Governance snapshot for this window.
     pub gov: SpectralGovernanceAudit,
+    /// How routing gates are applied (ALN `enforcement_mode`).
+    #[serde(default)]
+    pub enforcement: EnforcementMode,
+    /// Sleep channel for this window, if bound.
+    #[serde(default)]
+    pub sleep: Option<SleepStateSnapshot>,
+    /// G_safe thresholds for `sleep`.
+    #[serde(default)]
+    pub sleep_gate: SleepGateParams,
 }
 
 /// High-level XR action band, nonsoul, routing-only.
@@ -72,7 +81,7 @@ pub fn compute_xrzone_from_haunt(hnorm: f32) -> XRZone {
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct XRGridRiskIndex {
     pub x: f32,
-    pub band: String, // "NORMAL" | "MODERATE" | "HIGH"
+    pub band: PsychRiskBand,
 }
 
 pub fn compute_risk_index(
@@ -88,40 +97,78 @@ pub fn compute_risk_index(
     let mut x = (h + f + p) / 3.0;
     x = clamp01(x);
 
-    let band = if x < 0.4 {
-        "NORMAL".to_string()
-    } else if x < 0.7 {
-        "MODERATE".to_string()
-    } else {
-        "HIGH".to_string()
-    };
-
-    XRGridRiskIndex { x, band }
+    XRGridRiskIndex {
+        x,
+        band: PsychRiskBand::from_index(x),
+    }
 }
 
 /// Map XRZone + governance into a route action.
 /// Non-interference + soulmodelingforbidden => ObserveOnly, regardless of risk.[file:7][file:8]
+/// A closed G_safe gate refuses FullInteraction (GuardedInteraction instead).
 pub fn resolve_xr_route_action(
     xr_zone: XRZone,
     gov: &SpectralGovernanceAudit,
     risk: &XRGridRiskIndex,
+    sleep: Option<&SleepGate>,
 ) -> XRRouteAction {
-    // Hard governance override: roaming + non-interference => observe-only.
-    if gov.soulmodelingforbidden && gov.noninterferencerequired {
-        return XRRouteAction::ObserveOnly;
-    }
-
-    // Optional: if risk is extreme HIGH, allow TerminateSafe in any zone.
-    if risk.band == "HIGH" {
-        return XRRouteAction::TerminateSafe;
-    }
+    resolve_xr_route_action_gated(xr_zone, gov, risk, sleep, EnforcementMode::Strict, &mut Vec::new())
+}
 
-    match xr_zone {
+/// As [`resolve_xr_route_action`], applying the soft overrides per `mode` and
+/// recording each one that fired. The sleep-gate and non-interference
+/// overrides are non-waivable and apply in every mode.
+pub fn resolve_xr_route_action_gated(
+    xr_zone: XRZone,
+    gov: &SpectralGovernanceAudit,
+    risk: &XRGridRiskIndex,
+    sleep: Option<&SleepGate>,
+    mode: EnforcementMode,
+    violations: &mut Vec<PolicyViolation>,
+) -> XRRouteAction {
+    let mut action = match xr_zone {
         XRZone::XRCONTROL => XRRouteAction::FullInteraction,
         XRZone::XRMONITORED => XRRouteAction::GuardedInteraction,
         XRZone::XRRESTRICTED => XRRouteAction::MitigationOnly,
         XRZone::XRCONTAINMENT => XRRouteAction::ObserveOnly,
+    };
+
+    // A closed G_safe gate is non-waivable: it caps the action in every mode,
+    // advisory and disabled included.
+    if let Some(gate) = sleep.filter(|g| !g.open) {
+        if action == XRRouteAction::FullInteraction {
+            action = PolicyViolation::enforce(
+                "xr_grid.sleep_gate",
+                format!("G_safe gate closed ({}) refuses FullInteraction", gate.describe()),
+                XRRouteAction::GuardedInteraction,
+                violations,
+            );
+        }
+    }
+
+    // Optional: if risk is extreme HIGH, allow TerminateSafe in any zone.
+    if risk.band == PsychRiskBand::High {
+        action = mode.resolve(
+            "xr_grid.high_risk",
+            format!("risk index {:.3} forces TerminateSafe", risk.x),
+            XRRouteAction::TerminateSafe,
+            action,
+            violations,
+        );
     }
+
+    // Governance override, checked last so it wins: roaming + non-interference => observe-only.
+    // Non-waivable: "may see but must not steer" holds in every enforcement mode.
+    if gov.soulmodelingforbidden && gov.noninterferencerequired {
+        action = PolicyViolation::enforce(
+            "xr_grid.non_interference",
+            "non-interference forces ObserveOnly",
+            XRRouteAction::ObserveOnly,
+            violations,
+        );
+    }
+
+    action
 }
 
 /// Build a GhostNet-compatible PsychRiskView from our risk index,
@@ -131,30 +178,140 @@ pub fn to_psych_risk_view(risk: &XRGridRiskIndex) -> PsychRiskView {
 }
 
 /// Convenience: given XRGridState, compute xrzone, risk index,
-/// route action, and optional hex-stamp for audit only.
-/// The caller must respect maybestampforroaming semantics: do not
-/// use the stamp to steer when non-interference is required.[file:7]
+/// route action, and optional hex-stamp.
+/// maybe_stamp_for_roaming semantics are enforced by RoamingStamp: under
+/// non-interference the stamp is AuditOnly and cannot reach steering APIs.[file:7]
 pub struct XRGridDecision {
     pub xr_zone: XRZone,
     pub risk: XRGridRiskIndex,
     pub action: XRRouteAction,
-    pub hex_stamp: Option<HexStampQuantumRoamingV1>,
+    pub hex_stamp: Option<RoamingStamp>,
+    /// G_safe gate for the window's sleep channel, if bound.
+    pub sleep_gate: Option<SleepGate>,
+    /// Routing gates that fired; unenforced entries mean advisory mode.
+    pub violations: Vec<PolicyViolation>,
 }
 
 pub fn decide_xr_route_for_state(
     state: &XRGridState,
     haunt: Option<&HauntDensityScore>,
-    maybe_stamp: Option<HexStampQuantumRoamingV1>,
+    maybe_stamp: Option<RoamingStamp>,
 ) -> XRGridDecision {
     let hnorm = haunt.map(|s| s.hnorm as f32).unwrap_or(0.0);
     let xr_zone = compute_xrzone_from_haunt(hnorm);
     let risk = compute_risk_index(haunt, state.fear_level, state.psych_load);
-    let action = resolve_xr_route_action(xr_zone, &state.gov, &risk);
+    let sleep_gate = state.sleep.as_ref().map(|s| s.gate(&state.sleep_gate));
+    let mut violations = Vec::new();
+    let action = resolve_xr_route_action_gated(
+        xr_zone,
+        &state.gov,
+        &risk,
+        sleep_gate.as_ref(),
+        state.enforcement,
+        &mut violations,
+    );
 
     XRGridDecision {
         xr_zone,
         risk,
         action,
-        hex_stamp: maybe_stamp,
+        // The stamp may predate this window's governance; never let it stay
+        // steerable, and drop it once governance no longer allows roaming.
+        hex_stamp: maybe_stamp.and_then(|stamp| stamp.constrain_to(&state.gov)),
+        sleep_gate,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ghostnet::spectral::RegionBucketing;
+    use crate::governance::GovernanceMode;
+
+    /// Strict, no sleep channel; `load` drives fear and psych load alike.
+    fn state(mode: GovernanceMode, haunt: f64, load: f32) -> XRGridState {
+        let free = mode == GovernanceMode::ActiveFree;
+        XRGridState {
+            region_session: RegionSessionKey::from_raw(0.0, 0.0, 0, "s", &RegionBucketing::FIVE_MINUTES)
+                .unwrap(),
+            haunt: Some(HauntDensityScore::new(haunt)),
+            fear_level: load,
+            fear_rate_norm: load,
+            psych_load: load,
+            gov: SpectralGovernanceAudit {
+                spectralroamingactive: free,
+                noninterferencerequired: free,
+                spectralquantificationactive: true,
+                soulmodelingforbidden: true,
+                channelactive: true,
+                bindingstrength: 0.5,
+                mode,
+            },
+            enforcement: EnforcementMode::Strict,
+            sleep: None,
+            sleep_gate: SleepGateParams::default(),
+        }
+    }
+
+    fn decide(state: &XRGridState) -> XRGridDecision {
+        decide_xr_route_for_state(state, state.haunt.as_ref(), None)
+    }
+
+    #[test]
+    fn test_advisory_mode_annotates_soft_gates_only() {
+        let state = XRGridState {
+            enforcement: EnforcementMode::Advisory,
+            ..state(GovernanceMode::ActiveFree, 0.15, 1.0)
+        };
+        let decision = decide(&state);
+        // High risk is only annotated; non-interference is non-waivable.
+        assert_eq!(decision.action, XRRouteAction::ObserveOnly);
+        let gates: Vec<_> = decision
+            .violations
+            .iter()
+            .map(|v| (v.gate.as_str(), v.enforced))
+            .collect();
+        assert_eq!(
+            gates,
+            [("xr_grid.high_risk", false), ("xr_grid.non_interference", true)]
+        );
+
+        let disabled = XRGridState {
+            enforcement: EnforcementMode::Disabled,
+            ..state
+        };
+        let decision = decide(&disabled);
+        assert_eq!(decision.action, XRRouteAction::ObserveOnly);
+        assert_eq!(decision.violations.len(), 1);
+        assert_eq!(decision.violations[0].gate, "xr_grid.non_interference");
+    }
+
+    #[test]
+    fn test_closed_sleep_gate_refuses_full_interaction() {
+        let state = XRGridState {
+            sleep: Some(SleepStateSnapshot::new(0.0, 0.9, 0.0, 0.1)),
+            ..state(GovernanceMode::ActiveGoverned, 0.1, 0.0)
+        };
+        let decision = decide(&state);
+        assert_eq!(decision.action, XRRouteAction::FullInteraction);
+        assert!(decision.sleep_gate.as_ref().unwrap().open);
+
+        let light = XRGridState {
+            sleep: Some(SleepStateSnapshot::new(0.6, 0.3, 0.0, 0.2)),
+            ..state
+        };
+        let decision = decide(&light);
+        assert_eq!(decision.action, XRRouteAction::GuardedInteraction);
+        assert_eq!(decision.violations[0].gate, "xr_grid.sleep_gate");
+        assert_eq!(decision.sleep_gate.unwrap().reasons.len(), 2);
+
+        let advisory = XRGridState {
+            enforcement: EnforcementMode::Advisory,
+            ..light
+        };
+        let decision = decide(&advisory);
+        assert_eq!(decision.action, XRRouteAction::GuardedInteraction);
+        assert!(decision.violations[0].enforced);
     }
 }